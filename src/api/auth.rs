@@ -0,0 +1,443 @@
+//! Authentication, authorization and rate limiting of the API requests.
+//!
+//! If API keys are configured, every API request has to provide a key, either as a bearer token
+//! (`Authorization: Bearer {key}`) or in the `X-API-Key` header. Keys with the `read` scope can access read endpoints,
+//! while transaction submission requires the `submit` scope. Health endpoints are not authenticated.
+//!
+//! Requests are rate limited per API key and per client IP address, if limits are configured.
+//! Failed requests are responded with `401 Unauthorized`, `403 Forbidden` or `429 Too Many Requests`,
+//! in the v2 [`Error`] format. Rate limited responses have the `Retry-After` header, computed from the limit refill rate.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::HashMap,
+	net::{IpAddr, SocketAddr},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+use warp::{
+	http::{header::RETRY_AFTER, HeaderValue, Method},
+	path::FullPath,
+	Filter, Rejection, Reply,
+};
+
+use crate::{
+	api::v2::types::Error,
+	types::{AuthConfig, Secret},
+};
+
+/// Maximum number of tracked rate limit buckets, before idle buckets are removed
+const MAX_BUCKETS: usize = 10_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+	/// Access to the read endpoints and websocket subscriptions
+	Read,
+	/// Access to the transaction submission
+	Submit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiKey {
+	pub key: Secret<String>,
+	pub scopes: Vec<Scope>,
+	/// Maximum number of requests per minute, overrides the default limit per API key
+	pub rate_limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthError {
+	Unauthorized,
+	Forbidden,
+	/// Rate limit is exceeded, request can be retried after the given duration
+	TooManyRequests(Duration),
+}
+
+impl warp::reject::Reject for AuthError {}
+
+impl From<AuthError> for Error {
+	fn from(error: AuthError) -> Self {
+		match error {
+			AuthError::Unauthorized => Error::unauthorized(),
+			AuthError::Forbidden => Error::forbidden(None),
+			AuthError::TooManyRequests(_) => Error::too_many_requests(),
+		}
+	}
+}
+
+struct Bucket {
+	/// Maximum number of tokens, equal to the limit per minute
+	capacity: f64,
+	tokens: f64,
+	updated_at: Instant,
+}
+
+impl Bucket {
+	/// Tokens refilled per second
+	fn refill_rate(&self) -> f64 {
+		self.capacity / 60.0
+	}
+
+	fn tokens_at(&self, now: Instant) -> f64 {
+		let elapsed = now.saturating_duration_since(self.updated_at);
+		(self.tokens + elapsed.as_secs_f64() * self.refill_rate()).min(self.capacity)
+	}
+}
+
+/// Token bucket rate limiter, with buckets refilled continuously up to the limit per minute
+#[derive(Clone, Default)]
+struct RateLimiter(Arc<Mutex<HashMap<String, Bucket>>>);
+
+impl RateLimiter {
+	/// Acquires token from the bucket with the given id.
+	/// If bucket is empty, returns duration after which the next token is available.
+	fn try_acquire(&self, id: &str, limit: u32, now: Instant) -> Result<(), Duration> {
+		let mut buckets = self.0.lock().expect("Rate limiter lock can be acquired");
+
+		if buckets.len() >= MAX_BUCKETS {
+			buckets.retain(|_, bucket| bucket.tokens_at(now) < bucket.capacity);
+		}
+
+		let capacity = f64::from(limit);
+		let bucket = buckets.entry(id.to_string()).or_insert(Bucket {
+			capacity,
+			tokens: capacity,
+			updated_at: now,
+		});
+		bucket.tokens = bucket.tokens_at(now);
+		bucket.updated_at = now;
+
+		if bucket.tokens < 1.0 {
+			let seconds = (1.0 - bucket.tokens) / bucket.refill_rate();
+			return Err(Duration::from_secs_f64(seconds));
+		}
+		bucket.tokens -= 1.0;
+		Ok(())
+	}
+}
+
+/// Scopes granted to the API request
+#[derive(Clone, Debug)]
+pub struct Access(Option<Vec<Scope>>);
+
+impl Access {
+	pub fn allows(&self, scope: Scope) -> bool {
+		match &self.0 {
+			None => true,
+			Some(scopes) => scopes.contains(&scope),
+		}
+	}
+}
+
+/// SHA-256 hash of the API key, so plain keys are not used as lookup keys or rate limit bucket IDs
+type KeyHash = [u8; 32];
+
+fn key_hash(key: &str) -> KeyHash {
+	Sha256::digest(key.as_bytes()).into()
+}
+
+#[derive(Clone, Default)]
+pub struct Auth {
+	keys: Arc<HashMap<KeyHash, ApiKey>>,
+	key_rate_limit: Option<u32>,
+	ip_rate_limit: Option<u32>,
+	key_limiter: RateLimiter,
+	ip_limiter: RateLimiter,
+}
+
+impl Auth {
+	pub fn new(cfg: AuthConfig) -> Self {
+		let keys = cfg
+			.keys
+			.into_iter()
+			.map(|api_key| (key_hash(api_key.key.expose()), api_key))
+			.collect();
+
+		Auth {
+			keys: Arc::new(keys),
+			key_rate_limit: cfg.key_rate_limit,
+			ip_rate_limit: cfg.ip_rate_limit,
+			..Default::default()
+		}
+	}
+
+	/// Authentication is enabled if at least one API key is configured
+	fn is_enabled(&self) -> bool {
+		!self.keys.is_empty()
+	}
+
+	pub fn access(&self, key: Option<&str>) -> Access {
+		if !self.is_enabled() {
+			return Access(None);
+		}
+		let scopes = key.and_then(|key| self.keys.get(&key_hash(key)));
+		Access(Some(
+			scopes
+				.map(|api_key| api_key.scopes.clone())
+				.unwrap_or_default(),
+		))
+	}
+
+	fn authorize(
+		&self,
+		scope: Option<Scope>,
+		key: Option<&str>,
+		ip: Option<IpAddr>,
+		now: Instant,
+	) -> Result<(), AuthError> {
+		let Some(scope) = scope else {
+			return Ok(());
+		};
+
+		if let (Some(limit), Some(ip)) = (self.ip_rate_limit, ip) {
+			self.ip_limiter
+				.try_acquire(&ip.to_string(), limit, now)
+				.map_err(AuthError::TooManyRequests)?;
+		}
+
+		if !self.is_enabled() {
+			return Ok(());
+		}
+
+		let (hash, api_key) = key
+			.map(key_hash)
+			.and_then(|hash| self.keys.get_key_value(&hash))
+			.ok_or(AuthError::Unauthorized)?;
+
+		if !api_key.scopes.contains(&scope) {
+			return Err(AuthError::Forbidden);
+		}
+
+		if let Some(limit) = api_key.rate_limit.or(self.key_rate_limit) {
+			self.key_limiter
+				.try_acquire(&hex::encode(hash), limit, now)
+				.map_err(AuthError::TooManyRequests)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Returns scope required to access the given path, health endpoints are neither authenticated nor rate limited
+fn required_scope(method: &Method, path: &str) -> Option<Scope> {
+	if path == "/health" || path.starts_with("/health/") {
+		return None;
+	}
+	if method == Method::POST && path.starts_with("/v2/submit") {
+		return Some(Scope::Submit);
+	}
+	Some(Scope::Read)
+}
+
+/// Extracts API key from the `X-API-Key` header, or from the bearer token in the `Authorization` header
+fn api_key() -> impl Filter<Extract = (Option<String>,), Error = Rejection> + Clone {
+	warp::header::optional::<String>("x-api-key")
+		.and(warp::header::optional::<String>("authorization"))
+		.map(|api_key: Option<String>, authorization: Option<String>| {
+			api_key.or_else(|| {
+				authorization.and_then(|value| {
+					value
+						.strip_prefix("Bearer ")
+						.map(|token| token.trim().to_string())
+				})
+			})
+		})
+}
+
+/// Authorizes request and applies rate limits, before request is routed
+pub fn authorize(auth: Auth) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+	warp::method()
+		.and(warp::path::full())
+		.and(api_key())
+		.and(warp::addr::remote())
+		.and_then(
+			move |method: Method, path: FullPath, key: Option<String>, addr: Option<SocketAddr>| {
+				let scope = required_scope(&method, path.as_str());
+				let ip = addr.map(|addr| addr.ip());
+				let result = auth.authorize(scope, key.as_deref(), ip, Instant::now());
+				async move { result.map_err(warp::reject::custom) }
+			},
+		)
+		.untuple_one()
+}
+
+/// Extracts scopes granted to the request, used to authorize websocket requests
+pub fn with_access(auth: Auth) -> impl Filter<Extract = (Access,), Error = Rejection> + Clone {
+	api_key().map(move |key: Option<String>| auth.access(key.as_deref()))
+}
+
+pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+	let Some(&error) = rejection.find::<AuthError>() else {
+		return Err(rejection);
+	};
+	let mut response = Error::from(error).into_response();
+	if let AuthError::TooManyRequests(retry_after) = error {
+		// Retry-After is in whole seconds, rounded up so retried request is not limited again
+		let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+		response
+			.headers_mut()
+			.insert(RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+	}
+	Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		handle_rejection, required_scope, ApiKey, Auth, AuthError, RateLimiter, Scope, MAX_BUCKETS,
+	};
+	use crate::types::AuthConfig;
+	use std::{
+		net::{IpAddr, Ipv4Addr},
+		time::{Duration, Instant},
+	};
+	use test_case::test_case;
+	use warp::{
+		http::{header::RETRY_AFTER, Method, StatusCode},
+		Reply,
+	};
+
+	const IP: Option<IpAddr> = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+	fn auth(key_rate_limit: Option<u32>, ip_rate_limit: Option<u32>) -> Auth {
+		let api_key = |key: &str, scopes: Vec<Scope>| ApiKey {
+			key: key.to_string().into(),
+			scopes,
+			rate_limit: None,
+		};
+		Auth::new(AuthConfig {
+			keys: vec![
+				api_key("reader", vec![Scope::Read]),
+				api_key("submitter", vec![Scope::Read, Scope::Submit]),
+			],
+			key_rate_limit,
+			ip_rate_limit,
+		})
+	}
+
+	#[test_case(Method::GET, "/health" => None ; "Health")]
+	#[test_case(Method::GET, "/health/ready" => None ; "Readiness")]
+	#[test_case(Method::GET, "/v1/status" => Some(Scope::Read) ; "V1 status")]
+	#[test_case(Method::GET, "/v2/blocks/1" => Some(Scope::Read) ; "V2 block")]
+	#[test_case(Method::POST, "/v2/subscriptions" => Some(Scope::Read) ; "V2 subscriptions")]
+	#[test_case(Method::POST, "/v2/submit" => Some(Scope::Submit) ; "V2 submit")]
+	fn scope_required_for_path(method: Method, path: &str) -> Option<Scope> {
+		required_scope(&method, path)
+	}
+
+	#[test_case(Some(Scope::Read), None => Err(AuthError::Unauthorized) ; "Missing key")]
+	#[test_case(Some(Scope::Read), Some("unknown") => Err(AuthError::Unauthorized) ; "Unknown key")]
+	#[test_case(Some(Scope::Read), Some("reader") => Ok(()) ; "Read with read scope")]
+	#[test_case(Some(Scope::Submit), Some("reader") => Err(AuthError::Forbidden) ; "Submit with read scope")]
+	#[test_case(Some(Scope::Submit), Some("submitter") => Ok(()) ; "Submit with submit scope")]
+	#[test_case(None, None => Ok(()) ; "Not authenticated endpoint")]
+	fn authorize(scope: Option<Scope>, key: Option<&str>) -> Result<(), AuthError> {
+		auth(None, None).authorize(scope, key, IP, Instant::now())
+	}
+
+	#[test]
+	fn authorize_when_disabled() {
+		let auth = Auth::default();
+		let now = Instant::now();
+		assert_eq!(auth.authorize(Some(Scope::Submit), None, IP, now), Ok(()));
+		assert!(auth.access(None).allows(Scope::Submit));
+	}
+
+	#[test]
+	fn access_scopes() {
+		let auth = auth(None, None);
+		assert!(auth.access(Some("reader")).allows(Scope::Read));
+		assert!(!auth.access(Some("reader")).allows(Scope::Submit));
+		assert!(auth.access(Some("submitter")).allows(Scope::Submit));
+		assert!(!auth.access(None).allows(Scope::Read));
+	}
+
+	#[test]
+	fn rate_limit_per_key() {
+		let auth = auth(Some(2), None);
+		let now = Instant::now();
+		let read = |key| auth.authorize(Some(Scope::Read), Some(key), IP, now);
+		assert_eq!(read("reader"), Ok(()));
+		assert_eq!(read("reader"), Ok(()));
+		assert!(matches!(read("reader"), Err(AuthError::TooManyRequests(_))));
+		assert_eq!(read("submitter"), Ok(()));
+	}
+
+	#[test]
+	fn rate_limit_per_ip() {
+		let auth = auth(None, Some(1));
+		let now = Instant::now();
+		let read = |key| auth.authorize(Some(Scope::Read), Some(key), IP, now);
+		assert_eq!(read("reader"), Ok(()));
+		assert!(matches!(
+			read("submitter"),
+			Err(AuthError::TooManyRequests(_))
+		));
+		assert_eq!(auth.authorize(None, None, IP, now), Ok(()));
+	}
+
+	#[test]
+	fn rate_limiter_refills_tokens() {
+		let limiter = RateLimiter::default();
+		let now = Instant::now();
+		assert!(limiter.try_acquire("id", 60, now).is_ok());
+		for _ in 0..59 {
+			assert!(limiter.try_acquire("id", 60, now).is_ok());
+		}
+		assert!(limiter.try_acquire("id", 60, now).is_err());
+		assert!(limiter
+			.try_acquire("id", 60, now + Duration::from_secs(1))
+			.is_ok());
+		assert!(limiter
+			.try_acquire("id", 60, now + Duration::from_secs(1))
+			.is_err());
+	}
+
+	#[test]
+	fn rate_limiter_retry_after_refill_rate() {
+		let limiter = RateLimiter::default();
+		let now = Instant::now();
+		assert!(limiter.try_acquire("id", 2, now).is_ok());
+		assert!(limiter.try_acquire("id", 2, now).is_ok());
+		// Two tokens per minute are refilled every 30 seconds
+		let retry_after = limiter.try_acquire("id", 2, now).unwrap_err();
+		assert!((retry_after.as_secs_f64() - 30.0).abs() < 0.001);
+
+		let later = now + Duration::from_secs(20);
+		let retry_after = limiter.try_acquire("id", 2, later).unwrap_err();
+		assert!((retry_after.as_secs_f64() - 10.0).abs() < 0.001);
+	}
+
+	#[test]
+	fn rate_limiter_evicts_only_full_buckets() {
+		let limiter = RateLimiter::default();
+		let now = Instant::now();
+		for i in 0..MAX_BUCKETS - 1 {
+			assert!(limiter.try_acquire(&format!("idle-{i}"), 60, now).is_ok());
+		}
+
+		let later = now + Duration::from_secs(60);
+		for _ in 0..30 {
+			assert!(limiter.try_acquire("busy", 60, later).is_ok());
+		}
+
+		// Bucket with a lower limit triggers eviction, which uses capacities of the evicted buckets
+		assert!(limiter.try_acquire("new", 1, later).is_ok());
+		assert_eq!(limiter.0.lock().unwrap().len(), 2);
+		for _ in 0..30 {
+			assert!(limiter.try_acquire("busy", 60, later).is_ok());
+		}
+		assert!(limiter.try_acquire("busy", 60, later).is_err());
+	}
+
+	#[tokio::test]
+	async fn too_many_requests_has_retry_after_header() {
+		let rejection =
+			warp::reject::custom(AuthError::TooManyRequests(Duration::from_millis(2500)));
+		let response = handle_rejection(rejection).await.unwrap().into_response();
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "3");
+	}
+}
@@ -0,0 +1,321 @@
+//! Liveness and readiness checks.
+//!
+//! # Endpoints
+//!
+//! * `/health/live` - fails if client process is not responsive (shared state cannot be accessed), without checking dependencies
+//! * `/health/ready` - fails if any of the dependency checks fails (RPC connection, DHT peers, database),
+//!   or if client does not keep up with the network
+//!
+//! Both endpoints respond with JSON detail per check, and with `503 Service Unavailable` status code if any check fails.
+
+use rocksdb::DB;
+use serde::Serialize;
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+use warp::{http::StatusCode, Filter, Reply};
+
+use crate::{
+	consts::EXPECTED_BLOCK_TIME,
+	data,
+	network::p2p,
+	types::{HealthConfig, OptionBlockRange, State},
+};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+	Pass,
+	Fail,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Check {
+	name: &'static str,
+	status: Status,
+	message: String,
+}
+
+impl Check {
+	fn new(name: &'static str, passed: bool, message: String) -> Self {
+		let status = if passed { Status::Pass } else { Status::Fail };
+		Check {
+			name,
+			status,
+			message,
+		}
+	}
+}
+
+#[derive(Serialize, Debug)]
+pub struct Report {
+	status: Status,
+	checks: Vec<Check>,
+}
+
+impl Report {
+	fn new(checks: Vec<Check>) -> Self {
+		let status = match checks.iter().all(|check| check.status == Status::Pass) {
+			true => Status::Pass,
+			false => Status::Fail,
+		};
+		Report { status, checks }
+	}
+}
+
+impl Reply for Report {
+	fn into_response(self) -> warp::reply::Response {
+		let status = match self.status {
+			Status::Pass => StatusCode::OK,
+			Status::Fail => StatusCode::SERVICE_UNAVAILABLE,
+		};
+		warp::reply::with_status(warp::reply::json(&self), status).into_response()
+	}
+}
+
+/// Client state, collected before evaluating the checks
+struct Snapshot {
+	rpc_connected: bool,
+	since_latest_header: Option<Duration>,
+	finality_synced: bool,
+	dht_peers: Result<usize, String>,
+	db_writable: Result<(), String>,
+	/// Number of verified headers not yet processed by the app client
+	app_lag: Option<u32>,
+}
+
+fn rpc_connected(snapshot: &Snapshot) -> Check {
+	let message = match snapshot.rpc_connected {
+		true => "RPC event loop is connected to the node".to_string(),
+		false => "RPC event loop is not connected to the node".to_string(),
+	};
+	Check::new("rpc_connected", snapshot.rpc_connected, message)
+}
+
+fn db_writable(snapshot: &Snapshot) -> Check {
+	match &snapshot.db_writable {
+		Ok(()) => Check::new("db_writable", true, "Database is writable".to_string()),
+		Err(error) => Check::new("db_writable", false, error.clone()),
+	}
+}
+
+fn header_received(snapshot: &Snapshot, cfg: &HealthConfig) -> Check {
+	let max_delay = EXPECTED_BLOCK_TIME * cfg.max_header_delay;
+	match snapshot.since_latest_header {
+		None => Check::new("header_received", false, "No header received".to_string()),
+		Some(elapsed) => Check::new(
+			"header_received",
+			elapsed <= max_delay,
+			format!(
+				"Latest header received {}s ago (max {}s)",
+				elapsed.as_secs(),
+				max_delay.as_secs()
+			),
+		),
+	}
+}
+
+fn finality_synced(snapshot: &Snapshot) -> Check {
+	let message = match snapshot.finality_synced {
+		true => "Finality is synced".to_string(),
+		false => "Finality is not synced".to_string(),
+	};
+	Check::new("finality_synced", snapshot.finality_synced, message)
+}
+
+fn dht_peers(snapshot: &Snapshot, cfg: &HealthConfig) -> Check {
+	match &snapshot.dht_peers {
+		Ok(peers) => Check::new(
+			"dht_peers",
+			*peers >= cfg.min_dht_peers,
+			format!(
+				"{peers} peers in DHT routing table (min {})",
+				cfg.min_dht_peers
+			),
+		),
+		Err(error) => Check::new("dht_peers", false, error.clone()),
+	}
+}
+
+fn app_lag(snapshot: &Snapshot, cfg: &HealthConfig) -> Check {
+	match snapshot.app_lag {
+		None => Check::new("app_lag", true, "App client is disabled".to_string()),
+		Some(lag) => Check::new(
+			"app_lag",
+			lag <= cfg.max_app_lag,
+			format!(
+				"App client is {lag} blocks behind (max {})",
+				cfg.max_app_lag
+			),
+		),
+	}
+}
+
+/// Liveness only checks that the process responds and that its shared state is accessible,
+/// since failing dependencies (node, DHT or database) are not fixed by restarting the client.
+fn liveness(state_accessible: Result<(), String>) -> Report {
+	let check = match state_accessible {
+		Ok(()) => Check::new("responsive", true, "Client is responsive".to_string()),
+		Err(error) => Check::new("responsive", false, error),
+	};
+	Report::new(vec![check])
+}
+
+fn readiness(snapshot: &Snapshot, cfg: &HealthConfig) -> Report {
+	Report::new(vec![
+		rpc_connected(snapshot),
+		header_received(snapshot, cfg),
+		finality_synced(snapshot),
+		dht_peers(snapshot, cfg),
+		db_writable(snapshot),
+		app_lag(snapshot, cfg),
+	])
+}
+
+#[derive(Clone)]
+pub struct Health {
+	pub cfg: HealthConfig,
+	pub state: Arc<Mutex<State>>,
+	pub db: Arc<DB>,
+	pub p2p_client: p2p::Client,
+}
+
+impl Health {
+	fn state_accessible(&self) -> Result<(), String> {
+		self.state
+			.lock()
+			.map(|_| ())
+			.map_err(|_| "Client state is poisoned by a failed task".to_string())
+	}
+
+	async fn snapshot(&self) -> Snapshot {
+		let (rpc_connected, since_latest_header, finality_synced, app_lag) = {
+			let state = self.state.lock().expect("State lock can be acquired");
+			let app_lag = self.cfg.app_client.then(|| {
+				let verified = state.header_verified.last().unwrap_or(0);
+				let processed = state
+					.data_verified
+					.last()
+					.or(state.header_verified.first())
+					.unwrap_or(0);
+				verified.saturating_sub(processed)
+			});
+			(
+				state.rpc_connected,
+				state.latest_header_received_at.map(|at| at.elapsed()),
+				state.finality_synced,
+				app_lag,
+			)
+		};
+
+		Snapshot {
+			rpc_connected,
+			since_latest_header,
+			finality_synced,
+			dht_peers: self
+				.p2p_client
+				.count_dht_entries()
+				.await
+				.map_err(|error| format!("Cannot count DHT peers: {error}")),
+			db_writable: data::store_health_check_in_db(self.db.clone())
+				.map_err(|error| format!("{error:#}")),
+			app_lag,
+		}
+	}
+}
+
+async fn live(health: Health) -> Report {
+	liveness(health.state_accessible())
+}
+
+async fn ready(health: Health) -> Report {
+	readiness(&health.snapshot().await, &health.cfg)
+}
+
+pub fn routes(
+	health: Health,
+) -> impl Filter<Extract = impl Reply, Error = warp::Rejection> + Clone {
+	let live_health = health.clone();
+	let live_route = warp::path!("health" / "live")
+		.and(warp::get())
+		.and(warp::any().map(move || live_health.clone()))
+		.then(live);
+
+	let ready_route = warp::path!("health" / "ready")
+		.and(warp::get())
+		.and(warp::any().map(move || health.clone()))
+		.then(ready);
+
+	live_route.or(ready_route)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{liveness, readiness, Snapshot, Status};
+	use crate::types::HealthConfig;
+	use std::time::Duration;
+	use test_case::test_case;
+
+	fn cfg() -> HealthConfig {
+		HealthConfig {
+			max_header_delay: 3,
+			min_dht_peers: 1,
+			max_app_lag: 10,
+			app_client: true,
+		}
+	}
+
+	fn healthy() -> Snapshot {
+		Snapshot {
+			rpc_connected: true,
+			since_latest_header: Some(Duration::from_secs(10)),
+			finality_synced: true,
+			dht_peers: Ok(5),
+			db_writable: Ok(()),
+			app_lag: Some(1),
+		}
+	}
+
+	#[test]
+	fn healthy_client_is_live_and_ready() {
+		assert_eq!(liveness(Ok(())).status, Status::Pass);
+		assert_eq!(readiness(&healthy(), &cfg()).status, Status::Pass);
+	}
+
+	#[test]
+	fn client_with_inaccessible_state_is_not_live() {
+		let report = liveness(Err("error".to_string()));
+		assert_eq!(report.status, Status::Fail);
+		assert_eq!(report.checks[0].name, "responsive");
+	}
+
+	#[test_case(Snapshot { rpc_connected: false, ..healthy() }, "rpc_connected" ; "RPC disconnected")]
+	#[test_case(Snapshot { db_writable: Err("error".to_string()), ..healthy() }, "db_writable" ; "DB not writable")]
+	#[test_case(Snapshot { since_latest_header: None, ..healthy() }, "header_received" ; "No header received")]
+	#[test_case(Snapshot { since_latest_header: Some(Duration::from_secs(61)), ..healthy() }, "header_received" ; "Header delayed")]
+	#[test_case(Snapshot { finality_synced: false, ..healthy() }, "finality_synced" ; "Finality not synced")]
+	#[test_case(Snapshot { dht_peers: Ok(0), ..healthy() }, "dht_peers" ; "No DHT peers")]
+	#[test_case(Snapshot { dht_peers: Err("error".to_string()), ..healthy() }, "dht_peers" ; "DHT peers unknown")]
+	#[test_case(Snapshot { app_lag: Some(11), ..healthy() }, "app_lag" ; "App client lagging")]
+	fn client_is_not_ready(snapshot: Snapshot, failed: &str) {
+		let report = readiness(&snapshot, &cfg());
+		assert_eq!(report.status, Status::Fail);
+		let failed_checks = report
+			.checks
+			.iter()
+			.filter(|check| check.status == Status::Fail)
+			.map(|check| check.name)
+			.collect::<Vec<_>>();
+		assert_eq!(failed_checks, vec![failed]);
+	}
+
+	#[test]
+	fn app_lag_passes_when_app_client_is_disabled() {
+		let snapshot = Snapshot {
+			app_lag: None,
+			..healthy()
+		};
+		assert_eq!(readiness(&snapshot, &cfg()).status, Status::Pass);
+	}
+}
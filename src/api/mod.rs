@@ -1,4 +1,7 @@
+pub mod auth;
 pub mod common;
+pub mod health;
 pub mod server;
+mod tls;
 mod v1;
 pub mod v2;
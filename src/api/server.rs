@@ -7,22 +7,33 @@
 //! * `/v1/latest_block` - returns latest processed block
 //! * `/v1/confidence/{block_number}` - returns calculated confidence for a given block number
 //! * `/v1/appdata/{block_number}` - returns decoded extrinsic data for configured app_id and given block number
+//! * `/health/live` and `/health/ready` - returns liveness and readiness checks (see [`health`](crate::api::health))
+//!
+//! API requests are authenticated and rate limited if configured (see [`auth`](crate::api::auth)).
+//! If TLS is configured, server is served over HTTPS and WSS.
 
 use crate::api::v2;
 use crate::{
-	api::v1,
-	network::rpc::{self, Node},
+	api::{auth, health, tls, v1},
+	cell_cache::CellCache,
+	network::{
+		p2p,
+		rpc::{self, Node},
+	},
+	proof,
 	types::{RuntimeConfig, State},
 };
 
 use anyhow::Context;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
 use rocksdb::DB;
 use std::{
 	net::SocketAddr,
 	str::FromStr,
 	sync::{Arc, Mutex},
 };
-use tracing::info;
+use tokio::sync::broadcast;
+use tracing::{error, info};
 use warp::{Filter, Reply};
 
 pub struct Server {
@@ -33,7 +44,13 @@ pub struct Server {
 	pub network_version: String,
 	pub node: Node,
 	pub node_client: rpc::Client,
+	pub p2p_client: p2p::Client,
 	pub ws_clients: v2::types::WsClients,
+	pub events: broadcast::Sender<v2::types::PublishMessage>,
+	pub submissions: broadcast::Sender<v2::types::Submission>,
+	pub cell_cache: CellCache,
+	pub verifier: proof::Verifier,
+	pub pp: Arc<PublicParameters>,
 }
 
 fn health_route() -> impl Filter<Extract = impl Reply, Error = warp::Rejection> + Clone {
@@ -43,6 +60,17 @@ fn health_route() -> impl Filter<Extract = impl Reply, Error = warp::Rejection>
 		.map(|_| warp::reply::with_status("", warp::http::StatusCode::OK))
 }
 
+/// Allows any origin if origins contain "*", otherwise only the given origins are allowed
+fn cors(origins: &[String]) -> warp::cors::Builder {
+	let cors = if origins.iter().any(|origin| origin == "*") {
+		warp::cors().allow_any_origin()
+	} else {
+		warp::cors().allow_origins(origins.iter().map(String::as_str))
+	};
+	cors.allow_headers(vec!["content-type", "authorization", "x-api-key"])
+		.allow_methods(vec!["GET", "POST", "DELETE"])
+}
+
 
 
 impl Server {
@@ -51,10 +79,20 @@ impl Server {
 		let RuntimeConfig {
 			http_server_host: host,
 			http_server_port: port,
+			http_server_tls,
 			app_id,
 			..
 		} = self.cfg.clone();
 
+		let health_api = health::routes(health::Health {
+			cfg: (&self.cfg).into(),
+			state: self.state.clone(),
+			db: self.db.clone(),
+			p2p_client: self.p2p_client.clone(),
+		});
+		let auth = auth::Auth::new((&self.cfg).into());
+		let cors = cors(&self.cfg.api_cors_allowed_origins);
+
 		let v1_api = v1::routes(self.db.clone(), app_id, self.state.clone());
 		let v2_api = v2::routes(
 			self.version.clone(),
@@ -63,21 +101,34 @@ impl Server {
 			self.state.clone(),
 			self.cfg,
 			self.node_client.clone(),
+			self.p2p_client.clone(),
+			self.cell_cache.clone(),
+			self.verifier.clone(),
+			self.pp.clone(),
 			self.ws_clients.clone(),
+			self.events.clone(),
+			self.submissions.clone(),
 			crate::data::RocksDB(self.db.clone()),
+			auth.clone(),
 		);
 
-		let cors = warp::cors()
-			.allow_any_origin()
-			.allow_header("content-type")
-			.allow_methods(vec!["GET", "POST", "DELETE"]);
-
-		let routes = health_route().or(v1_api).or(v2_api).with(cors);
+		let routes = auth::authorize(auth)
+			.and(health_api.or(health_route()).or(v1_api).or(v2_api))
+			.recover(auth::handle_rejection)
+			.with(cors);
 
 		let addr = SocketAddr::from_str(format!("{host}:{port}").as_str())
 			.context("Unable to parse host address from config")
 			.unwrap();
-		info!("RPC running on http://{host}:{port}");
-		warp::serve(routes).run(addr).await;
+		let Some(tls_config) = http_server_tls else {
+			info!("RPC running on http://{host}:{port}");
+			warp::serve(routes).run(addr).await;
+			return;
+		};
+
+		info!("RPC running on https://{host}:{port}");
+		if let Err(error) = tls::serve(routes, addr, tls_config).await {
+			error!("Cannot run RPC over TLS: {error:#}");
+		}
 	}
 }
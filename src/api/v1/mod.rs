@@ -10,7 +10,7 @@ use warp::{Filter, Rejection, Reply};
 
 mod ffi_api;
 mod handlers;
-mod types;
+pub(crate) mod types;
 
 fn with_state(
 	state: Arc<Mutex<State>>,
@@ -1,5 +1,6 @@
 use avail_subxt::primitives::AppUncheckedExtrinsic;
 use hyper::StatusCode;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
@@ -14,7 +15,7 @@ where
 	Error(anyhow::Error),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[repr(C)]
 pub struct ConfidenceResponse {
 	pub block: u32,
@@ -30,25 +31,26 @@ pub struct FfiConfidenceResponse {
 	pub serialised_confidence: *const u8,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum Extrinsics {
-	Encoded(Vec<AppUncheckedExtrinsic>),
+	Encoded(#[schemars(with = "Vec<String>")] Vec<AppUncheckedExtrinsic>),
 	Decoded(Vec<String>),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct ExtrinsicsDataResponse {
 	pub block: u32,
 	pub extrinsics: Extrinsics,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct LatestBlockResponse {
 	pub latest_block: u32,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[schemars(rename = "StatusV1")]
 pub struct Status {
 	pub block_num: u32,
 	pub confidence: f64,
@@ -0,0 +1,94 @@
+use crate::{
+	cell_cache::CellCache,
+	network::{p2p, rpc},
+	proof::{Priority, Verifier},
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use kate_recovery::{
+	data::Cell,
+	matrix::{Dimensions, Position},
+};
+use sp_core::H256;
+
+#[async_trait]
+pub trait Fetch {
+	/// Fetches cells with proofs from the local cache, DHT and RPC, in that order.
+	/// Cells fetched from the DHT and RPC are verified against the commitments before caching.
+	/// Returns fetched cells and positions of the cells which cannot be fetched or verified.
+	async fn fetch(
+		&self,
+		block_number: u32,
+		block_hash: H256,
+		dimensions: Dimensions,
+		commitments: &[[u8; 48]],
+		positions: &[Position],
+	) -> Result<(Vec<Cell>, Vec<Position>)>;
+}
+
+#[derive(Clone)]
+pub struct Fetcher {
+	pub cell_cache: CellCache,
+	pub p2p_client: p2p::Client,
+	pub rpc_client: rpc::Client,
+	pub disable_rpc: bool,
+	pub max_cells_per_rpc: usize,
+	pub verifier: Verifier,
+}
+
+#[async_trait]
+impl Fetch for Fetcher {
+	async fn fetch(
+		&self,
+		block_number: u32,
+		block_hash: H256,
+		dimensions: Dimensions,
+		commitments: &[[u8; 48]],
+		positions: &[Position],
+	) -> Result<(Vec<Cell>, Vec<Position>)> {
+		let (mut cells, unfetched) = self.cell_cache.get(block_number, positions);
+		if unfetched.is_empty() {
+			return Ok((cells, unfetched));
+		}
+
+		let (mut fetched, unfetched) = self
+			.p2p_client
+			.fetch_cells_from_dht(block_number, &unfetched)
+			.await;
+
+		if !unfetched.is_empty() && !self.disable_rpc {
+			for positions in unfetched.chunks(self.max_cells_per_rpc) {
+				let rpc_fetched = self
+					.rpc_client
+					.request_kate_proof(block_hash, positions)
+					.await
+					.context("Failed to fetch cells from node RPC")?;
+				fetched.extend(rpc_fetched);
+			}
+		}
+
+		let (verified, _) = self
+			.verifier
+			.verify(
+				Priority::Sync,
+				block_number,
+				dimensions,
+				&fetched,
+				commitments,
+			)
+			.await
+			.context("Failed to verify fetched cells")?;
+
+		fetched.retain(|cell| verified.contains(&cell.position));
+		self.cell_cache.insert(block_number, &fetched);
+		cells.extend(fetched);
+
+		let missing = positions
+			.iter()
+			.filter(|&position| !cells.iter().any(|cell| cell.position.eq(position)))
+			.cloned()
+			.collect::<Vec<_>>();
+
+		Ok((cells, missing))
+	}
+}
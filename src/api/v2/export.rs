@@ -0,0 +1,50 @@
+use super::types::{data_transactions, DataResponse};
+use crate::{data::Database, utils::calculate_confidence};
+use anyhow::{Context, Result};
+
+/// Iterates over blocks in the given inclusive range and yields application data
+/// of each block, along with its confidence. Blocks without application data are skipped.
+/// Since every yielded response contains block number, interrupted export
+/// can be resumed from the block following the last received one.
+pub fn app_data(
+	db: impl Database,
+	app_id: u32,
+	from: u32,
+	to: u32,
+) -> impl Iterator<Item = Result<DataResponse>> {
+	(from..=to)
+		.filter_map(move |block_number| block_app_data(&db, app_id, block_number).transpose())
+}
+
+fn block_app_data(
+	db: &impl Database,
+	app_id: u32,
+	block_number: u32,
+) -> Result<Option<DataResponse>> {
+	let Some(data) = db.get_data(app_id, block_number)? else {
+		return Ok(None);
+	};
+
+	if data.is_empty() {
+		return Ok(None);
+	}
+
+	let extrinsic_indices = db.get_extrinsic_indices(app_id, block_number)?;
+	let data_transactions = data_transactions(data, extrinsic_indices)
+		.with_context(|| format!("Failed to decode app data of block {block_number}"))?;
+
+	let confidence = db.get_confidence(block_number)?.map(calculate_confidence);
+
+	Ok(Some(DataResponse {
+		block_number,
+		data_transactions,
+		confidence,
+	}))
+}
+
+/// Encodes response as a single line of newline-delimited JSON.
+pub fn ndjson_line(response: &DataResponse) -> Result<Vec<u8>> {
+	let mut line = serde_json::to_vec(response)?;
+	line.push(b'\n');
+	Ok(line)
+}
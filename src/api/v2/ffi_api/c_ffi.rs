@@ -185,6 +185,11 @@ pub async fn call_callbacks<T: Clone + TryInto<PublishMessage>>(
 					callback(json_message);
 				}
 			},
+			PublishMessage::TransactionStatus(_) => {
+				if topic == Topic::TransactionStatus {
+					callback(json_message);
+				}
+			},
 		}
 	}
 }
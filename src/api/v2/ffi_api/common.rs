@@ -1,5 +1,6 @@
 use crate::api::common::{object_to_str, string_to_error_resp_json};
 use crate::api::v2::handlers::{block_data_from_db, block_from_db, block_header_from_db};
+use crate::api::v2::signers::{Signer, Signers, DEFAULT_SIGNER};
 use crate::api::v2::transactions::{self, AvailSigner, Submit};
 use crate::api::v2::types::{DataField, DataQuery, Error, FieldsQueryParameter};
 use crate::data::{
@@ -16,9 +17,11 @@ use crate::types::{AvailSecretKey, RuntimeConfig, State};
 
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
-use tracing::error;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, warn};
+use uuid::Uuid;
 
-use crate::api::v2::types::{Status, Transaction};
+use crate::api::v2::types::{Status, Submission, SubmitRequest, Transaction};
 
 pub async unsafe fn submit_transaction(
 	cfg: RuntimeConfig,
@@ -33,30 +36,82 @@ pub async unsafe fn submit_transaction(
 		);
 	}
 
-	let avail_secret = AvailSecretKey::try_from(private_key);
-	let db = init_db(&cfg.clone().avail_path, true).unwrap();
+	// If private key is empty, configured account key is used (possibly unlocked from the keystore)
+	let avail_secret = if private_key.is_empty() {
+		match cfg.load_avail_secret_key() {
+			Ok(avail_secret) => avail_secret,
+			Err(error) => {
+				error!("Cannot load account key: {error:#}");
+				return string_to_error_resp_json(format!("Cannot load account key: {error:#}"));
+			},
+		}
+	} else {
+		AvailSecretKey::try_from(private_key).ok()
+	};
+	// Submissions are persisted only if the database is not locked by the running light client
+	let (db, tracker) = match init_db(&cfg.avail_path, false) {
+		Ok(db) => {
+			let (sender, _) = broadcast::channel::<Submission>(1 << 7);
+			// FFI runs without the P2P client, so rows of the submitted data are not reseeded into the DHT
+			let tracker = transactions::Tracker::new(db.clone(), sender, None);
+			(db, Some(tracker))
+		},
+		Err(error) => {
+			warn!("Submission status will not be tracked: {error:#}");
+			(init_db(&cfg.avail_path, true).unwrap(), None)
+		},
+	};
+	// Submission updates are received before the submission is tracked
+	let mut updates = tracker.as_ref().map(|tracker| tracker.sender.subscribe());
 
 	let state = Arc::new(Mutex::new(State::default()));
 	let (rpc_client, _, _) = rpc::init(db, state, &cfg.full_node_ws);
 
 	match avail_secret {
-		Ok(avail_secret) => {
+		Some(avail_secret) => {
 			let submitter = Arc::new(transactions::Submitter {
 				node_client: rpc_client,
-				app_id,
-				pair_signer: Some(AvailSigner::from(avail_secret)),
+				app_id: Some(app_id),
+				signers: Signers::new(vec![Signer::new(
+					DEFAULT_SIGNER,
+					AvailSigner::from(avail_secret),
+					[app_id],
+				)]),
+				blob_chunk_size: cfg.blob_chunk_size,
+				tracker,
 			});
-			let response = submitter.submit(transaction).await.map_err(|error| {
+			let request = SubmitRequest {
+				transaction,
+				signer: None,
+				app_id: None,
+			};
+			let response = submitter.submit(request).await.map_err(|error| {
 				error!(%error, "Submit transaction failed");
 
 				Error::internal_server_error(error)
 			});
 			match response {
-				Ok(response) => response.hash.to_string(),
+				Ok(response) => {
+					// Runtime of the FFI call stops on return, so tracking is awaited until the final status
+					if let Some(updates) = updates.as_mut() {
+						wait_for_final_status(updates, response.id).await;
+					}
+					response.hash.to_string()
+				},
 				Err(err) => err.cause.unwrap().root_cause().to_string(),
 			}
 		},
-		Err(_) => "Secret Key error".to_string(),
+		None => "Secret Key error".to_string(),
+	}
+}
+
+async fn wait_for_final_status(updates: &mut broadcast::Receiver<Submission>, id: Uuid) {
+	loop {
+		match updates.recv().await {
+			Ok(submission) if submission.id == id && submission.status.is_final() => return,
+			Ok(_) | Err(RecvError::Lagged(_)) => continue,
+			Err(RecvError::Closed) => return,
+		}
 	}
 }
 
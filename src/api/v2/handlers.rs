@@ -1,29 +1,65 @@
 use super::{
-	transactions,
+	cells, export, transactions,
 	types::{
-		block_status, filter_fields, Block, BlockAndConfidence, BlockStatus, DataQuery,
-		DataResponse, DataTransaction, Error, FieldsQueryParameter, Header, Status, SubmitResponse,
-		Subscription, SubscriptionId, Transaction, Version, WsClients,
+		block_status, data_transactions, filter_fields, AppDataQuery, BlobResponse, Block,
+		BlockAndConfidence, BlockQuery, BlockStatus, BlocksQuery, BlocksResponse, CellsQuery,
+		CellsResponse, DataField, DataQuery, DataResponse, DataTransaction, Error, EstimateRequest,
+		EstimateResponse, EventsQuery, FieldsQueryParameter, Header, NumberedBlock, PublishMessage,
+		Status, Submission, SubmitRequest, SubmitResponse, Subscription, SubscriptionId,
+		Transaction, TransactionResponse, TransactionsQuery, TransactionsResponse,
+		VerifyCellsRequest, VerifyCellsResponse, Version, WsClients,
 	},
 	ws,
 };
 use crate::{
-	api::v2::types::{ErrorCode, InternalServerError},
+	api::{
+		auth::Access,
+		v2::types::{ErrorCode, InternalServerError},
+	},
 	data::{get_confidence_from_db, get_latest_block, Database},
-	network::rpc::Node,
-	types::{RuntimeConfig, State},
-	utils::calculate_confidence,
+	network::rpc::{self, Node},
+	proof::{self, DataInclusionProof, Priority},
+	types::{RuntimeConfig, State, TransactionLocation},
+	utils::{calculate_confidence, extract_kate},
 };
 use anyhow::anyhow;
+use avail_subxt::primitives::Header as DaHeader;
+use codec::Encode;
+use futures::{future, StreamExt};
 use hyper::StatusCode;
+use kate_recovery::{
+	commitments,
+	data::Cell,
+	matrix::{Dimensions, Position},
+};
 use rocksdb::DB;
+use sp_core::{blake2_256, H256};
 use std::{
+	collections::HashSet,
 	convert::Infallible,
 	sync::{Arc, Mutex},
+	time::{Duration, Instant},
 };
-use tracing::error;
+use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
+use tracing::{error, warn};
 use uuid::Uuid;
-use warp::{ws::Ws, Rejection, Reply};
+use warp::{sse::Event, ws::Ws, Rejection, Reply};
+
+/// Maximum number of blocks in the requested range
+const MAX_BLOCKS_RANGE: u32 = 1000;
+/// Default and maximum time to wait for the block status
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+/// Interval of checking the block status while waiting
+const WAIT_INTERVAL: Duration = Duration::from_millis(250);
+/// Maximum number of requests concurrently waiting for the block status
+pub const MAX_BLOCK_WAITS: usize = 1000;
+/// Maximum number of the requested or verified cells
+const MAX_CELLS: usize = 1024;
+
+/// Number of the application data lines read ahead of the response stream
+const APP_DATA_BUFFER: usize = 16;
 
 pub async fn subscriptions(
 	subscription: Subscription,
@@ -34,20 +70,71 @@ pub async fn subscriptions(
 	Ok(SubscriptionId { subscription_id })
 }
 
+/// Checks if the data transaction can be signed, extrinsics are already signed
+fn check_signer(
+	submitter: &impl transactions::Submit,
+	transaction: &Transaction,
+	signer: Option<&str>,
+	app_id: Option<u32>,
+) -> Result<(), Error> {
+	if matches!(transaction, Transaction::Data(_)) {
+		if !submitter.has_signer() {
+			return Err(Error::not_found());
+		}
+		submitter
+			.check_signer(signer, app_id)
+			.map_err(|error| Error::bad_request_unknown(&error.to_string()))?;
+	};
+	Ok(())
+}
+
 pub async fn submit(
 	submitter: Arc<impl transactions::Submit>,
-	transaction: Transaction,
+	request: SubmitRequest,
 ) -> Result<SubmitResponse, Error> {
-	if matches!(&transaction, Transaction::Data(_)) && !submitter.has_signer() {
-		return Err(Error::not_found());
-	};
+	check_signer(
+		submitter.as_ref(),
+		&request.transaction,
+		request.signer.as_deref(),
+		request.app_id,
+	)?;
+
+	submitter
+		.submit(request)
+		.await
+		.map_err(Error::internal_server_error)
+}
+
+pub async fn estimate(
+	submitter: Arc<impl transactions::Submit>,
+	request: EstimateRequest,
+) -> Result<EstimateResponse, Error> {
+	check_signer(
+		submitter.as_ref(),
+		&request.request.transaction,
+		request.request.signer.as_deref(),
+		request.request.app_id,
+	)?;
 
 	submitter
-		.submit(transaction)
+		.estimate(request)
 		.await
 		.map_err(Error::internal_server_error)
 }
 
+pub async fn submission(id: Uuid, db: impl Database) -> Result<Submission, Error> {
+	db.get_submission(id)
+		.map_err(Error::internal_server_error)?
+		.ok_or_else(Error::not_found)
+}
+
+pub async fn blob(manifest_hash: H256, db: impl Database) -> Result<BlobResponse, Error> {
+	db.get_blob(manifest_hash)
+		.map_err(Error::internal_server_error)?
+		.map(|blob| BlobResponse::new(manifest_hash, blob))
+		.ok_or_else(Error::not_found)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn ws(
 	subscription_id: String,
@@ -58,6 +145,8 @@ pub async fn ws(
 	node: Node,
 	submitter: Option<Arc<impl transactions::Submit + Clone + Send + Sync + 'static>>,
 	state: Arc<Mutex<State>>,
+	db: impl Database + Sync + 'static,
+	access: Access,
 ) -> Result<impl Reply, Rejection> {
 	if !clients.has_subscription(&subscription_id).await {
 		return Err(warp::reject::not_found());
@@ -73,10 +162,47 @@ pub async fn ws(
 			node,
 			submitter.clone(),
 			state.clone(),
+			db.clone(),
+			access.clone(),
 		)
 	}))
 }
 
+/// Converts published message to the server-sent event, if client is subscribed to its topic
+fn event(query: &EventsQuery, mut message: PublishMessage) -> Option<Event> {
+	let topic = message.topic();
+	if !query.is_subscribed(&topic) {
+		return None;
+	}
+	if let Some(FieldsQueryParameter(fields)) = &query.fields {
+		message.apply_filter(fields);
+	}
+	match Event::default()
+		.event(topic.to_string())
+		.json_data(&message)
+	{
+		Ok(event) => Some(event),
+		Err(error) => {
+			error!(?topic, "Cannot create event: {error}");
+			None
+		},
+	}
+}
+
+pub fn events(query: EventsQuery, receiver: broadcast::Receiver<PublishMessage>) -> impl Reply {
+	let stream = BroadcastStream::new(receiver).filter_map(move |message| {
+		let event = match message {
+			Ok(message) => event(&query, message),
+			Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+				warn!("Event stream is lagging, {skipped} messages skipped");
+				None
+			},
+		};
+		future::ready(event.map(Ok::<_, Infallible>))
+	});
+	warp::sse::reply(warp::sse::keep_alive().stream(stream))
+}
+
 pub fn status(config: RuntimeConfig, node: Node, state: Arc<Mutex<State>>) -> impl Reply {
 	let state = state.lock().expect("Lock should be acquired");
 	Status::new(&config, &node, &state)
@@ -95,15 +221,70 @@ pub fn log_internal_server_error(result: Result<impl Reply, Error>) -> Result<im
 	result
 }
 
-pub async fn block(
+/// Returns block status, waiting until block reaches the requested status or the timeout expires
+async fn wait_for_block_status(
+	block_number: u32,
+	query: BlockQuery,
+	config: &RuntimeConfig,
+	state: Arc<Mutex<State>>,
+) -> Option<BlockStatus> {
+	let timeout = query
+		.timeout
+		.map(|timeout| timeout.0)
+		.unwrap_or(DEFAULT_WAIT_TIMEOUT)
+		.min(MAX_WAIT_TIMEOUT);
+	let deadline = Instant::now() + timeout;
+	let mut interval = tokio::time::interval(WAIT_INTERVAL);
+
+	loop {
+		interval.tick().await;
+		let status = {
+			let state = state.lock().expect("Lock should be acquired");
+			block_status(&config.sync_start_block, &state, block_number)
+		};
+
+		let reached = match (query.wait_for, status) {
+			(None, _) => true,
+			(Some(wait_for), Some(status)) => status.has_reached(wait_for),
+			(Some(_), None) => false,
+		};
+
+		if reached || Instant::now() >= deadline {
+			return status;
+		}
+	}
+}
+
+/// Returns block, waiting for the requested status if `wait_for` is set.
+/// Waiting requests are limited by the `waits` semaphore, and rejected with `429 Too Many Requests` if exceeded.
+pub async fn wait_for_block(
 	block_number: u32,
+	query: BlockQuery,
+	waits: Arc<Semaphore>,
 	config: RuntimeConfig,
 	state: Arc<Mutex<State>>,
 	db: impl Database,
-) -> Result<impl Reply, Error> {
-	let state = state.lock().expect("Lock should be acquired");
+) -> Result<Block, Error> {
+	let _permit = match query.wait_for {
+		Some(_) => Some(
+			waits
+				.try_acquire()
+				.map_err(|_| Error::too_many_requests())?,
+		),
+		None => None,
+	};
+	block(block_number, query, config, state, db).await
+}
 
-	let Some(block_status) = block_status(&config.sync_start_block, &state, block_number) else {
+pub async fn block(
+	block_number: u32,
+	query: BlockQuery,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> Result<Block, Error> {
+	let Some(block_status) = wait_for_block_status(block_number, query, &config, state).await
+	else {
 		return Err(Error::not_found());
 	};
 
@@ -112,7 +293,54 @@ pub async fn block(
 		.map_err(Error::internal_server_error)?
 		.map(calculate_confidence);
 
-	Ok(Block::new(block_status, confidence))
+	let dht_columns = db
+		.get_dht_columns(block_number)
+		.map_err(Error::internal_server_error)?;
+
+	Ok(Block {
+		dht_columns,
+		..Block::new(block_status, confidence)
+	})
+}
+
+pub async fn blocks(
+	query: BlocksQuery,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> Result<BlocksResponse, Error> {
+	if query.from > query.to {
+		return Err(Error::bad_request_unknown("Invalid block range"));
+	}
+	if query.to - query.from >= MAX_BLOCKS_RANGE {
+		let message = format!("Block range is limited to {MAX_BLOCKS_RANGE} blocks");
+		return Err(Error::bad_request_unknown(&message));
+	}
+
+	let statuses = {
+		let state = state.lock().expect("Lock should be acquired");
+		(query.from..=query.to)
+			.map_while(|block_number| {
+				block_status(&config.sync_start_block, &state, block_number)
+					.map(|status| (block_number, status))
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let blocks = statuses
+		.into_iter()
+		.map(|(block_number, status)| {
+			let confidence = db.get_confidence(block_number)?.map(calculate_confidence);
+			let block = Block::new(status, confidence);
+			Ok(NumberedBlock {
+				block_number,
+				block,
+			})
+		})
+		.collect::<anyhow::Result<Vec<_>>>()
+		.map_err(Error::internal_server_error)?;
+
+	Ok(BlocksResponse { blocks })
 }
 
 pub async fn block_from_db(
@@ -155,6 +383,287 @@ pub async fn block_header(
 		.and_then(|header| header.try_into())
 		.map_err(Error::internal_server_error)
 }
+
+pub async fn block_data_proof(
+	block_number: u32,
+	transaction_index: u32,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	node_client: rpc::Client,
+) -> Result<DataInclusionProof, Error> {
+	let header = verified_header(block_number, &config, &state, &db)?;
+
+	proof::fetch_data_inclusion_proof(&node_client, &header, transaction_index)
+		.await
+		.map_err(Error::internal_server_error)
+}
+
+/// Returns header of the block, if block header is verified
+fn verified_header(
+	block_number: u32,
+	config: &RuntimeConfig,
+	state: &Arc<Mutex<State>>,
+	db: &impl Database,
+) -> Result<DaHeader, Error> {
+	{
+		let state = state.lock().expect("Lock should be acquired");
+
+		let Some(block_status) = block_status(&config.sync_start_block, &state, block_number)
+		else {
+			return Err(Error::not_found());
+		};
+
+		if matches!(
+			block_status,
+			BlockStatus::Unavailable | BlockStatus::Pending | BlockStatus::VerifyingHeader
+		) {
+			return Err(Error::bad_request_unknown("Block header is not available"));
+		};
+	}
+
+	db.get_header(block_number)
+		.and_then(|header| header.ok_or_else(|| anyhow!("Header not found")))
+		.map_err(Error::internal_server_error)
+}
+
+/// Returns matrix dimensions and commitments from the block header,
+/// checking that given positions are within the extended matrix
+fn matrix(header: &DaHeader, positions: &[Position]) -> Result<(Dimensions, Vec<[u8; 48]>), Error> {
+	if positions.is_empty() {
+		return Err(Error::bad_request_unknown("At least one cell is required"));
+	}
+
+	if positions.len() > MAX_CELLS {
+		let message = format!("Maximum number of cells is {MAX_CELLS}");
+		return Err(Error::bad_request_unknown(&message));
+	}
+
+	let (rows, cols, _, commitment) = extract_kate(&header.extension);
+	let Some(dimensions) = Dimensions::new(rows, cols) else {
+		return Err(Error::bad_request_unknown("Block has no data matrix"));
+	};
+
+	let out_of_matrix = |position: &Position| {
+		position.row >= dimensions.extended_rows() || position.col >= dimensions.cols().get()
+	};
+	if positions.iter().any(out_of_matrix) {
+		let message = format!("Cell position is out of the {rows}x{cols} matrix");
+		return Err(Error::bad_request_unknown(&message));
+	}
+
+	let commitments = commitments::from_slice(&commitment)
+		.map_err(|error| Error::internal_server_error(anyhow!("{error}")))?;
+
+	if commitments.len() != dimensions.extended_rows() as usize {
+		let error = anyhow!(
+			"Number of commitments ({}) does not match the number of extended rows ({})",
+			commitments.len(),
+			dimensions.extended_rows()
+		);
+		return Err(Error::internal_server_error(error));
+	}
+
+	Ok((dimensions, commitments))
+}
+
+pub async fn block_cells(
+	block_number: u32,
+	query: CellsQuery,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	fetcher: Arc<impl cells::Fetch>,
+) -> Result<CellsResponse, Error> {
+	let header = verified_header(block_number, &config, &state, &db)?;
+	let positions = query.positions.0;
+	let (dimensions, commitments) = matrix(&header, &positions)?;
+
+	let block_hash: H256 = Encode::using_encoded(&header, blake2_256).into();
+	let (cells, missing) = fetcher
+		.fetch(
+			block_number,
+			block_hash,
+			dimensions,
+			&commitments,
+			&positions,
+		)
+		.await
+		.map_err(Error::internal_server_error)?;
+
+	Ok(CellsResponse::new(
+		block_number,
+		commitments,
+		cells,
+		missing,
+	))
+}
+
+pub async fn verify_cells(
+	request: VerifyCellsRequest,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	verifier: proof::Verifier,
+) -> Result<VerifyCellsResponse, Error> {
+	let block_number = request.block_number;
+	let header = verified_header(block_number, &config, &state, &db)?;
+	let cells = request
+		.cells
+		.into_iter()
+		.map(Cell::from)
+		.collect::<Vec<_>>();
+	let positions = cells.iter().map(|cell| cell.position).collect::<Vec<_>>();
+	let (dimensions, commitments) = matrix(&header, &positions)?;
+
+	let (verified, unverified) = verifier
+		.verify(
+			Priority::Sync,
+			block_number,
+			dimensions,
+			&cells,
+			&commitments,
+		)
+		.await
+		.map_err(Error::internal_server_error)?;
+
+	Ok(VerifyCellsResponse {
+		block_number,
+		verified: verified.into_iter().map(From::from).collect(),
+		unverified: unverified.into_iter().map(From::from).collect(),
+	})
+}
+
+/// Maximum number of the listed transactions
+const MAX_TRANSACTIONS: usize = 1000;
+const DEFAULT_TRANSACTIONS: usize = 100;
+
+fn transaction_response(
+	location: TransactionLocation,
+	config: &RuntimeConfig,
+	state: &Arc<Mutex<State>>,
+	db: &impl Database,
+) -> Result<TransactionResponse, Error> {
+	let TransactionLocation {
+		app_id,
+		block_number,
+		index,
+	} = location;
+
+	let block_status = {
+		let state = state.lock().expect("Lock should be acquired");
+		block_status(&config.sync_start_block, &state, block_number)
+	};
+	let Some(block_status) = block_status else {
+		return Err(Error::not_found());
+	};
+
+	let extrinsic = db
+		.get_data(app_id, block_number)
+		.map_err(Error::internal_server_error)?
+		.and_then(|data| data.into_iter().nth(index as usize))
+		.ok_or_else(|| anyhow!("Transaction {index} not found in block {block_number} data"))
+		.map_err(Error::internal_server_error)?;
+
+	// Location index is the position among the application transactions
+	let extrinsic_index = db
+		.get_extrinsic_indices(app_id, block_number)
+		.map_err(Error::internal_server_error)?
+		.and_then(|indices| indices.get(index as usize).copied());
+
+	let transaction =
+		DataTransaction::new(extrinsic_index, extrinsic).map_err(Error::internal_server_error)?;
+
+	let confidence = db
+		.get_confidence(block_number)
+		.map_err(Error::internal_server_error)?
+		.map(calculate_confidence);
+
+	Ok(TransactionResponse {
+		app_id,
+		block_number,
+		block: Block::new(block_status, confidence),
+		transaction,
+	})
+}
+
+pub async fn transaction(
+	hash: H256,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> Result<TransactionResponse, Error> {
+	let Some(location) = db
+		.get_transaction_location(hash)
+		.map_err(Error::internal_server_error)?
+	else {
+		return Err(Error::not_found());
+	};
+
+	transaction_response(location, &config, &state, &db)
+}
+
+pub async fn signer_transactions(
+	app_id: u32,
+	query: TransactionsQuery,
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> Result<TransactionsResponse, Error> {
+	let limit = query.limit.unwrap_or(DEFAULT_TRANSACTIONS);
+	if limit > MAX_TRANSACTIONS {
+		let message = format!("Maximum limit is {MAX_TRANSACTIONS}");
+		return Err(Error::bad_request_unknown(&message));
+	}
+
+	let transactions = db
+		.get_signer_transactions(app_id, &query.signer, query.from.unwrap_or(0), limit)
+		.map_err(Error::internal_server_error)?
+		.into_iter()
+		.map(|location| transaction_response(location, &config, &state, &db))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(TransactionsResponse { transactions })
+}
+
+pub async fn app_data(
+	app_id: u32,
+	query: AppDataQuery,
+	db: impl Database + 'static,
+) -> Result<warp::reply::Response, Error> {
+	let AppDataQuery { from, to } = query;
+	if from > to {
+		return Err(Error::bad_request_unknown(
+			"Range start must not be greater than range end",
+		));
+	}
+
+	if to - from >= MAX_BLOCKS_RANGE {
+		let message = format!("Block range is limited to {MAX_BLOCKS_RANGE} blocks");
+		return Err(Error::bad_request_unknown(&message));
+	}
+
+	// Database is read on the blocking thread pool, and lines are streamed as they are read
+	let (sender, receiver) = mpsc::channel(APP_DATA_BUFFER);
+	tokio::task::spawn_blocking(move || {
+		for response in export::app_data(db, app_id, from, to) {
+			let line = response.and_then(|response| export::ndjson_line(&response));
+			if sender.blocking_send(line).is_err() {
+				// Response stream is dropped, so there is no need to read further
+				return;
+			}
+		}
+	});
+
+	let body = hyper::Body::wrap_stream(ReceiverStream::new(receiver));
+	let mut response = warp::reply::Response::new(body);
+	response.headers_mut().insert(
+		hyper::header::CONTENT_TYPE,
+		hyper::header::HeaderValue::from_static("application/x-ndjson"),
+	);
+	Ok(response)
+}
+
 pub fn block_header_from_db(
 	block_number: u32,
 	db_impl: impl Database,
@@ -200,22 +709,28 @@ pub async fn block_data(
 		return Ok(DataResponse {
 			block_number,
 			data_transactions: vec![],
+			confidence: None,
 		});
 	};
 
-	let mut data_transactions: Vec<DataTransaction> = data
-		.into_iter()
-		.map(DataTransaction::try_from)
-		.collect::<anyhow::Result<_>>()
+	let extrinsic_indices = db
+		.get_extrinsic_indices(app_id, block_number)
 		.map_err(Error::internal_server_error)?;
 
-	if let Some(FieldsQueryParameter(fields)) = &query.fields {
-		filter_fields(&mut data_transactions, fields);
-	}
+	let mut data_transactions =
+		data_transactions(data, extrinsic_indices).map_err(Error::internal_server_error)?;
+
+	// Decoded data and encoded extrinsic are returned by default
+	let fields = query
+		.fields
+		.map(|FieldsQueryParameter(fields)| fields)
+		.unwrap_or_else(|| HashSet::from([DataField::Data, DataField::Extrinsic]));
+	filter_fields(&mut data_transactions, &fields);
 
 	Ok(DataResponse {
 		block_number,
 		data_transactions,
+		confidence: None,
 	})
 }
 
@@ -242,22 +757,28 @@ pub async fn block_data_from_db(
 		return Ok(DataResponse {
 			block_number,
 			data_transactions: vec![],
+			confidence: None,
 		});
 	};
 
-	let mut data_transactions: Vec<DataTransaction> = data
-		.into_iter()
-		.map(DataTransaction::try_from)
-		.collect::<anyhow::Result<_>>()
+	let extrinsic_indices = db_impl
+		.get_extrinsic_indices(app_id, block_number)
 		.map_err(Error::internal_server_error)?;
 
-	if let Some(FieldsQueryParameter(fields)) = &query.fields {
-		filter_fields(&mut data_transactions, fields);
-	}
+	let mut data_transactions =
+		data_transactions(data, extrinsic_indices).map_err(Error::internal_server_error)?;
+
+	// Decoded data and encoded extrinsic are returned by default
+	let fields = query
+		.fields
+		.map(|FieldsQueryParameter(fields)| fields)
+		.unwrap_or_else(|| HashSet::from([DataField::Data, DataField::Extrinsic]));
+	filter_fields(&mut data_transactions, &fields);
 
 	Ok(DataResponse {
 		block_number,
 		data_transactions,
+		confidence: None,
 	})
 }
 pub async fn handle_rejection(error: Rejection) -> Result<impl Reply, Rejection> {
@@ -1,26 +1,46 @@
 use self::{
 	handlers::{handle_rejection, log_internal_server_error},
-	types::{DataQuery, PublishMessage, Version, WsClients},
+	signers::Signers,
+	types::{
+		AppDataQuery, BlockQuery, BlocksQuery, CellsQuery, DataQuery, EventsQuery, PublishMessage,
+		Submission, TransactionsQuery, Version, WsClients,
+	},
 };
 use crate::{
-	api::v2::types::Topic,
+	api::{
+		auth::{self, Auth},
+		v2::types::Topic,
+	},
+	cell_cache::CellCache,
 	data::{Database, RocksDB},
-	network::rpc::{Client, Node},
+	network::{
+		p2p,
+		rpc::{Client, Node},
+	},
+	proof::Verifier,
 	types::{RuntimeConfig, State},
 };
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use sp_core::H256;
 use std::{
 	convert::Infallible,
 	fmt::Display,
 	sync::{Arc, Mutex},
 };
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Semaphore};
 use tracing::{debug, error, info};
+use uuid::Uuid;
 use warp::{Filter, Rejection, Reply};
 
+mod cells;
+pub mod export;
 pub mod ffi_api;
 mod handlers;
-mod transactions;
+mod openapi;
+mod signers;
+pub mod transactions;
 pub mod types;
+pub mod webhooks;
 mod ws;
 
 async fn optionally<T>(value: Option<T>) -> Result<T, Rejection> {
@@ -44,6 +64,15 @@ fn version_route(
 		.map(move || version.clone())
 }
 
+fn openapi_route(
+	version: &Version,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	let spec = openapi::spec(&version.version);
+	warp::path!("v2" / "openapi.json")
+		.and(warp::get())
+		.map(move || warp::reply::json(&spec))
+}
+
 fn status_route(
 	config: RuntimeConfig,
 	node: Node,
@@ -61,13 +90,31 @@ fn block_route(
 	config: RuntimeConfig,
 	state: Arc<Mutex<State>>,
 	db: impl Database,
+	waits: Arc<Semaphore>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
 	warp::path!("v2" / "blocks" / u32)
 		.and(warp::get())
+		.and(warp::query::<BlockQuery>())
+		.and(warp::any().map(move || waits.clone()))
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::wait_for_block)
+		.map(log_internal_server_error)
+}
+
+fn blocks_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "blocks")
+		.and(warp::get())
+		.and(warp::query::<BlocksQuery>())
 		.and(warp::any().map(move || config.clone()))
 		.and(warp::any().map(move || state.clone()))
 		.and(warp::any().map(move || db.clone()))
-		.then(handlers::block)
+		.then(handlers::blocks)
 		.map(log_internal_server_error)
 }
 
@@ -100,6 +147,96 @@ fn block_data_route(
 		.map(log_internal_server_error)
 }
 
+fn block_data_proof_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	node_client: Client,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "blocks" / u32 / "data" / u32 / "proof")
+		.and(warp::get())
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.and(warp::any().map(move || node_client.clone()))
+		.then(handlers::block_data_proof)
+		.map(log_internal_server_error)
+}
+
+fn block_cells_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	fetcher: Arc<impl cells::Fetch + Send + Sync + 'static>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "blocks" / u32 / "cells")
+		.and(warp::get())
+		.and(warp::query::<CellsQuery>())
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.and(warp::any().map(move || fetcher.clone()))
+		.then(handlers::block_cells)
+		.map(log_internal_server_error)
+}
+
+fn verify_cells_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+	verifier: Verifier,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "verify" / "cells")
+		.and(warp::post())
+		.and(warp::body::json())
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.and(warp::any().map(move || verifier.clone()))
+		.then(handlers::verify_cells)
+		.map(log_internal_server_error)
+}
+
+fn transaction_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "transactions" / H256)
+		.and(warp::get())
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::transaction)
+		.map(log_internal_server_error)
+}
+
+fn app_data_route(
+	db: impl Database + 'static,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "apps" / u32 / "data")
+		.and(warp::get())
+		.and(warp::query::<AppDataQuery>())
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::app_data)
+		.map(log_internal_server_error)
+}
+
+fn signer_transactions_route(
+	config: RuntimeConfig,
+	state: Arc<Mutex<State>>,
+	db: impl Database,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "apps" / u32 / "transactions")
+		.and(warp::get())
+		.and(warp::query::<TransactionsQuery>())
+		.and(warp::any().map(move || config.clone()))
+		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::signer_transactions)
+		.map(log_internal_server_error)
+}
+
 fn submit_route(
 	submitter: Option<Arc<impl transactions::Submit + Clone + Send + Sync>>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
@@ -111,6 +248,17 @@ fn submit_route(
 		.map(log_internal_server_error)
 }
 
+fn submit_estimate_route(
+	submitter: Option<Arc<impl transactions::Submit + Clone + Send + Sync>>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "submit" / "estimate")
+		.and(warp::post())
+		.and_then(move || optionally(submitter.clone()))
+		.and(warp::body::json())
+		.then(handlers::estimate)
+		.map(log_internal_server_error)
+}
+
 fn subscriptions_route(
 	clients: WsClients,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
@@ -121,6 +269,37 @@ fn subscriptions_route(
 		.and_then(handlers::subscriptions)
 }
 
+fn submission_route(
+	db: impl Database,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "submissions" / Uuid)
+		.and(warp::get())
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::submission)
+		.map(log_internal_server_error)
+}
+
+fn blob_route(
+	db: impl Database,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "blobs" / H256)
+		.and(warp::get())
+		.and(warp::any().map(move || db.clone()))
+		.then(handlers::blob)
+		.map(log_internal_server_error)
+}
+
+fn events_route(
+	events: broadcast::Sender<PublishMessage>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+	warp::path!("v2" / "events")
+		.and(warp::get())
+		.and(warp::query::<EventsQuery>())
+		.and(warp::any().map(move || events.subscribe()))
+		.map(handlers::events)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn ws_route(
 	clients: WsClients,
 	version: Version,
@@ -128,6 +307,8 @@ fn ws_route(
 	node: Node,
 	submitter: Option<Arc<impl transactions::Submit + Clone + Send + Sync + 'static>>,
 	state: Arc<Mutex<State>>,
+	db: impl Database + Sync + 'static,
+	auth: Auth,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
 	warp::path!("v2" / "ws" / String)
 		.and(warp::ws())
@@ -137,6 +318,8 @@ fn ws_route(
 		.and(warp::any().map(move || node.clone()))
 		.and(warp::any().map(move || submitter.clone()))
 		.and(warp::any().map(move || state.clone()))
+		.and(warp::any().map(move || db.clone()))
+		.and(auth::with_access(auth))
 		.and_then(handlers::ws)
 }
 
@@ -144,6 +327,7 @@ pub async fn publish<T: Clone + TryInto<PublishMessage>>(
 	topic: Topic,
 	mut receiver: broadcast::Receiver<T>,
 	clients: WsClients,
+	events: broadcast::Sender<PublishMessage>,
 ) where
 	<T as TryInto<PublishMessage>>::Error: Display,
 {
@@ -164,6 +348,9 @@ pub async fn publish<T: Clone + TryInto<PublishMessage>>(
 			},
 		};
 
+		// Sending fails only if there are no server-sent events or webhook receivers
+		_ = events.send(message.clone());
+
 		match clients.publish(&topic, message).await {
 			Ok(results) => {
 				let published = results.iter().filter(|&result| result.is_ok()).count();
@@ -186,53 +373,119 @@ pub fn routes(
 	state: Arc<Mutex<State>>,
 	config: RuntimeConfig,
 	node_client: Client,
+	p2p_client: p2p::Client,
+	cell_cache: CellCache,
+	verifier: Verifier,
+	pp: Arc<PublicParameters>,
 	ws_clients: WsClients,
+	events: broadcast::Sender<PublishMessage>,
+	submissions: broadcast::Sender<Submission>,
 	db: RocksDB,
+	auth: Auth,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
 	let version = Version {
 		version,
 		network_version,
 	};
 
-	let app_id = config.app_id.as_ref();
-	let pair_signer = config.avail_secret_key.clone().map(From::from);
+	let signers = Signers::from(&config);
 
-	let submitter = app_id.map(|&app_id| {
-		Arc::new(transactions::Submitter {
-			node_client,
-			app_id,
-			pair_signer,
-		})
+	let reseeder = transactions::Reseeder {
+		node_client: node_client.clone(),
+		p2p_client: p2p_client.clone(),
+		pp,
+	};
+
+	let fetcher = Arc::new(cells::Fetcher {
+		cell_cache,
+		p2p_client,
+		rpc_client: node_client.clone(),
+		disable_rpc: config.disable_rpc,
+		max_cells_per_rpc: config.max_cells_per_rpc.unwrap_or(30),
+		verifier: verifier.clone(),
 	});
 
+	let submitter = Some(Arc::new(transactions::Submitter {
+		node_client: node_client.clone(),
+		app_id: config.app_id,
+		signers,
+		blob_chunk_size: config.blob_chunk_size,
+		tracker: Some(transactions::Tracker::new(
+			db.0.clone(),
+			submissions,
+			Some(Arc::new(reseeder)),
+		)),
+	}));
+
 	version_route(version.clone())
+		.or(openapi_route(&version))
 		.or(status_route(config.clone(), node.clone(), state.clone()))
-		.or(block_route(config.clone(), state.clone(), db.clone()))
+		.or(blocks_route(config.clone(), state.clone(), db.clone()))
+		.or(block_route(
+			config.clone(),
+			state.clone(),
+			db.clone(),
+			Arc::new(Semaphore::new(handlers::MAX_BLOCK_WAITS)),
+		))
 		.or(block_header_route(
 			config.clone(),
 			state.clone(),
 			db.clone(),
 		))
 		.or(block_data_route(config.clone(), state.clone(), db.clone()))
+		.or(block_data_proof_route(
+			config.clone(),
+			state.clone(),
+			db.clone(),
+			node_client,
+		))
+		.or(block_cells_route(
+			config.clone(),
+			state.clone(),
+			db.clone(),
+			fetcher,
+		))
+		.or(verify_cells_route(
+			config.clone(),
+			state.clone(),
+			db.clone(),
+			verifier,
+		))
+		.or(transaction_route(config.clone(), state.clone(), db.clone()))
+		.or(signer_transactions_route(
+			config.clone(),
+			state.clone(),
+			db.clone(),
+		))
+		.or(app_data_route(db.clone()))
 		.or(subscriptions_route(ws_clients.clone()))
+		.or(events_route(events))
 		.or(submit_route(submitter.clone()))
+		.or(submit_estimate_route(submitter.clone()))
+		.or(submission_route(db.clone()))
+		.or(blob_route(db.clone()))
 		.or(ws_route(
-			ws_clients, version, config, node, submitter, state,
+			ws_clients, version, config, node, submitter, state, db, auth,
 		))
 		.recover(handle_rejection)
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{transactions, types::Transaction};
+	use super::{cells, transactions, Client};
 	use crate::{
+		api::auth::Auth,
 		api::v2::types::{
-			DataField, ErrorCode, SubmitResponse, Subscription, SubscriptionId, Topic, Version,
-			WsClients, WsError, WsResponse,
+			BlockStatus, CellPosition, CellsResponse, DataField, DryRunResult, ErrorCode,
+			EstimateRequest, EstimateResponse, PublishMessage, Submission, SubmissionStatus,
+			SubmitRequest, SubmitResponse, Subscription, SubscriptionId, Topic, VerifyCellsRequest,
+			VerifyCellsResponse, Version, WsClients, WsError, WsResponse,
 		},
+		blobs::Blob,
 		data::Database,
-		network::rpc::Node,
-		types::{BlockRange, OptionBlockRange, RuntimeConfig, State},
+		network::rpc::{Node, Weight},
+		proof::test_verifier,
+		types::{BlockRange, OptionBlockRange, RuntimeConfig, State, TransactionLocation},
 	};
 	use async_trait::async_trait;
 	use avail_subxt::{
@@ -243,16 +496,24 @@ mod tests {
 		},
 		primitives::Header as DaHeader,
 	};
-	use hyper::StatusCode;
-	use kate_recovery::{com::AppData, matrix::Partition};
+	use base64::{engine::general_purpose, Engine};
+	use hyper::{body::HttpBody, StatusCode};
+	use kate_recovery::{
+		com::AppData,
+		couscous,
+		data::Cell as DaCell,
+		matrix::{Dimensions, Partition, Position},
+	};
 	use sp_core::H256;
 	use std::{
 		collections::HashSet,
 		str::FromStr,
 		sync::{Arc, Mutex},
+		time::Duration,
 	};
 	use subxt::config::substrate::Digest;
 	use test_case::test_case;
+	use tokio::sync::Semaphore;
 	use uuid::Uuid;
 
 	fn v1() -> Version {
@@ -265,6 +526,20 @@ mod tests {
 	const GENESIS_HASH: &str = "0xc590b3c924c35c2f241746522284e4709df490d73a38aaa7d6de4ed1eac2f500";
 	const NETWORK: &str = "{host}/{system_version}/data-avail/0";
 
+	#[tokio::test]
+	async fn openapi_route() {
+		let route = super::openapi_route(&v1());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/openapi.json")
+			.reply(&route)
+			.await;
+
+		assert_eq!(response.status(), 200);
+		let spec: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+		assert_eq!(spec["info"]["version"], "v1.0.0");
+	}
+
 	#[tokio::test]
 	async fn version_route() {
 		let route = super::version_route(v1());
@@ -346,6 +621,10 @@ mod tests {
 		assert_eq!(response.body(), &expected);
 	}
 
+	fn waits(permits: usize) -> Arc<Semaphore> {
+		Arc::new(Semaphore::new(permits))
+	}
+
 	#[test_case(1, 2)]
 	#[test_case(10, 11)]
 	#[test_case(10, 20)]
@@ -357,7 +636,7 @@ mod tests {
 			let mut state = state.lock().unwrap();
 			state.latest = latest;
 		}
-		let route = super::block_route(config, state, MockDatabase::default());
+		let route = super::block_route(config, state, MockDatabase::default(), waits(1));
 		let response = warp::test::request()
 			.method("GET")
 			.path(&format!("/v2/blocks/{block_number}"))
@@ -384,6 +663,7 @@ mod tests {
 				confidence: Some(4),
 				..Default::default()
 			},
+			waits(1),
 		);
 		let response = warp::test::request()
 			.method("GET")
@@ -398,98 +678,315 @@ mod tests {
 		);
 	}
 
-	#[test_case(0, r#"Block header is not available"#  ; "Block is unavailable")]
-	#[test_case(6, r#"Block header is not available"#  ; "Block is pending")]
-	#[test_case(10, r#"Block header is not available"#  ; "Block is in verifying-header state")]
 	#[tokio::test]
-	async fn block_header_route_bad_request(block_number: u32, expected: &str) {
-		let config = RuntimeConfig {
-			sync_start_block: Some(1),
-			..Default::default()
-		};
-		let state = Arc::new(Mutex::new(State {
-			latest: 10,
-			sync_latest: Some(5),
-			header_verified: Some(BlockRange::init(9)),
-			..Default::default()
-		}));
-
-		let route = super::block_header_route(config, state, MockDatabase::default());
+	async fn block_route_archived() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State::default()));
+		{
+			let mut state = state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+			state.data_verified.set(10);
+		}
+		let route = super::block_route(
+			config,
+			state,
+			MockDatabase {
+				confidence: Some(4),
+				dht_columns: Some(vec![0, 1, 3]),
+				..Default::default()
+			},
+			waits(1),
+		);
 		let response = warp::test::request()
 			.method("GET")
-			.path(&format!("/v2/blocks/{block_number}/header"))
+			.path("/v2/blocks/10")
 			.reply(&route)
 			.await;
-		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-		assert_eq!(response.body(), expected);
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			response.body(),
+			r#"{"status":"finished","confidence":93.75,"dht_columns":[0,1,3]}"#
+		);
 	}
 
 	#[tokio::test]
-	async fn block_header_route_not_found() {
+	async fn block_route_wait_for_finished() {
 		let config = RuntimeConfig::default();
-		let state = Arc::new(Mutex::new(State {
-			latest: 10,
-			..Default::default()
-		}));
+		let state = Arc::new(Mutex::new(State::default()));
+		{
+			let mut state = state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+		}
+		let route = super::block_route(
+			config,
+			state.clone(),
+			MockDatabase {
+				confidence: Some(4),
+				..Default::default()
+			},
+			waits(1),
+		);
+
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_millis(500)).await;
+			state.lock().unwrap().data_verified.set(10);
+		});
 
-		let route = super::block_header_route(config, state, MockDatabase::default());
 		let response = warp::test::request()
 			.method("GET")
-			.path("/v2/blocks/11/header")
+			.path("/v2/blocks/10?wait_for=finished&timeout=10s")
 			.reply(&route)
 			.await;
-		assert_eq!(response.status(), StatusCode::NOT_FOUND);
-	}
 
-	fn header() -> DaHeader {
-		DaHeader {
-			parent_hash: H256::default(),
-			number: 1,
-			state_root: H256::default(),
-			extrinsics_root: H256::default(),
-			extension: HeaderExtension::V2(v2::HeaderExtension {
-				commitment: KateCommitment::default(),
-				app_lookup: CompactDataLookup {
-					size: 0,
-					index: vec![],
-				},
-			}),
-			digest: Digest { logs: vec![] },
-		}
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			response.body(),
+			r#"{"status":"finished","confidence":93.75}"#
+		);
 	}
 
 	#[tokio::test]
-	async fn block_header_route_ok() {
+	async fn block_route_wait_for_timeout() {
 		let config = RuntimeConfig::default();
-		let state = Arc::new(Mutex::new(State {
-			latest: 1,
-			header_verified: Some(BlockRange::init(1)),
-			..Default::default()
-		}));
-		let database = MockDatabase {
-			header: Some(header()),
-			..Default::default()
-		};
-		let route = super::block_header_route(config, state, database);
+		let state = Arc::new(Mutex::new(State::default()));
+		{
+			let mut state = state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+		}
+		let route = super::block_route(config, state, MockDatabase::default(), waits(1));
 		let response = warp::test::request()
 			.method("GET")
-			.path("/v2/blocks/1/header")
+			.path("/v2/blocks/10?wait_for=finished&timeout=500ms")
 			.reply(&route)
 			.await;
+
+		assert_eq!(response.status(), StatusCode::OK);
 		assert_eq!(
 			response.body(),
-			r#"{"hash":"0xffb84ad27c9d095b0f4790bd168637a6891f962d5a738e2dc7fbdb7a482cce83","parent_hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":1,"state_root":"0x0000000000000000000000000000000000000000000000000000000000000000","extrinsics_root":"0x0000000000000000000000000000000000000000000000000000000000000000","extension":{"rows":0,"cols":0,"data_root":"0x0000000000000000000000000000000000000000000000000000000000000000","commitments":[],"app_lookup":{"size":0,"index":[]}}}"#
+			r#"{"status":"verifying-confidence","confidence":null}"#
 		);
 	}
 
-	#[test_case(0, r#"Block data is not available"#  ; "Block is unavailable")]
-	#[test_case(6, r#"Block data is not available"#  ; "Block is pending")]
-	#[test_case(8, r#"Block data is not available"#  ; "Block is in verifying-data state")]
-	#[test_case(9, r#"Block data is not available"#  ; "Block is in verifying-confidence state")]
-	#[test_case(10, r#"Block data is not available"#  ; "Block is in verifying-header state")]
 	#[tokio::test]
-	async fn block_data_route_bad_request(block_number: u32, expected: &str) {
-		let config = RuntimeConfig {
+	async fn block_route_wait_limit() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State::default()));
+		{
+			let mut state = state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+		}
+		let route = super::block_route(config, state, MockDatabase::default(), waits(0));
+
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/10?wait_for=finished&timeout=500ms")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+		// Requests without waiting are not limited
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/10")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+	}
+
+	#[test_case("/v2/blocks?from=10&to=9" ; "Invalid range")]
+	#[test_case("/v2/blocks?from=0&to=1000" ; "Range too large")]
+	#[tokio::test]
+	async fn blocks_route_bad_request(path: &str) {
+		let state = Arc::new(Mutex::new(State::default()));
+		let route = super::blocks_route(RuntimeConfig::default(), state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path(path)
+			.reply(&route)
+			.await;
+
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+	}
+
+	#[tokio::test]
+	async fn blocks_route() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State::default()));
+		{
+			let mut state = state.lock().unwrap();
+			state.latest = 11;
+			state.header_verified.set(10);
+			state.header_verified.set(11);
+			state.data_verified.set(10);
+		}
+		let route = super::blocks_route(
+			config,
+			state,
+			MockDatabase {
+				confidence: Some(4),
+				..Default::default()
+			},
+		);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks?from=9&to=20")
+			.reply(&route)
+			.await;
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			response.body(),
+			r#"{"blocks":[{"block_number":9,"status":"unavailable","confidence":93.75},{"block_number":10,"status":"finished","confidence":93.75},{"block_number":11,"status":"verifying-confidence","confidence":93.75}]}"#
+		);
+	}
+
+	#[test_case(0, r#"Block header is not available"#  ; "Block is unavailable")]
+	#[test_case(6, r#"Block header is not available"#  ; "Block is pending")]
+	#[test_case(10, r#"Block header is not available"#  ; "Block is in verifying-header state")]
+	#[tokio::test]
+	async fn block_header_route_bad_request(block_number: u32, expected: &str) {
+		let config = RuntimeConfig {
+			sync_start_block: Some(1),
+			..Default::default()
+		};
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			sync_latest: Some(5),
+			header_verified: Some(BlockRange::init(9)),
+			..Default::default()
+		}));
+
+		let route = super::block_header_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/blocks/{block_number}/header"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), expected);
+	}
+
+	#[tokio::test]
+	async fn block_header_route_not_found() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			..Default::default()
+		}));
+
+		let route = super::block_header_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/11/header")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	fn header() -> DaHeader {
+		DaHeader {
+			parent_hash: H256::default(),
+			number: 1,
+			state_root: H256::default(),
+			extrinsics_root: H256::default(),
+			extension: HeaderExtension::V2(v2::HeaderExtension {
+				commitment: KateCommitment::default(),
+				app_lookup: CompactDataLookup {
+					size: 0,
+					index: vec![],
+				},
+			}),
+			digest: Digest { logs: vec![] },
+		}
+	}
+
+	#[tokio::test]
+	async fn block_header_route_ok() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State {
+			latest: 1,
+			header_verified: Some(BlockRange::init(1)),
+			..Default::default()
+		}));
+		let database = MockDatabase {
+			header: Some(header()),
+			..Default::default()
+		};
+		let route = super::block_header_route(config, state, database);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1/header")
+			.reply(&route)
+			.await;
+		assert_eq!(
+			response.body(),
+			r#"{"hash":"0xffb84ad27c9d095b0f4790bd168637a6891f962d5a738e2dc7fbdb7a482cce83","parent_hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":1,"state_root":"0x0000000000000000000000000000000000000000000000000000000000000000","extrinsics_root":"0x0000000000000000000000000000000000000000000000000000000000000000","extension":{"rows":0,"cols":0,"data_root":"0x0000000000000000000000000000000000000000000000000000000000000000","commitments":[],"app_lookup":{"size":0,"index":[]}}}"#
+		);
+	}
+
+	fn node_client() -> Client {
+		let (command_sender, _) = tokio::sync::mpsc::channel(1);
+		Client::new(command_sender)
+	}
+
+	#[test_case(0, r#"Block header is not available"#  ; "Block is unavailable")]
+	#[test_case(6, r#"Block header is not available"#  ; "Block is pending")]
+	#[test_case(10, r#"Block header is not available"#  ; "Block is in verifying-header state")]
+	#[tokio::test]
+	async fn block_data_proof_route_bad_request(block_number: u32, expected: &str) {
+		let config = RuntimeConfig {
+			sync_start_block: Some(1),
+			..Default::default()
+		};
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			sync_latest: Some(5),
+			header_verified: Some(BlockRange::init(9)),
+			..Default::default()
+		}));
+
+		let route =
+			super::block_data_proof_route(config, state, MockDatabase::default(), node_client());
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/blocks/{block_number}/data/0/proof"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), expected);
+	}
+
+	#[tokio::test]
+	async fn block_data_proof_route_not_found() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			..Default::default()
+		}));
+
+		let route =
+			super::block_data_proof_route(config, state, MockDatabase::default(), node_client());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/11/data/0/proof")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[test_case(0, r#"Block data is not available"#  ; "Block is unavailable")]
+	#[test_case(6, r#"Block data is not available"#  ; "Block is pending")]
+	#[test_case(8, r#"Block data is not available"#  ; "Block is in verifying-data state")]
+	#[test_case(9, r#"Block data is not available"#  ; "Block is in verifying-confidence state")]
+	#[test_case(10, r#"Block data is not available"#  ; "Block is in verifying-header state")]
+	#[tokio::test]
+	async fn block_data_route_bad_request(block_number: u32, expected: &str) {
+		let config = RuntimeConfig {
 			app_id: Some(1),
 			sync_start_block: Some(1),
 			..Default::default()
@@ -503,157 +1000,619 @@ mod tests {
 			..Default::default()
 		}));
 
-		let route = super::block_data_route(config, state, MockDatabase::default());
+		let route = super::block_data_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/blocks/{block_number}/data"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), expected);
+	}
+
+	#[tokio::test]
+	async fn block_data_route_not_found() {
+		let config = RuntimeConfig::default();
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			..Default::default()
+		}));
+
+		let route = super::block_data_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/11/data")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn block_data_route_ok_empty() {
+		let config = RuntimeConfig {
+			app_id: Some(1),
+			..Default::default()
+		};
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			header_verified: Some(BlockRange::init(5)),
+			confidence_achieved: Some(BlockRange::init(5)),
+			data_verified: Some(BlockRange::init(5)),
+			..Default::default()
+		}));
+
+		let route = super::block_data_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/5/data")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			response.body(),
+			r#"{"block_number":5,"data_transactions":[]}"#
+		);
+	}
+
+	#[tokio::test]
+	async fn block_data_route_ok() {
+		let config = RuntimeConfig {
+			app_id: Some(1),
+			..Default::default()
+		};
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			header_verified: Some(BlockRange::init(5)),
+			confidence_achieved: Some(BlockRange::init(5)),
+			data_verified: Some(BlockRange::init(5)),
+			..Default::default()
+		}));
+		let db = MockDatabase {
+			app_data: Some(vec![vec![
+				189, 1, 132, 0, 212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159,
+				214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125, 1,
+				50, 12, 43, 176, 19, 42, 23, 73, 70, 223, 198, 180, 103, 34, 60, 246, 184, 49, 140,
+				113, 174, 234, 229, 95, 71, 18, 92, 158, 185, 168, 140, 126, 12, 191, 156, 50, 234,
+				8, 4, 68, 137, 5, 156, 94, 209, 7, 169, 105, 62, 63, 1, 122, 253, 195, 112, 173,
+				239, 21, 73, 163, 240, 106, 109, 131, 0, 4, 0, 4, 29, 1, 20, 116, 101, 115, 116,
+				10,
+			]]),
+			..Default::default()
+		};
+
+		let route = super::block_data_route(config, state, db);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/5/data")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(
+			response.body(),
+			r#"{"block_number":5,"data_transactions":[{"data":"dGVzdAo=","extrinsic":"vQGEANQ1k8cV/dMcYRQavQSpn9aCLIVYhUzN45pWhOelbaJ9ATIMK7ATKhdJRt/GtGciPPa4MYxxrurlX0cSXJ65qIx+DL+cMuoIBESJBZxe0QepaT4/AXr9w3Ct7xVJo/BqbYMABAAEHQEUdGVzdAo="}]}"#
+		);
+	}
+
+	fn all_topics() -> HashSet<Topic> {
+		vec![
+			Topic::HeaderVerified,
+			Topic::ConfidenceAchieved,
+			Topic::DataVerified,
+		]
+		.into_iter()
+		.collect()
+	}
+
+	fn all_data_fields() -> HashSet<DataField> {
+		vec![DataField::Extrinsic, DataField::Data]
+			.into_iter()
+			.collect()
+	}
+
+	#[derive(Clone)]
+	struct MockSubmitter {
+		pub has_signer: bool,
+	}
+
+	#[async_trait]
+	impl transactions::Submit for MockSubmitter {
+		async fn submit(&self, _: SubmitRequest) -> anyhow::Result<SubmitResponse> {
+			Ok(SubmitResponse {
+				id: Uuid::new_v4(),
+				hash: H256::zero(),
+				manifest_hash: None,
+			})
+		}
+
+		async fn estimate(&self, request: EstimateRequest) -> anyhow::Result<EstimateResponse> {
+			Ok(EstimateResponse {
+				fee: "124000000000000000".to_string(),
+				weight: Weight {
+					ref_time: 220_000_000,
+					proof_size: 0,
+				},
+				length: 142,
+				dry_run: request.dry_run.then_some(DryRunResult {
+					valid: true,
+					error: None,
+				}),
+			})
+		}
+
+		fn has_signer(&self) -> bool {
+			self.has_signer
+		}
+
+		fn check_signer(&self, signer: Option<&str>, _: Option<u32>) -> anyhow::Result<()> {
+			match signer {
+				Some("unknown") => Err(anyhow::anyhow!("Signer unknown is not configured")),
+				_ => Ok(()),
+			}
+		}
+	}
+
+	#[derive(Clone, Default)]
+	struct MockDatabase {
+		confidence: Option<u32>,
+		header: Option<DaHeader>,
+		app_data: Option<AppData>,
+		extrinsic_indices: Option<Vec<u32>>,
+		transaction: Option<TransactionLocation>,
+		submission: Option<Submission>,
+		blob: Option<Blob>,
+		dht_columns: Option<Vec<u16>>,
+	}
+
+	impl Database for MockDatabase {
+		fn get_confidence(&self, _: u32) -> anyhow::Result<Option<u32>> {
+			Ok(self.confidence)
+		}
+
+		fn get_header(&self, _: u32) -> anyhow::Result<Option<DaHeader>> {
+			Ok(self.header.clone())
+		}
+
+		fn get_data(&self, _app_id: u32, _: u32) -> anyhow::Result<Option<AppData>> {
+			Ok(self.app_data.clone())
+		}
+
+		fn get_extrinsic_indices(&self, _: u32, _: u32) -> anyhow::Result<Option<Vec<u32>>> {
+			Ok(self.extrinsic_indices.clone())
+		}
+
+		fn get_transaction_location(&self, _: H256) -> anyhow::Result<Option<TransactionLocation>> {
+			Ok(self.transaction)
+		}
+
+		fn get_signer_transactions(
+			&self,
+			_: u32,
+			_: &str,
+			_: u32,
+			_: usize,
+		) -> anyhow::Result<Vec<TransactionLocation>> {
+			Ok(self.transaction.into_iter().collect())
+		}
+
+		fn get_submission(&self, _: Uuid) -> anyhow::Result<Option<Submission>> {
+			Ok(self.submission.clone())
+		}
+
+		fn get_blob(&self, _: H256) -> anyhow::Result<Option<Blob>> {
+			Ok(self.blob.clone())
+		}
+
+		fn get_dht_columns(&self, _: u32) -> anyhow::Result<Option<Vec<u16>>> {
+			Ok(self.dht_columns.clone())
+		}
+	}
+
+	#[derive(Clone)]
+	struct MockFetcher {
+		cells: Vec<DaCell>,
+	}
+
+	#[async_trait]
+	impl cells::Fetch for MockFetcher {
+		async fn fetch(
+			&self,
+			_: u32,
+			_: H256,
+			_: Dimensions,
+			_: &[[u8; 48]],
+			positions: &[Position],
+		) -> anyhow::Result<(Vec<DaCell>, Vec<Position>)> {
+			let (cells, missing) = positions.iter().partition::<Vec<_>, _>(|&position| {
+				self.cells.iter().any(|cell| cell.position.eq(position))
+			});
+			let cells = self
+				.cells
+				.iter()
+				.filter(|cell| cells.contains(&&cell.position))
+				.cloned()
+				.collect();
+			Ok((cells, missing.into_iter().cloned().collect()))
+		}
+	}
+
+	fn cells_header() -> DaHeader {
+		let commitment = [
+			128, 34, 252, 194, 232, 229, 27, 124, 216, 33, 253, 23, 251, 126, 112, 244, 7, 231, 73,
+			242, 0, 20, 5, 116, 175, 104, 27, 50, 45, 111, 127, 123, 202, 255, 63, 192, 243, 236,
+			62, 75, 104, 86, 36, 198, 134, 27, 182, 224,
+		];
+		DaHeader {
+			extension: HeaderExtension::V2(v2::HeaderExtension {
+				commitment: KateCommitment {
+					rows: 1,
+					cols: 4,
+					data_root: H256::default(),
+					commitment: [commitment, commitment].concat(),
+				},
+				app_lookup: CompactDataLookup {
+					size: 1,
+					index: vec![],
+				},
+			}),
+			..header()
+		}
+	}
+
+	fn cell() -> DaCell {
+		DaCell {
+			position: Position { row: 0, col: 2 },
+			content: [
+				183, 215, 10, 175, 218, 48, 236, 18, 30, 163, 215, 125, 205, 130, 176, 227, 133,
+				157, 194, 35, 153, 144, 141, 7, 208, 133, 170, 79, 27, 176, 202, 22, 111, 63, 107,
+				147, 93, 44, 82, 137, 78, 32, 161, 175, 214, 152, 125, 50, 247, 52, 138, 161, 52,
+				83, 193, 255, 17, 235, 98, 10, 88, 241, 25, 186, 3, 174, 139, 200, 128, 117, 255,
+				213, 200, 4, 46, 244, 219, 5, 131, 0,
+			],
+		}
+	}
+
+	fn cells_setup() -> (RuntimeConfig, Arc<Mutex<State>>, MockDatabase) {
+		let state = Arc::new(Mutex::new(State {
+			latest: 1,
+			header_verified: Some(BlockRange::init(1)),
+			..Default::default()
+		}));
+		let database = MockDatabase {
+			header: Some(cells_header()),
+			..Default::default()
+		};
+		(RuntimeConfig::default(), state, database)
+	}
+
+	#[tokio::test]
+	async fn block_cells_route_commitments_mismatch() {
+		let (config, state, mut database) = cells_setup();
+		let mut header = cells_header();
+		if let HeaderExtension::V2(extension) = &mut header.extension {
+			extension.commitment.commitment.truncate(48);
+		}
+		database.header = Some(header);
+		let fetcher = Arc::new(MockFetcher {
+			cells: vec![cell()],
+		});
+		let route = super::block_cells_route(config, state, database, fetcher);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1/cells?positions=0:2")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+	}
+
+	#[test_case("/v2/blocks/2/cells?positions=0:0", StatusCode::NOT_FOUND, "Not Found" ; "Block not found")]
+	#[test_case("/v2/blocks/1/cells?positions=2:0", StatusCode::BAD_REQUEST, "Cell position is out of the 1x4 matrix" ; "Row out of matrix")]
+	#[test_case("/v2/blocks/1/cells?positions=0:4", StatusCode::BAD_REQUEST, "Cell position is out of the 1x4 matrix" ; "Column out of matrix")]
+	#[tokio::test]
+	async fn block_cells_route_error(path: &str, status: StatusCode, expected: &str) {
+		let (config, state, database) = cells_setup();
+		let fetcher = Arc::new(MockFetcher {
+			cells: vec![cell()],
+		});
+		let route = super::block_cells_route(config, state, database, fetcher);
+		let response = warp::test::request()
+			.method("GET")
+			.path(path)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), status);
+		assert_eq!(response.body(), expected);
+	}
+
+	#[tokio::test]
+	async fn block_cells_route_ok() {
+		let (config, state, database) = cells_setup();
+		let fetcher = Arc::new(MockFetcher {
+			cells: vec![cell()],
+		});
+		let route = super::block_cells_route(config, state, database, fetcher);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1/cells?positions=0:2,1:1")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let response: CellsResponse = serde_json::from_slice(response.body()).unwrap();
+		assert_eq!(response.block_number, 1);
+		assert_eq!(response.cells.len(), 1);
+		let fetched = DaCell::from(response.cells[0].clone());
+		assert_eq!(fetched.position, cell().position);
+		assert_eq!(fetched.content, cell().content);
+		assert_eq!(response.missing, vec![CellPosition { row: 1, col: 1 }]);
+	}
+
+	#[tokio::test]
+	async fn verify_cells_route_bad_request() {
+		let (config, state, database) = cells_setup();
+		let verifier = test_verifier(Arc::new(couscous::public_params()));
+		let route = super::verify_cells_route(config, state, database, verifier);
+		let request = VerifyCellsRequest {
+			block_number: 1,
+			cells: vec![],
+		};
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/verify/cells")
+			.json(&request)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), "At least one cell is required");
+	}
+
+	#[tokio::test]
+	async fn verify_cells_route() {
+		let (config, state, database) = cells_setup();
+		let verifier = test_verifier(Arc::new(couscous::public_params()));
+		let route = super::verify_cells_route(config, state, database, verifier);
+
+		let mut invalid = cell();
+		invalid.position = Position { row: 0, col: 1 };
+		let request = VerifyCellsRequest {
+			block_number: 1,
+			cells: vec![cell().into(), invalid.into()],
+		};
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/verify/cells")
+			.json(&request)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let response: VerifyCellsResponse = serde_json::from_slice(response.body()).unwrap();
+		assert_eq!(response.block_number, 1);
+		assert!(response
+			.unverified
+			.contains(&CellPosition { row: 0, col: 1 }));
+		assert_eq!(response.verified.len() + response.unverified.len(), 2);
+	}
+
+	fn transactions_setup() -> (RuntimeConfig, Arc<Mutex<State>>, MockDatabase) {
+		let state = Arc::new(Mutex::new(State {
+			latest: 10,
+			header_verified: Some(BlockRange::init(5)),
+			confidence_achieved: Some(BlockRange::init(5)),
+			data_verified: Some(BlockRange::init(5)),
+			..Default::default()
+		}));
+		let extrinsic = "vQGEANQ1k8cV/dMcYRQavQSpn9aCLIVYhUzN45pWhOelbaJ9ATIMK7ATKhdJRt/GtGciPPa4MYxxrurlX0cSXJ65qIx+DL+cMuoIBESJBZxe0QepaT4/AXr9w3Ct7xVJo/BqbYMABAAEHQEUdGVzdAo=";
+		let db = MockDatabase {
+			confidence: Some(4),
+			app_data: Some(vec![general_purpose::STANDARD.decode(extrinsic).unwrap()]),
+			extrinsic_indices: Some(vec![3]),
+			transaction: Some(TransactionLocation {
+				app_id: 1,
+				block_number: 5,
+				index: 0,
+			}),
+			..Default::default()
+		};
+		(RuntimeConfig::default(), state, db)
+	}
+
+	const TRANSACTION_HASH: &str =
+		"0x2cc86c8e42a3f0cfb45c2e8b48e3be2a73aa7f9439cfe4cf95725e9f7cb71007";
+
+	#[tokio::test]
+	async fn transaction_route_not_found() {
+		let (config, state, _) = transactions_setup();
+		let route = super::transaction_route(config, state, MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/transactions/{TRANSACTION_HASH}"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn transaction_route_ok() {
+		let (config, state, db) = transactions_setup();
+		let route = super::transaction_route(config, state, db);
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/transactions/{TRANSACTION_HASH}"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let response: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+		assert_eq!(response["app_id"], 1);
+		assert_eq!(response["block_number"], 5);
+		assert_eq!(response["status"], "finished");
+		assert_eq!(response["confidence"], 93.75);
+		assert_eq!(response["transaction"]["hash"], TRANSACTION_HASH);
+		assert_eq!(response["transaction"]["index"], 3);
+		assert_eq!(response["transaction"]["data"], "dGVzdAo=");
+	}
+
+	#[test_case("/v2/apps/1/transactions" ; "Missing signer")]
+	#[test_case("/v2/apps/1/transactions?signer=5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY&limit=1001" ; "Limit too large")]
+	#[tokio::test]
+	async fn signer_transactions_route_bad_request(path: &str) {
+		let (config, state, db) = transactions_setup();
+		let route = super::signer_transactions_route(config, state, db);
+		let response = warp::test::request()
+			.method("GET")
+			.path(path)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+	}
+
+	#[tokio::test]
+	async fn signer_transactions_route() {
+		let (config, state, db) = transactions_setup();
+		let route = super::signer_transactions_route(config, state, db);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/apps/1/transactions?signer=5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let response: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+		let transactions = response["transactions"].as_array().unwrap();
+		assert_eq!(transactions.len(), 1);
+		assert_eq!(transactions[0]["transaction"]["hash"], TRANSACTION_HASH);
+		assert_eq!(
+			transactions[0]["transaction"]["signer"],
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+		);
+	}
+
+	#[test_case("/v2/apps/1/data?from=1" ; "Missing range end")]
+	#[test_case("/v2/apps/1/data?from=5&to=1" ; "Invalid range")]
+	#[test_case("/v2/apps/1/data?from=1&to=1001" ; "Range too large")]
+	#[tokio::test]
+	async fn app_data_route_bad_request(path: &str) {
+		let route = super::app_data_route(MockDatabase::default());
 		let response = warp::test::request()
 			.method("GET")
-			.path(&format!("/v2/blocks/{block_number}/data"))
+			.path(path)
 			.reply(&route)
 			.await;
 		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-		assert_eq!(response.body(), expected);
 	}
 
 	#[tokio::test]
-	async fn block_data_route_not_found() {
-		let config = RuntimeConfig::default();
-		let state = Arc::new(Mutex::new(State {
-			latest: 10,
-			..Default::default()
-		}));
+	async fn app_data_route_empty() {
+		let route = super::app_data_route(MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/apps/1/data?from=1&to=3")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(response.body().is_empty());
+	}
 
-		let route = super::block_data_route(config, state, MockDatabase::default());
+	#[tokio::test]
+	async fn app_data_route() {
+		let (_, _, db) = transactions_setup();
+		let route = super::app_data_route(db);
 		let response = warp::test::request()
 			.method("GET")
-			.path("/v2/blocks/11/data")
+			.path("/v2/apps/1/data?from=1&to=3")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+
+		let body = std::str::from_utf8(response.body()).unwrap();
+		let lines = body
+			.lines()
+			.map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(lines.len(), 3);
+		for (line, block_number) in lines.iter().zip(1..) {
+			assert_eq!(line["block_number"], block_number);
+			assert_eq!(line["confidence"], 93.75);
+			assert_eq!(line["data_transactions"][0]["hash"], TRANSACTION_HASH);
+		}
+	}
+
+	#[tokio::test]
+	async fn submission_route_not_found() {
+		let route = super::submission_route(MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/submissions/6d3a9d4c-bc86-4b0c-9fd1-5e3e5aa9a2e1")
 			.reply(&route)
 			.await;
 		assert_eq!(response.status(), StatusCode::NOT_FOUND);
 	}
 
 	#[tokio::test]
-	async fn block_data_route_ok_empty() {
-		let config = RuntimeConfig {
-			app_id: Some(1),
+	async fn submission_route() {
+		let id = to_uuid("6d3a9d4c-bc86-4b0c-9fd1-5e3e5aa9a2e1");
+		let db = MockDatabase {
+			submission: Some(Submission {
+				status: SubmissionStatus::Finalized,
+				block_hash: Some(H256::zero()),
+				index: Some(1),
+				updated_at: 1700000000,
+				..Submission::new(id, H256::from_str(TRANSACTION_HASH).unwrap())
+			}),
 			..Default::default()
 		};
-		let state = Arc::new(Mutex::new(State {
-			latest: 10,
-			header_verified: Some(BlockRange::init(5)),
-			confidence_achieved: Some(BlockRange::init(5)),
-			data_verified: Some(BlockRange::init(5)),
-			..Default::default()
-		}));
-
-		let route = super::block_data_route(config, state, MockDatabase::default());
+		let route = super::submission_route(db);
 		let response = warp::test::request()
 			.method("GET")
-			.path("/v2/blocks/5/data")
+			.path("/v2/submissions/6d3a9d4c-bc86-4b0c-9fd1-5e3e5aa9a2e1")
 			.reply(&route)
 			.await;
 		assert_eq!(response.status(), StatusCode::OK);
 		assert_eq!(
 			response.body(),
-			r#"{"block_number":5,"data_transactions":[]}"#
+			r#"{"id":"6d3a9d4c-bc86-4b0c-9fd1-5e3e5aa9a2e1","hash":"0x2cc86c8e42a3f0cfb45c2e8b48e3be2a73aa7f9439cfe4cf95725e9f7cb71007","status":"finalized","block_hash":"0x0000000000000000000000000000000000000000000000000000000000000000","index":1,"updated_at":1700000000}"#
 		);
 	}
 
 	#[tokio::test]
-	async fn block_data_route_ok() {
-		let config = RuntimeConfig {
-			app_id: Some(1),
-			..Default::default()
-		};
-		let state = Arc::new(Mutex::new(State {
-			latest: 10,
-			header_verified: Some(BlockRange::init(5)),
-			confidence_achieved: Some(BlockRange::init(5)),
-			data_verified: Some(BlockRange::init(5)),
-			..Default::default()
-		}));
+	async fn blob_route_not_found() {
+		let route = super::blob_route(MockDatabase::default());
+		let response = warp::test::request()
+			.method("GET")
+			.path(&format!("/v2/blobs/{TRANSACTION_HASH}"))
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn blob_route() {
 		let db = MockDatabase {
-			app_data: Some(vec![vec![
-				189, 1, 132, 0, 212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159,
-				214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125, 1,
-				50, 12, 43, 176, 19, 42, 23, 73, 70, 223, 198, 180, 103, 34, 60, 246, 184, 49, 140,
-				113, 174, 234, 229, 95, 71, 18, 92, 158, 185, 168, 140, 126, 12, 191, 156, 50, 234,
-				8, 4, 68, 137, 5, 156, 94, 209, 7, 169, 105, 62, 63, 1, 122, 253, 195, 112, 173,
-				239, 21, 73, 163, 240, 106, 109, 131, 0, 4, 0, 4, 29, 1, 20, 116, 101, 115, 116,
-				10,
-			]]),
+			blob: Some(Blob {
+				app_id: 1,
+				block_number: 5,
+				data: b"blob".to_vec(),
+			}),
 			..Default::default()
 		};
-
-		let route = super::block_data_route(config, state, db);
+		let route = super::blob_route(db);
 		let response = warp::test::request()
 			.method("GET")
-			.path("/v2/blocks/5/data")
+			.path(&format!("/v2/blobs/{TRANSACTION_HASH}"))
 			.reply(&route)
 			.await;
 		assert_eq!(response.status(), StatusCode::OK);
 		assert_eq!(
 			response.body(),
-			r#"{"block_number":5,"data_transactions":[{"data":"dGVzdAo=","extrinsic":"vQGEANQ1k8cV/dMcYRQavQSpn9aCLIVYhUzN45pWhOelbaJ9ATIMK7ATKhdJRt/GtGciPPa4MYxxrurlX0cSXJ65qIx+DL+cMuoIBESJBZxe0QepaT4/AXr9w3Ct7xVJo/BqbYMABAAEHQEUdGVzdAo="}]}"#
+			r#"{"manifest_hash":"0x2cc86c8e42a3f0cfb45c2e8b48e3be2a73aa7f9439cfe4cf95725e9f7cb71007","app_id":1,"block_number":5,"data":"YmxvYg=="}"#
 		);
 	}
 
-	fn all_topics() -> HashSet<Topic> {
-		vec![
-			Topic::HeaderVerified,
-			Topic::ConfidenceAchieved,
-			Topic::DataVerified,
-		]
-		.into_iter()
-		.collect()
-	}
-
-	fn all_data_fields() -> HashSet<DataField> {
-		vec![DataField::Extrinsic, DataField::Data]
-			.into_iter()
-			.collect()
-	}
-
-	#[derive(Clone)]
-	struct MockSubmitter {
-		pub has_signer: bool,
-	}
-
-	#[async_trait]
-	impl transactions::Submit for MockSubmitter {
-		async fn submit(&self, _: Transaction) -> anyhow::Result<SubmitResponse> {
-			Ok(SubmitResponse {
-				block_hash: H256::random(),
-				hash: H256::random(),
-				index: 0,
-			})
-		}
-
-		fn has_signer(&self) -> bool {
-			self.has_signer
-		}
-	}
-
-	#[derive(Clone, Default)]
-	struct MockDatabase {
-		confidence: Option<u32>,
-		header: Option<DaHeader>,
-		app_data: Option<AppData>,
-	}
-
-	impl Database for MockDatabase {
-		fn get_confidence(&self, _: u32) -> anyhow::Result<Option<u32>> {
-			Ok(self.confidence)
-		}
-
-		fn get_header(&self, _: u32) -> anyhow::Result<Option<DaHeader>> {
-			Ok(self.header.clone())
-		}
-
-		fn get_data(&self, _app_id: u32, _: u32) -> anyhow::Result<Option<AppData>> {
-			Ok(self.app_data.clone())
-		}
-	}
-
-	#[test_case(r#"{"raw":""}"#, b"Request body deserialize error: unknown variant `raw`" ; "Invalid json schema")]
+	#[test_case(r#"{"raw":""}"#, b"Request body deserialize error: unknown field `raw`" ; "Invalid json schema")]
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K","app":1}"#, b"Request body deserialize error: unknown field `app`" ; "Misspelled field")]
+	#[test_case(r#"{"signer":"default"}"#, b"Request body deserialize error: Either data or extrinsic field is required" ; "Missing transaction")]
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K","extrinsic":"dHJhbnNhY3Rpb24K"}"#, b"Request body deserialize error: Only one of data or extrinsic fields is allowed" ; "Both data and extrinsic")]
 	#[test_case(r#"{"data":"dHJhbnooNhY3Rpb24:"}"#, b"Request body deserialize error: Invalid byte" ; "Invalid base64 value")]
 	#[tokio::test]
 	async fn submit_route_bad_request(json: &str, message: &[u8]) {
@@ -680,7 +1639,21 @@ mod tests {
 		assert_eq!(response.status(), StatusCode::NOT_FOUND);
 	}
 
+	#[tokio::test]
+	async fn submit_route_unknown_signer() {
+		let route = super::submit_route(Some(Arc::new(MockSubmitter { has_signer: true })));
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/submit")
+			.body(r#"{"data":"dHJhbnNhY3Rpb24K","signer":"unknown"}"#)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), "Signer unknown is not configured");
+	}
+
 	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K"}"# ; "No errors in case of submitted data")]
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K","signer":"default","app_id":2}"# ; "No errors in case of submitted data with signer and app")]
 	#[test_case(r#"{"extrinsic":"dHJhbnNhY3Rpb24K"}"# ; "No errors in case of submitted extrinsic")]
 	#[tokio::test]
 	async fn submit_route_extrinsic(body: &str) {
@@ -696,6 +1669,55 @@ mod tests {
 		let _ = serde_json::to_string_pretty(&response).unwrap();
 	}
 
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K"}"#, false ; "Estimate data transaction")]
+	#[test_case(r#"{"extrinsic":"dHJhbnNhY3Rpb24K","dry_run":true}"#, true ; "Dry run extrinsic")]
+	#[tokio::test]
+	async fn submit_estimate_route(body: &str, dry_run: bool) {
+		let route =
+			super::submit_estimate_route(Some(Arc::new(MockSubmitter { has_signer: true })));
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/submit/estimate")
+			.body(body)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+		let response: EstimateResponse = serde_json::from_slice(response.body()).unwrap();
+		assert_eq!(response.fee, "124000000000000000");
+		assert_eq!(response.dry_run.is_some(), dry_run);
+	}
+
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K","dryrun":true}"#, b"Request body deserialize error: unknown field `dryrun`" ; "Misspelled field")]
+	#[test_case(r#"{"dry_run":true}"#, b"Request body deserialize error: Either data or extrinsic field is required" ; "Missing transaction")]
+	#[test_case(r#"{"data":"dHJhbnNhY3Rpb24K","dry_run":1}"#, b"Request body deserialize error: invalid type" ; "Invalid dry run")]
+	#[tokio::test]
+	async fn submit_estimate_route_bad_request(json: &str, message: &[u8]) {
+		let route =
+			super::submit_estimate_route(Some(Arc::new(MockSubmitter { has_signer: true })));
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/submit/estimate")
+			.body(json)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert!(response.body().starts_with(message));
+	}
+
+	#[tokio::test]
+	async fn submit_estimate_route_unknown_signer() {
+		let route =
+			super::submit_estimate_route(Some(Arc::new(MockSubmitter { has_signer: true })));
+		let response = warp::test::request()
+			.method("POST")
+			.path("/v2/submit/estimate")
+			.body(r#"{"data":"dHJhbnNhY3Rpb24K","signer":"unknown"}"#)
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert_eq!(response.body(), "Signer unknown is not configured");
+	}
+
 	#[tokio::test]
 	async fn subscriptions_route() {
 		let clients = WsClients::default();
@@ -722,13 +1744,66 @@ mod tests {
 		assert!(client.subscription == expected);
 	}
 
+	#[tokio::test]
+	async fn events_route() {
+		let (events, _) = tokio::sync::broadcast::channel::<PublishMessage>(16);
+		let route = super::events_route(events.clone());
+		let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+		tokio::spawn(server);
+
+		let uri = format!("http://{addr}/v2/events?topics=confidence-achieved")
+			.parse()
+			.unwrap();
+		let response = hyper::Client::new().get(uri).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let data_verified =
+			r#"{"topic":"data-verified","message":{"block_number":1,"data_transactions":[]}}"#;
+		let confidence_achieved =
+			r#"{"topic":"confidence-achieved","message":{"block_number":1,"confidence":93.75}}"#;
+		for message in [data_verified, confidence_achieved] {
+			events.send(serde_json::from_str(message).unwrap()).unwrap();
+		}
+
+		let mut body = response.into_body();
+		let event = body.data().await.unwrap().unwrap();
+		let event = String::from_utf8(event.to_vec()).unwrap();
+		assert!(event.contains("event:confidence-achieved"));
+		assert!(event.contains(confidence_achieved));
+	}
+
+	#[test_case("/v2/events?topics=unknown" ; "Unknown topic")]
+	#[test_case("/v2/events?topics=header-verified&fields=unknown" ; "Unknown field")]
+	#[tokio::test]
+	async fn events_route_bad_request(path: &str) {
+		let (events, _) = tokio::sync::broadcast::channel::<PublishMessage>(16);
+		let route = super::events_route(events);
+		let response = warp::test::request()
+			.method("GET")
+			.path(path)
+			.reply(&route)
+			.await;
+
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+	}
+
 	struct MockSetup {
 		ws_client: warp::test::WsClient,
 		state: Arc<Mutex<State>>,
+		clients: WsClients,
+		client_uuid: String,
 	}
 
 	impl MockSetup {
 		async fn new(config: RuntimeConfig, submitter: Option<MockSubmitter>) -> Self {
+			Self::with_database(config, submitter, MockDatabase::default()).await
+		}
+
+		async fn with_database(
+			config: RuntimeConfig,
+			submitter: Option<MockSubmitter>,
+			db: MockDatabase,
+		) -> Self {
 			let client_uuid = uuid::Uuid::new_v4().to_string();
 			let clients = WsClients::default();
 			clients
@@ -743,6 +1818,8 @@ mod tests {
 				Node::default(),
 				submitter.map(Arc::new),
 				state.clone(),
+				db,
+				Auth::default(),
 			);
 			let ws_client = warp::test::ws()
 				.path(&format!("/v2/ws/{client_uuid}"))
@@ -750,7 +1827,12 @@ mod tests {
 				.await
 				.expect("handshake");
 
-			MockSetup { ws_client, state }
+			MockSetup {
+				ws_client,
+				state,
+				clients,
+				client_uuid,
+			}
 		}
 
 		async fn ws_send_text(&mut self, message: &str) -> String {
@@ -807,6 +1889,99 @@ mod tests {
 		assert_eq!(expected, test.ws_send_text(status_request).await);
 	}
 
+	#[tokio::test]
+	async fn ws_route_block() {
+		let db = MockDatabase {
+			confidence: Some(4),
+			..Default::default()
+		};
+		let mut test = MockSetup::with_database(RuntimeConfig::default(), None, db).await;
+		{
+			let mut state = test.state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+			state.data_verified.set(10);
+		}
+
+		let request = r#"{"type":"block","request_id":"0c8d2a4e-34a5-4f8a-9c36-7d0b1c5f3a61","message":{"block_number":10}}"#;
+		let response = test.ws_send_text(request).await;
+
+		let WsResponse::Block(response) = serde_json::from_str(&response).unwrap() else {
+			panic!("Invalid response");
+		};
+		assert_eq!(
+			response.request_id,
+			to_uuid("0c8d2a4e-34a5-4f8a-9c36-7d0b1c5f3a61")
+		);
+		assert_eq!(response.message.status, BlockStatus::Finished);
+		assert_eq!(response.message.confidence, Some(93.75));
+	}
+
+	#[tokio::test]
+	async fn ws_route_block_not_found() {
+		let mut test = MockSetup::new(RuntimeConfig::default(), None).await;
+
+		let request = r#"{"type":"header","request_id":"2f6e5c1b-8d47-4a0e-b3b9-5e1f0d6c7a28","message":{"block_number":10}}"#;
+		let response = test.ws_send_text(request).await;
+
+		let WsError::Error(error) = serde_json::from_str(&response).unwrap();
+		assert_eq!(error.error_code, ErrorCode::NotFound);
+		assert_eq!(
+			error.request_id,
+			Some(to_uuid("2f6e5c1b-8d47-4a0e-b3b9-5e1f0d6c7a28"))
+		);
+	}
+
+	#[tokio::test]
+	async fn ws_route_data() {
+		let config = RuntimeConfig {
+			app_id: Some(1),
+			..Default::default()
+		};
+		let mut test = MockSetup::new(config, None).await;
+		{
+			let mut state = test.state.lock().unwrap();
+			state.latest = 10;
+			state.header_verified.set(10);
+			state.data_verified.set(10);
+		}
+
+		let request = r#"{"type":"data","request_id":"a1b7c3d9-5e2f-4a6b-8c0d-1e3f5a7b9c2d","message":{"block_number":10,"fields":["data"]}}"#;
+		let response = test.ws_send_text(request).await;
+
+		let WsResponse::Data(response) = serde_json::from_str(&response).unwrap() else {
+			panic!("Invalid response");
+		};
+		assert_eq!(
+			response.request_id,
+			to_uuid("a1b7c3d9-5e2f-4a6b-8c0d-1e3f5a7b9c2d")
+		);
+		assert_eq!(response.message.block_number, 10);
+		assert!(response.message.data_transactions.is_empty());
+	}
+
+	#[tokio::test]
+	async fn ws_route_subscription() {
+		let mut test = MockSetup::new(RuntimeConfig::default(), None).await;
+
+		let request = r#"{"type":"subscription","request_id":"7d3e9f1a-2b4c-4d6e-8f0a-b1c2d3e4f5a6","message":{"topics":["header-verified"],"data_fields":["data"]}}"#;
+		let response = test.ws_send_text(request).await;
+
+		let WsResponse::SubscriptionUpdated(response) = serde_json::from_str(&response).unwrap()
+		else {
+			panic!("Invalid response");
+		};
+		let expected = Subscription {
+			topics: HashSet::from([Topic::HeaderVerified]),
+			data_fields: HashSet::from([DataField::Data]),
+		};
+		assert!(response.message == expected);
+
+		let clients = test.clients.0.read().await;
+		let client = clients.get(&test.client_uuid).unwrap();
+		assert!(client.subscription == expected);
+	}
+
 	#[test_case("",  "Failed to parse request" ; "Empty request")]
 	#[test_case("abcd",  "Failed to parse request" ; "Invalid json")]
 	#[test_case("{}",  "Failed to parse request" ; "Empty json")]
@@ -826,6 +2001,7 @@ mod tests {
 	#[test_case(r#"{"type":"submit","request_id":"537a3c39-c029-4283-9612-17465bf7cfd1","message":{"data":"dHJhbnNhY3Rpb24K"}}"#, Some(false), Some("537a3c39-c029-4283-9612-17465bf7cfd1"), "Signer is not configured" ; "No signer")]
 	#[test_case(r#"{"type":"submit","request_id":"36bc1f28-e093-422f-964b-1cb1b3882baf","message":{"extrinsic":""}}"#, Some(false), Some("36bc1f28-e093-422f-964b-1cb1b3882baf"), "Transaction is empty" ; "Empty extrinsic")]
 	#[test_case(r#"{"type":"submit","request_id":"cc60b2f3-d9ff-4c73-9632-d21d07f7b620","message":{"data":""}}"#, Some(true), Some("cc60b2f3-d9ff-4c73-9632-d21d07f7b620"), "Transaction is empty" ; "Empty data")]
+	#[test_case(r#"{"type":"submit","request_id":"0b1c8e4a-2f8d-4a57-9d43-5c4a9d1e7f21","message":{"data":"dHJhbnNhY3Rpb24K","signer":"unknown"}}"#, Some(true), Some("0b1c8e4a-2f8d-4a57-9d43-5c4a9d1e7f21"), "Signer unknown is not configured" ; "Unknown signer")]
 	#[test_case(r#"{"type":"submit","request_id":"9181df86-22f0-42a1-a965-60adb9fc6bdc","message":{"extrinsic":"bad"}}"#, Some(false), None, "Failed to parse request" ; "Bad extrinsic")]
 	#[test_case(r#"{"type":"submit","request_id":"78cd7b7b-ba70-48e9-a1da-96b370db4d8f","message":{"data":"bad"}}"#, Some(true), None, "Failed to parse request" ; "Bad data")]
 	#[tokio::test]
@@ -860,7 +2036,7 @@ mod tests {
 		};
 		let expected_request_id = to_uuid("fca2ff0c-7a26-42a2-a6f0-d0aeeaba8a9a");
 		assert_eq!(response.request_id, expected_request_id);
-		assert_eq!(response.message.index, 0);
+		assert_eq!(response.message.hash, H256::zero());
 	}
 
 	#[tokio::test]
@@ -878,6 +2054,6 @@ mod tests {
 		};
 		let expected_request_id = to_uuid("fca2ff0c-7a26-42a2-a6f0-d0aeeaba8a9a");
 		assert_eq!(response.request_id, expected_request_id);
-		assert_eq!(response.message.index, 0);
+		assert_eq!(response.message.hash, H256::zero());
 	}
 }
@@ -0,0 +1,634 @@
+//! OpenAPI specification of the light client HTTP API.
+//!
+//! Request and response schemas are generated from the API types, so the specification stays in sync with the
+//! serialized messages. Websocket messages are not described by OpenAPI operations, so their schemas
+//! (`Request`, `Payload`, `WsResponse`, `WsError` and `PublishMessage`) are included in the components section,
+//! and referenced from the websocket endpoint.
+
+use schemars::{gen::SchemaSettings, JsonSchema};
+use serde_json::{json, Map, Value};
+
+use super::types::{
+	BlobResponse, Block, BlockStatus, BlocksResponse, CellsResponse, DataResponse, Error,
+	EstimateRequest, EstimateResponse, Header, Payload, PublishMessage, Request, Status,
+	Submission, SubmitRequest, SubmitResponse, Subscription, SubscriptionId, TransactionResponse,
+	TransactionsResponse, VerifyCellsRequest, VerifyCellsResponse, Version, WsError, WsResponse,
+};
+use crate::{
+	api::v1::types::{
+		ConfidenceResponse, ExtrinsicsDataResponse, LatestBlockResponse, Status as StatusV1,
+	},
+	proof::DataInclusionProof,
+	types::Mode as ModeV1,
+};
+
+struct Spec {
+	generator: schemars::gen::SchemaGenerator,
+	paths: Map<String, Value>,
+}
+
+impl Spec {
+	fn new() -> Self {
+		Spec {
+			generator: SchemaSettings::openapi3().into_generator(),
+			paths: Map::new(),
+		}
+	}
+
+	/// Returns reference to the schema of the given type, adding it to the components
+	fn schema<T: JsonSchema>(&mut self) -> Value {
+		let schema = self.generator.subschema_for::<T>();
+		serde_json::to_value(schema).expect("Schema is serializable")
+	}
+
+	fn json<T: JsonSchema>(&mut self, description: &str) -> Value {
+		json!({
+			"description": description,
+			"content": { "application/json": { "schema": self.schema::<T>() } }
+		})
+	}
+
+	fn get(&mut self, path: &str, operation: Value) {
+		self.paths
+			.insert(path.to_string(), json!({ "get": operation }));
+	}
+
+	fn post(&mut self, path: &str, operation: Value) {
+		self.paths
+			.insert(path.to_string(), json!({ "post": operation }));
+	}
+
+	fn into_value(mut self, version: &str) -> Value {
+		json!({
+			"openapi": "3.0.3",
+			"info": {
+				"title": "Avail Light Client API",
+				"version": version,
+			},
+			"paths": self.paths,
+			// API key is required only if authentication is enabled (see `api::auth`)
+			"security": [{ "bearer": [] }, { "api_key": [] }, {}],
+			"components": {
+				"schemas": self.generator.take_definitions(),
+				"securitySchemes": {
+					"bearer": { "type": "http", "scheme": "bearer" },
+					"api_key": { "type": "apiKey", "in": "header", "name": "X-API-Key" },
+				},
+			},
+		})
+	}
+}
+
+fn path_parameter(name: &str, description: &str) -> Value {
+	json!({
+		"name": name,
+		"in": "path",
+		"required": true,
+		"description": description,
+		"schema": { "type": "integer", "format": "uint32", "minimum": 0 }
+	})
+}
+
+fn block_number() -> Value {
+	path_parameter("block_number", "Block number")
+}
+
+fn query_parameter(name: &str, required: bool, description: &str, schema: Value) -> Value {
+	json!({
+		"name": name,
+		"in": "query",
+		"required": required,
+		"description": description,
+		"schema": schema
+	})
+}
+
+/// Error responses of the v2 API, which contain error message in the response body
+fn error(description: &str) -> Value {
+	json!({
+		"description": description,
+		"content": { "text/plain": { "schema": { "type": "string" } } }
+	})
+}
+
+/// Error responses of the v1 API, which contain JSON encoded error message
+fn error_v1(description: &str) -> Value {
+	json!({
+		"description": description,
+		"content": { "application/json": { "schema": { "type": "string" } } }
+	})
+}
+
+fn v1_paths(spec: &mut Spec) {
+	let operation = json!({
+		"tags": ["v1"],
+		"summary": "Gets the mode of the light client",
+		"responses": { "200": spec.json::<ModeV1>("Light client mode") },
+	});
+	spec.get("/v1/mode", operation);
+
+	let operation = json!({
+		"tags": ["v1"],
+		"summary": "Gets the latest processed block",
+		"responses": { "200": spec.json::<LatestBlockResponse>("Latest block") },
+	});
+	spec.get("/v1/latest_block", operation);
+
+	let operation = json!({
+		"tags": ["v1"],
+		"summary": "Gets the confidence of the block",
+		"parameters": [block_number()],
+		"responses": {
+			"200": spec.json::<ConfidenceResponse>("Block confidence"),
+			"400": error_v1("Block is not synced"),
+			"404": error_v1("Block is not found"),
+			"500": error_v1("Internal server error"),
+		},
+	});
+	spec.get("/v1/confidence/{block_number}", operation);
+
+	let operation = json!({
+		"tags": ["v1"],
+		"summary": "Gets the application data of the block",
+		"parameters": [
+			block_number(),
+			{
+				"name": "decode",
+				"in": "query",
+				"required": false,
+				"description": "Returns base64 encoded data instead of extrinsics if true",
+				"schema": { "type": "boolean" }
+			}
+		],
+		"responses": {
+			"200": spec.json::<ExtrinsicsDataResponse>("Block application data"),
+			"400": error_v1("Block is not synced"),
+			"401": error_v1("Block is being processed"),
+			"404": error_v1("Block is not found"),
+			"500": error_v1("Internal server error"),
+		},
+	});
+	spec.get("/v1/appdata/{block_number}", operation);
+
+	let operation = json!({
+		"tags": ["v1"],
+		"summary": "Gets the status of the light client",
+		"responses": {
+			"200": spec.json::<StatusV1>("Light client status"),
+			"404": error_v1("Status is not available"),
+			"500": error_v1("Internal server error"),
+		},
+	});
+	spec.get("/v1/status", operation);
+}
+
+fn v2_paths(spec: &mut Spec) {
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the version of the light client and the compatible network",
+		"responses": { "200": spec.json::<Version>("Light client version") },
+	});
+	spec.get("/v2/version", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the current status and active modes of the light client",
+		"responses": {
+			"200": spec.json::<Status>("Light client status"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/status", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the block status and confidence",
+		"description": "If `wait_for` is set, response is held until the block reaches the given status, \
+			or the timeout expires. In the latter case, the current block status is returned.",
+		"parameters": [
+			block_number(),
+			query_parameter(
+				"wait_for",
+				false,
+				"Block status to wait for",
+				spec.schema::<BlockStatus>()
+			),
+			query_parameter(
+				"timeout",
+				false,
+				"Maximum waiting time in seconds (e.g. `30s`) or milliseconds (e.g. `500ms`), default is 30s, up to 120s",
+				json!({ "type": "string" })
+			)
+		],
+		"responses": {
+			"200": spec.json::<Block>("Block status"),
+			"404": error("Block is not found"),
+			"429": error("Too many requests are waiting for the block status"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blocks/{block_number}", operation);
+
+	let block_range = |name: &str| {
+		let schema = json!({ "type": "integer", "format": "uint32", "minimum": 0 });
+		query_parameter(name, true, "Block range bound (inclusive)", schema)
+	};
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the status and confidence of the blocks in the range, up to the latest block",
+		"parameters": [block_range("from"), block_range("to")],
+		"responses": {
+			"200": spec.json::<BlocksResponse>("Block statuses"),
+			"400": error("Block range is not valid, or contains more than 1000 blocks"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blocks", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the block header, if header is verified",
+		"parameters": [block_number()],
+		"responses": {
+			"200": spec.json::<Header>("Block header"),
+			"400": error("Header is not available"),
+			"404": error("Block is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blocks/{block_number}/header", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the block data, if data is verified",
+		"parameters": [
+			block_number(),
+			{
+				"name": "fields",
+				"in": "query",
+				"required": false,
+				"description": "Comma separated list of the data transaction fields (data, extrinsic, hash, index, signer, nonce, tip, call)",
+				"schema": { "type": "string" },
+				"example": "data,extrinsic"
+			}
+		],
+		"responses": {
+			"200": spec.json::<DataResponse>("Block data"),
+			"400": error("Data is not available"),
+			"404": error("Block is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blocks/{block_number}/data", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the verified proof of the data transaction inclusion into the block",
+		"parameters": [
+			block_number(),
+			path_parameter("transaction_index", "Index of the data transaction in the block")
+		],
+		"responses": {
+			"200": spec.json::<DataInclusionProof>("Data inclusion proof"),
+			"400": error("Proof is not available"),
+			"404": error("Block is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get(
+		"/v2/blocks/{block_number}/data/{transaction_index}/proof",
+		operation,
+	);
+
+	let positions = json!({ "type": "string" });
+	let positions = query_parameter(
+		"positions",
+		true,
+		"Comma separated list of the cell positions in row:col format (up to 1024)",
+		positions,
+	);
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the block cells with proofs, if header is verified",
+		"parameters": [block_number(), positions],
+		"responses": {
+			"200": spec.json::<CellsResponse>("Block cells and commitments"),
+			"400": error("Header is not available, or cell positions are not valid"),
+			"404": error("Block is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blocks/{block_number}/cells", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Verifies the cell proofs against the commitments of the verified block header",
+		"requestBody": {
+			"required": true,
+			"content": { "application/json": { "schema": spec.schema::<VerifyCellsRequest>() } }
+		},
+		"responses": {
+			"200": spec.json::<VerifyCellsResponse>("Verified and unverified cell positions"),
+			"400": error("Header is not available, or cell positions are not valid"),
+			"404": error("Block is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.post("/v2/verify/cells", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the verified application transaction with the given hash, and the status of its block",
+		"parameters": [{
+			"name": "hash",
+			"in": "path",
+			"required": true,
+			"description": "Hex encoded extrinsic hash",
+			"schema": { "type": "string" }
+		}],
+		"responses": {
+			"200": spec.json::<TransactionResponse>("Application transaction"),
+			"404": error("Transaction is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/transactions/{hash}", operation);
+
+	let signer = json!({ "type": "string" });
+	let signer = query_parameter("signer", true, "SS58 encoded address of the signer", signer);
+	let from = json!({ "type": "integer", "format": "uint32", "minimum": 0 });
+	let from = query_parameter("from", false, "First block to list from (default: 0)", from);
+	let limit = json!({ "type": "integer", "format": "uint", "minimum": 0, "maximum": 1000 });
+	let limit = query_parameter(
+		"limit",
+		false,
+		"Maximum number of items (default: 100)",
+		limit,
+	);
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Lists the verified application transactions of the signer, in block order",
+		"parameters": [path_parameter("app_id", "Application ID"), signer, from, limit],
+		"responses": {
+			"200": spec.json::<TransactionsResponse>("Application transactions"),
+			"400": error("Query parameters are not valid"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/apps/{app_id}/transactions", operation);
+
+	let block = json!({ "type": "integer", "format": "uint32", "minimum": 0 });
+	let from = query_parameter("from", true, "First block of the range", block.clone());
+	let to = query_parameter("to", true, "Last block of the range", block);
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Streams the application data of blocks in the range, one block per line, skipping blocks without application data",
+		"parameters": [path_parameter("app_id", "Application ID"), from, to],
+		"responses": {
+			"200": {
+				"description": "Newline-delimited application data with block confidence",
+				"content": { "application/x-ndjson": { "schema": spec.schema::<DataResponse>() } }
+			},
+			"400": error("Query parameters are not valid"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/apps/{app_id}/data", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Submits data or signed extrinsic, if light client is running in the app mode",
+		"requestBody": {
+			"required": true,
+			"content": { "application/json": { "schema": spec.schema::<SubmitRequest>() } }
+		},
+		"responses": {
+			"200": spec.json::<SubmitResponse>("Transaction is accepted into the transaction pool"),
+			"400": error("Transaction is not valid, or signer is not allowed to submit data for the app"),
+			"404": error("Submit is not available"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.post("/v2/submit", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Estimates fee, weight and length of the transaction without submitting it",
+		"requestBody": {
+			"required": true,
+			"content": { "application/json": { "schema": spec.schema::<EstimateRequest>() } }
+		},
+		"responses": {
+			"200": spec.json::<EstimateResponse>("Transaction estimate"),
+			"400": error("Signer is not allowed to submit data for the app"),
+			"404": error("Submit is not available"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.post("/v2/submit/estimate", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the latest status of the transaction submitted through the light client",
+		"parameters": [{
+			"name": "id",
+			"in": "path",
+			"required": true,
+			"description": "Submission ID, returned on submit",
+			"schema": { "type": "string", "format": "uuid" }
+		}],
+		"responses": {
+			"200": spec.json::<Submission>("Submitted transaction status"),
+			"404": error("Submission is not found"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/submissions/{id}", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the blob reassembled from the verified chunks, if light client is running in the app mode",
+		"parameters": [{
+			"name": "manifest_hash",
+			"in": "path",
+			"required": true,
+			"description": "Hex encoded blob manifest hash, returned on submit",
+			"schema": { "type": "string" }
+		}],
+		"responses": {
+			"200": spec.json::<BlobResponse>("Reassembled blob"),
+			"404": error("Blob is not found, or some of its chunks are not verified yet"),
+			"500": error("Internal server error"),
+		},
+	});
+	spec.get("/v2/blobs/{manifest_hash}", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Creates subscription to the websocket topics",
+		"requestBody": {
+			"required": true,
+			"content": { "application/json": { "schema": spec.schema::<Subscription>() } }
+		},
+		"responses": { "200": spec.json::<SubscriptionId>("Subscription created") },
+	});
+	spec.post("/v2/subscriptions", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Streams messages on the given topics as server-sent events",
+		"description": "Each event is named by the message topic, and contains JSON encoded `PublishMessage` data.",
+		"parameters": [
+			query_parameter(
+				"topics",
+				false,
+				"Comma separated list of the topics (header-verified, confidence-achieved, data-verified, transaction-status), all topics if omitted",
+				json!({ "type": "string" })
+			),
+			query_parameter(
+				"fields",
+				false,
+				"Comma separated list of the data transaction fields (data, extrinsic, hash, index, signer, nonce, tip, call)",
+				json!({ "type": "string" })
+			)
+		],
+		"responses": {
+			"200": {
+				"description": "Server-sent events stream",
+				"content": { "text/event-stream": { "schema": spec.schema::<PublishMessage>() } }
+			},
+			"400": error("Topics or fields are not valid"),
+		},
+	});
+	spec.get("/v2/events", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Connects to the websocket server using subscription ID",
+		"description": "Client sends `Request` messages, and server responds with `WsResponse` or `WsError` messages, \
+			correlated by the request ID. Messages on the subscribed topics are published as `PublishMessage`.",
+		"parameters": [{
+			"name": "subscription_id",
+			"in": "path",
+			"required": true,
+			"description": "Subscription ID",
+			"schema": { "type": "string", "format": "uuid" }
+		}],
+		"responses": {
+			"101": { "description": "Switching to the websocket protocol" },
+			"404": error("Subscription is not found"),
+		},
+		"x-websocket-messages": {
+			"client": spec.schema::<Request>(),
+			"server": {
+				"oneOf": [
+					spec.schema::<WsResponse>(),
+					spec.schema::<WsError>(),
+					spec.schema::<PublishMessage>()
+				]
+			}
+		},
+	});
+	spec.get("/v2/ws/{subscription_id}", operation);
+
+	let operation = json!({
+		"tags": ["v2"],
+		"summary": "Gets the OpenAPI specification of the light client API",
+		"responses": { "200": { "description": "OpenAPI specification" } },
+	});
+	spec.get("/v2/openapi.json", operation);
+}
+
+/// Generates OpenAPI specification of the v1 and v2 API
+pub fn spec(version: &str) -> Value {
+	let mut spec = Spec::new();
+	v1_paths(&mut spec);
+	v2_paths(&mut spec);
+	// Schemas which are not directly referenced by the operations
+	spec.schema::<Payload>();
+	spec.schema::<Error>();
+	spec.into_value(version)
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::Value;
+
+	fn references(value: &Value, result: &mut Vec<String>) {
+		match value {
+			Value::Object(map) => {
+				if let Some(Value::String(reference)) = map.get("$ref") {
+					result.push(reference.clone());
+				}
+				map.values().for_each(|value| references(value, result));
+			},
+			Value::Array(values) => values.iter().for_each(|value| references(value, result)),
+			_ => (),
+		}
+	}
+
+	#[test]
+	fn spec_contains_routes_and_schemas() {
+		let spec = super::spec("v1.0.0");
+
+		for path in [
+			"/v1/mode",
+			"/v1/latest_block",
+			"/v1/confidence/{block_number}",
+			"/v1/appdata/{block_number}",
+			"/v1/status",
+			"/v2/version",
+			"/v2/status",
+			"/v2/blocks",
+			"/v2/blocks/{block_number}",
+			"/v2/blocks/{block_number}/header",
+			"/v2/blocks/{block_number}/data",
+			"/v2/blocks/{block_number}/data/{transaction_index}/proof",
+			"/v2/blocks/{block_number}/cells",
+			"/v2/verify/cells",
+			"/v2/transactions/{hash}",
+			"/v2/apps/{app_id}/transactions",
+			"/v2/apps/{app_id}/data",
+			"/v2/submit",
+			"/v2/submit/estimate",
+			"/v2/submissions/{id}",
+			"/v2/blobs/{manifest_hash}",
+			"/v2/subscriptions",
+			"/v2/events",
+			"/v2/ws/{subscription_id}",
+			"/v2/openapi.json",
+		] {
+			assert!(spec["paths"].get(path).is_some(), "Missing path {path}");
+		}
+
+		for schema in [
+			"Request",
+			"Payload",
+			"WsResponse",
+			"WsError",
+			"PublishMessage",
+			"Error",
+		] {
+			let schemas = &spec["components"]["schemas"];
+			assert!(schemas.get(schema).is_some(), "Missing schema {schema}");
+		}
+	}
+
+	#[test]
+	fn spec_references_are_defined() {
+		let spec = super::spec("v1.0.0");
+		let mut result = vec![];
+		references(&spec, &mut result);
+		assert!(!result.is_empty());
+
+		for reference in result {
+			let name = reference
+				.strip_prefix("#/components/schemas/")
+				.expect("Reference to component schema");
+			assert!(
+				spec["components"]["schemas"].get(name).is_some(),
+				"Missing schema {name}"
+			);
+		}
+	}
+}
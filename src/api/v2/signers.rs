@@ -0,0 +1,221 @@
+use super::transactions::AvailSigner;
+use crate::types::RuntimeConfig;
+use anyhow::{anyhow, Result};
+use std::{collections::HashSet, future::Future, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Name of the signer created from the `avail_secret_key` configuration
+pub const DEFAULT_SIGNER: &str = "default";
+
+/// Next account nonce, tracked locally so transactions of the same account
+/// can be submitted without waiting for the previous ones to be included in the block.
+#[derive(Clone, Default)]
+pub struct Nonce(Arc<Mutex<Option<u32>>>);
+
+impl Nonce {
+	/// Submits transaction with the next account nonce. Submissions of the same account are serialized.
+	/// Nonce is fetched from the chain if it is not known yet, or if the previous submission failed.
+	pub async fn submit<T, F, S>(&self, fetch: F, submit: impl FnOnce(u32) -> S) -> Result<T>
+	where
+		F: Future<Output = Result<u32>>,
+		S: Future<Output = Result<T>>,
+	{
+		let mut next = self.0.lock().await;
+		let nonce = match *next {
+			Some(nonce) => nonce,
+			None => fetch.await?,
+		};
+
+		match submit(nonce).await {
+			Ok(value) => {
+				*next = Some(nonce + 1);
+				Ok(value)
+			},
+			Err(error) => {
+				*next = None;
+				Err(error)
+			},
+		}
+	}
+
+	/// Returns the next account nonce without reserving it, fetching it from the chain if it is not known yet.
+	/// Nonce is fetched without holding the lock, and cached unless it is set meanwhile.
+	pub async fn next(&self, fetch: impl Future<Output = Result<u32>>) -> Result<u32> {
+		if let Some(nonce) = *self.0.lock().await {
+			return Ok(nonce);
+		}
+
+		let fetched = fetch.await?;
+		Ok(*self.0.lock().await.get_or_insert(fetched))
+	}
+
+	/// Forces nonce to be fetched from the chain on the next submission
+	pub async fn resync(&self) {
+		*self.0.lock().await = None;
+	}
+}
+
+#[derive(Clone)]
+pub struct Signer {
+	pub name: String,
+	pub pair_signer: AvailSigner,
+	pub app_ids: HashSet<u32>,
+	pub nonce: Nonce,
+}
+
+impl Signer {
+	pub fn new(
+		name: &str,
+		pair_signer: AvailSigner,
+		app_ids: impl IntoIterator<Item = u32>,
+	) -> Self {
+		Signer {
+			name: name.to_string(),
+			pair_signer,
+			app_ids: app_ids.into_iter().collect(),
+			nonce: Nonce::default(),
+		}
+	}
+
+	pub fn allows(&self, app_id: u32) -> bool {
+		self.app_ids.contains(&app_id)
+	}
+}
+
+/// Registry of the named accounts used to sign data transactions
+#[derive(Clone, Default)]
+pub struct Signers(Vec<Signer>);
+
+impl Signers {
+	pub fn new(signers: Vec<Signer>) -> Self {
+		Signers(signers)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Finds signer with the given name, or the first signer allowed
+	/// to submit data for the application if the name is not given
+	pub fn find(&self, name: Option<&str>, app_id: u32) -> Result<&Signer> {
+		let Some(name) = name else {
+			return self
+				.0
+				.iter()
+				.find(|signer| signer.allows(app_id))
+				.ok_or_else(|| {
+					anyhow!("No signer is allowed to submit data for the app {app_id}")
+				});
+		};
+
+		let signer = self
+			.0
+			.iter()
+			.find(|signer| signer.name == name)
+			.ok_or_else(|| anyhow!("Signer {name} is not configured"))?;
+
+		if !signer.allows(app_id) {
+			return Err(anyhow!(
+				"Signer {name} is not allowed to submit data for the app {app_id}"
+			));
+		}
+
+		Ok(signer)
+	}
+}
+
+impl From<&RuntimeConfig> for Signers {
+	fn from(config: &RuntimeConfig) -> Self {
+		let default = config
+			.avail_secret_key
+			.clone()
+			.map(|secret_key| Signer::new(DEFAULT_SIGNER, secret_key.into(), config.app_id));
+
+		// Signer keys are checked and unlocked from the keystore on startup (see `RuntimeConfig::unlock_keys`)
+		let signers = config.signers.iter().filter_map(|signer| {
+			let pair_signer = signer.secret_key.clone()?.into();
+			Some(Signer::new(
+				&signer.name,
+				pair_signer,
+				signer.app_ids.clone(),
+			))
+		});
+
+		Signers(default.into_iter().chain(signers).collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Nonce, Signer, Signers};
+	use crate::api::v2::transactions::AvailSigner;
+	use anyhow::anyhow;
+	use sp_core::{sr25519::Pair, Pair as _};
+	use test_case::test_case;
+
+	fn signer(name: &str, app_ids: Vec<u32>) -> Signer {
+		let pair = Pair::from_string("//Alice", None).unwrap();
+		Signer::new(name, AvailSigner::new(pair), app_ids)
+	}
+
+	#[test_case(None, 1 => Ok("first".to_string()) ; "First allowed signer")]
+	#[test_case(None, 2 => Ok("second".to_string()) ; "First signer allowed for the app")]
+	#[test_case(Some("second"), 1 => Ok("second".to_string()) ; "Named signer")]
+	#[test_case(Some("first"), 3 => Err("Signer first is not allowed to submit data for the app 3".to_string()) ; "Named signer not allowed")]
+	#[test_case(Some("third"), 1 => Err("Signer third is not configured".to_string()) ; "Unknown signer")]
+	#[test_case(None, 4 => Err("No signer is allowed to submit data for the app 4".to_string()) ; "No signer allowed")]
+	fn find_signer(name: Option<&str>, app_id: u32) -> Result<String, String> {
+		let signers = Signers::new(vec![
+			signer("first", vec![1]),
+			signer("second", vec![1, 2, 3]),
+		]);
+		signers
+			.find(name, app_id)
+			.map(|signer| signer.name.clone())
+			.map_err(|error| error.to_string())
+	}
+
+	#[tokio::test]
+	async fn nonce_pipelined() {
+		let nonce = Nonce::default();
+		let mut submitted = vec![];
+		for _ in 0..3 {
+			let submit = |nonce| async move { Ok(nonce) };
+			submitted.push(nonce.submit(async { Ok(5) }, submit).await.unwrap());
+		}
+		assert_eq!(submitted, vec![5, 6, 7]);
+	}
+
+	#[tokio::test]
+	async fn nonce_resync() {
+		let nonce = Nonce::default();
+		let submit = |nonce| async move { Ok(nonce) };
+		assert_eq!(nonce.submit(async { Ok(5) }, submit).await.unwrap(), 5);
+
+		let fail = |_| async { Err::<u32, _>(anyhow!("Submit failed")) };
+		assert!(nonce.submit(async { Ok(0) }, fail).await.is_err());
+
+		// Nonce is fetched from the chain after the failed submission
+		assert_eq!(nonce.submit(async { Ok(6) }, submit).await.unwrap(), 6);
+		assert_eq!(nonce.submit(async { Ok(0) }, submit).await.unwrap(), 7);
+
+		nonce.resync().await;
+		assert_eq!(nonce.submit(async { Ok(10) }, submit).await.unwrap(), 10);
+	}
+
+	#[tokio::test]
+	async fn nonce_next() {
+		let nonce = Nonce::default();
+		assert!(nonce
+			.next(async { Err(anyhow!("Fetch failed")) })
+			.await
+			.is_err());
+
+		// Fetched nonce is cached and used by the next submission
+		assert_eq!(nonce.next(async { Ok(5) }).await.unwrap(), 5);
+		assert_eq!(nonce.next(async { Ok(0) }).await.unwrap(), 5);
+		let submit = |nonce| async move { Ok(nonce) };
+		assert_eq!(nonce.submit(async { Ok(0) }, submit).await.unwrap(), 5);
+		assert_eq!(nonce.next(async { Ok(0) }).await.unwrap(), 6);
+	}
+}
@@ -1,63 +1,945 @@
-use crate::{network::rpc, types::AvailSecretKey};
+use crate::{
+	blobs,
+	data::{
+		get_block_header_from_db, get_extrinsic_indices_from_db, get_submissions_from_db,
+		get_transaction_location_from_db, remove_submissions_from_db, store_submission_in_db,
+	},
+	network::{p2p, rpc},
+	types::{AvailSecretKey, BlockVerified},
+	utils::unix_timestamp,
+};
 
-use super::types::{SubmitResponse, Transaction};
-use anyhow::{anyhow, Context, Result};
+use super::{
+	signers::{Nonce, Signer, Signers},
+	types::{
+		DryRunResult, EstimateRequest, EstimateResponse, Submission, SubmissionStatus,
+		SubmitRequest, SubmitResponse, Transaction,
+	},
+};
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use avail_subxt::{api, primitives::AvailExtrinsicParams, AvailConfig};
-use sp_core::sr25519::Pair;
-use subxt::tx::PairSigner;
+use avail_core::AppId;
+use avail_subxt::{
+	api::{self, runtime_types::bounded_collections::bounded_vec::BoundedVec},
+	primitives::AvailExtrinsicParams,
+	utils::H256,
+	AvailConfig,
+};
+use codec::Encode;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use futures::{stream, Stream, StreamExt};
+use kate_recovery::{com::app_specific_rows, commitments, matrix::RowIndex};
+use mockall::automock;
+use rocksdb::DB;
+use sp_core::{blake2_256, sr25519::Pair};
+use std::{
+	collections::VecDeque,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+use subxt::{
+	tx::{PairSigner, TxInBlock, TxProgress, TxStatus},
+	OnlineClient,
+};
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 #[async_trait]
 pub trait Submit {
-	async fn submit(&self, transaction: Transaction) -> Result<SubmitResponse>;
+	/// Submits transaction and returns as soon as it is accepted into the transaction pool
+	async fn submit(&self, request: SubmitRequest) -> Result<SubmitResponse>;
+	/// Estimates fee, weight and length of the transaction without submitting it
+	async fn estimate(&self, request: EstimateRequest) -> Result<EstimateResponse>;
 	fn has_signer(&self) -> bool;
+	/// Checks if the data transaction for the application can be signed by the signer
+	fn check_signer(&self, signer: Option<&str>, app_id: Option<u32>) -> Result<()>;
 }
 // TODO: Replace this with avail::PairSigner after implementing required traits in avail-subxt
 pub type AvailSigner = PairSigner<AvailConfig, Pair>;
 
 impl From<AvailSecretKey> for AvailSigner {
 	fn from(value: AvailSecretKey) -> Self {
-		AvailSigner::new(value.0)
+		AvailSigner::new(value.pair())
 	}
 }
+
+type Progress = TxProgress<AvailConfig, OnlineClient<AvailConfig>>;
+type InBlock = TxInBlock<AvailConfig, OnlineClient<AvailConfig>>;
+
+/// Inserts application rows of the finalized data submissions into the DHT,
+/// so other app clients can fetch them without falling back to RPC.
+#[async_trait]
+#[automock]
+pub trait Reseed: Send + Sync {
+	/// Reseeds application rows of the given block
+	async fn reseed(&self, app_id: u32, block_hash: H256) -> Result<()>;
+}
+
+/// Number of the recently reseeded blocks, kept to reseed each block once
+const RESEEDED_BLOCKS: usize = 64;
+
+/// Places fetched application rows at their indices among the extended rows
+fn place_rows(
+	extended_rows: usize,
+	app_rows: Vec<u32>,
+	fetched_rows: Vec<Option<Vec<u8>>>,
+) -> Result<Vec<Option<Vec<u8>>>> {
+	if app_rows.len() != fetched_rows.len() {
+		return Err(anyhow!(
+			"Fetched {} rows, expected {}",
+			fetched_rows.len(),
+			app_rows.len()
+		));
+	}
+
+	let mut rows = vec![None; extended_rows];
+	for (index, row) in app_rows.into_iter().zip(fetched_rows) {
+		let slot = rows
+			.get_mut(index as usize)
+			.ok_or_else(|| anyhow!("Row index {index} is out of range"))?;
+		*slot = row;
+	}
+	Ok(rows)
+}
+
+/// Reseeds application rows fetched from RPC and verified against the block commitments
+#[derive(Clone)]
+pub struct Reseeder {
+	pub node_client: rpc::Client,
+	pub p2p_client: p2p::Client,
+	pub pp: Arc<PublicParameters>,
+}
+
+#[async_trait]
+impl Reseed for Reseeder {
+	/// Fetches application rows of the block from RPC, and inserts verified rows into the DHT
+	async fn reseed(&self, app_id: u32, block_hash: H256) -> Result<()> {
+		let header = self.node_client.get_header_by_hash(block_hash).await?;
+		let block = BlockVerified::try_from((header, None))?;
+		let app_id = AppId(app_id);
+		let app_rows = app_specific_rows(&block.lookup, block.dimensions, app_id);
+
+		let fetched_rows = self
+			.node_client
+			.request_kate_rows(app_rows.clone(), block_hash)
+			.await?;
+		let extended_rows = block.dimensions.extended_rows() as usize;
+		let mut rows = place_rows(extended_rows, app_rows, fetched_rows)?;
+
+		let (verified_rows, missing_rows) = commitments::verify_equality(
+			&self.pp,
+			&block.commitments,
+			&rows,
+			&block.lookup,
+			block.dimensions,
+			app_id,
+		)?;
+		debug!(
+			block_number = block.block_num,
+			"Verified {} app rows, missing {}",
+			verified_rows.len(),
+			missing_rows.len()
+		);
+
+		let verified_rows = verified_rows
+			.into_iter()
+			.filter_map(|index| Some((RowIndex(index), rows[index as usize].take()?)))
+			.collect::<Vec<_>>();
+		let success_rate = self
+			.p2p_client
+			.insert_rows_into_dht(block.block_num, verified_rows)
+			.await;
+		info!(
+			block_number = block.block_num,
+			"DHT PUT submitted app rows success rate: {success_rate}"
+		);
+		Ok(())
+	}
+}
+
+/// Final submissions are removed once they are not updated for the retention period
+pub const SUBMISSION_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const SUBMISSIONS_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Transaction progress update which changes the submission status
+#[derive(Debug)]
+enum Update {
+	/// Transaction is included in the block, with its index in the block or the execution error
+	InBlock {
+		block_hash: H256,
+		result: Result<u32, String>,
+	},
+	/// Block which includes the transaction is finalized
+	Finalized {
+		block_hash: H256,
+		result: Result<u32, String>,
+	},
+	Retracted,
+	Dropped,
+	Invalid,
+	/// Transaction progress cannot be followed anymore
+	Error(String),
+}
+
+async fn included(in_block: InBlock) -> (H256, Result<u32, String>) {
+	let block_hash = in_block.block_hash();
+	let result = in_block
+		.wait_for_success()
+		.await
+		.map(|events| events.extrinsic_index())
+		.map_err(|error| error.to_string());
+	(block_hash, result)
+}
+
+/// Maps transaction progress into the submission updates, skipping statuses before inclusion
+fn updates(progress: Progress) -> impl Stream<Item = Update> {
+	stream::unfold(progress, |mut progress| async move {
+		loop {
+			let update = match progress.next_item().await? {
+				Ok(TxStatus::Future | TxStatus::Ready | TxStatus::Broadcast(_)) => continue,
+				Ok(TxStatus::InBlock(in_block)) => {
+					let (block_hash, result) = included(in_block).await;
+					Update::InBlock { block_hash, result }
+				},
+				Ok(TxStatus::Finalized(in_block)) => {
+					let (block_hash, result) = included(in_block).await;
+					Update::Finalized { block_hash, result }
+				},
+				Ok(TxStatus::Retracted(_)) => Update::Retracted,
+				Ok(TxStatus::Usurped(_) | TxStatus::FinalityTimeout(_) | TxStatus::Dropped) => {
+					Update::Dropped
+				},
+				Ok(TxStatus::Invalid) => Update::Invalid,
+				Err(error) => Update::Error(format!("Cannot follow transaction progress: {error}")),
+			};
+			return Some((update, progress));
+		}
+	})
+}
+
+fn include(
+	submission: &mut Submission,
+	status: SubmissionStatus,
+	block_hash: H256,
+	result: Result<u32, String>,
+) {
+	submission.block_hash = Some(block_hash);
+	match result {
+		Ok(index) => {
+			submission.status = status;
+			submission.index = Some(index);
+			submission.error = None;
+		},
+		Err(error) => {
+			// Execution result is final only once the block is finalized
+			submission.status = match status {
+				SubmissionStatus::Finalized => SubmissionStatus::Failed,
+				status => status,
+			};
+			submission.error = Some(error);
+		},
+	}
+}
+
+fn apply(submission: &mut Submission, update: Update) {
+	match update {
+		Update::InBlock { block_hash, result } => {
+			include(submission, SubmissionStatus::InBlock, block_hash, result)
+		},
+		Update::Finalized { block_hash, result } => {
+			include(submission, SubmissionStatus::Finalized, block_hash, result)
+		},
+		Update::Retracted => {
+			submission.status = SubmissionStatus::Pending;
+			submission.block_hash = None;
+			submission.index = None;
+			submission.error = None;
+		},
+		Update::Dropped => submission.status = SubmissionStatus::Dropped,
+		Update::Invalid => submission.status = SubmissionStatus::Invalid,
+		Update::Error(error) => {
+			submission.status = SubmissionStatus::Unknown;
+			submission.error = Some(error);
+		},
+	}
+}
+
+/// Applies progress updates to the submission until its status is final.
+/// If updates end before that, inclusion of the transaction is unknown.
+/// Signer nonce is resynchronized if the transaction is not known to be included.
+async fn follow(
+	mut updates: impl Stream<Item = Update> + Unpin,
+	mut submission: Submission,
+	nonce: Option<&Nonce>,
+	mut on_update: impl FnMut(&mut Submission),
+) -> Submission {
+	while !submission.status.is_final() {
+		match updates.next().await {
+			Some(update) => apply(&mut submission, update),
+			None => {
+				submission.status = SubmissionStatus::Unknown;
+				submission.error = Some("Transaction progress stream ended".to_string());
+			},
+		}
+		debug!(id = %submission.id, status = ?submission.status, "Submission updated");
+		on_update(&mut submission);
+	}
+
+	if let (
+		SubmissionStatus::Dropped | SubmissionStatus::Invalid | SubmissionStatus::Unknown,
+		Some(nonce),
+	) = (submission.status, nonce)
+	{
+		nonce.resync().await;
+	}
+	submission
+}
+
+/// Persists and publishes status updates of the submitted transactions.
+#[derive(Clone)]
+pub struct Tracker {
+	pub db: Arc<DB>,
+	pub sender: broadcast::Sender<Submission>,
+	/// Without reseeder, rows of the submitted data are not inserted into the DHT
+	reseeder: Option<Arc<dyn Reseed>>,
+	/// Recently reseeded application blocks, so blob chunks included in the same block are reseeded once
+	reseeded: Arc<Mutex<VecDeque<(u32, H256)>>>,
+}
+
+impl Tracker {
+	pub fn new(
+		db: Arc<DB>,
+		sender: broadcast::Sender<Submission>,
+		reseeder: Option<Arc<dyn Reseed>>,
+	) -> Self {
+		Tracker {
+			db,
+			sender,
+			reseeder,
+			reseeded: Default::default(),
+		}
+	}
+
+	fn update(&self, submission: &mut Submission) {
+		submission.updated_at = unix_timestamp();
+		if let Err(error) = store_submission_in_db(self.db.clone(), submission) {
+			error!(id = %submission.id, "Cannot store submission: {error:#}");
+		}
+		// Sending fails only if there are no transaction status subscribers
+		_ = self.sender.send(submission.clone());
+	}
+
+	/// Follows transaction progress until the submission status is final.
+	/// Rows of the finalized data transaction are reseeded into the DHT if application is known.
+	async fn track(
+		self,
+		progress: Progress,
+		submission: Submission,
+		nonce: Option<Nonce>,
+		app_id: Option<u32>,
+	) {
+		let updates = Box::pin(updates(progress));
+		let submission = follow(updates, submission, nonce.as_ref(), |submission| {
+			self.update(submission)
+		})
+		.await;
+		self.reseed(&submission, app_id).await;
+	}
+
+	/// Reseeds rows of the finalized data transaction, once per application block
+	async fn reseed(&self, submission: &Submission, app_id: Option<u32>) {
+		let (SubmissionStatus::Finalized, Some(app_id), Some(block_hash), Some(reseeder)) = (
+			submission.status,
+			app_id,
+			submission.block_hash,
+			&self.reseeder,
+		) else {
+			return;
+		};
+
+		{
+			let mut reseeded = self.reseeded.lock().expect("Lock is not poisoned");
+			if reseeded.contains(&(app_id, block_hash)) {
+				return;
+			}
+			if reseeded.len() == RESEEDED_BLOCKS {
+				reseeded.pop_front();
+			}
+			reseeded.push_back((app_id, block_hash));
+		}
+
+		if let Err(error) = reseeder.reseed(app_id, block_hash).await {
+			error!(id = %submission.id, "Cannot reseed submitted data: {error:#}");
+			// Block can be reseeded again by the other submissions included in it
+			let mut reseeded = self.reseeded.lock().expect("Lock is not poisoned");
+			reseeded.retain(|block| *block != (app_id, block_hash));
+		}
+	}
+}
+
+/// Resolves submissions which were not final when the light client stopped,
+/// since their progress cannot be followed after the restart.
+/// Transactions found among the verified application data are finalized,
+/// and inclusion of the other transactions is unknown.
+pub fn recheck_submissions(db: Arc<DB>) -> Result<()> {
+	for mut submission in get_submissions_from_db(db.clone())? {
+		if submission.status.is_final() {
+			continue;
+		}
+
+		let location = get_transaction_location_from_db(db.clone(), submission.hash)?;
+		let header = location
+			.as_ref()
+			.map(|location| get_block_header_from_db(db.clone(), location.block_number))
+			.transpose()?
+			.flatten();
+
+		match (location, header) {
+			(Some(location), Some(header)) => {
+				let block_hash: H256 = Encode::using_encoded(&header, blake2_256).into();
+				// Location index is the position among the application transactions
+				let index = get_extrinsic_indices_from_db(
+					db.clone(),
+					location.app_id,
+					location.block_number,
+				)?
+				.and_then(|indices| indices.get(location.index as usize).copied());
+				submission.status = SubmissionStatus::Finalized;
+				submission.block_hash = Some(block_hash);
+				submission.index = index;
+				submission.error = None;
+			},
+			_ => {
+				submission.status = SubmissionStatus::Unknown;
+				submission.error = Some("Light client restarted before finalization".to_string());
+			},
+		}
+
+		info!(id = %submission.id, status = ?submission.status, "Submission rechecked");
+		submission.updated_at = unix_timestamp();
+		store_submission_in_db(db.clone(), &submission)?;
+	}
+	Ok(())
+}
+
+/// Returns IDs of the final submissions which are not updated within the retention period
+fn expired_submissions(submissions: &[Submission], now: u64) -> Vec<Uuid> {
+	submissions
+		.iter()
+		.filter(|submission| submission.status.is_final())
+		.filter(|submission| submission.updated_at + SUBMISSION_RETENTION.as_secs() < now)
+		.map(|submission| submission.id)
+		.collect()
+}
+
+/// Periodically removes expired submissions from the database
+pub async fn prune_submissions(db: Arc<DB>) {
+	let mut interval = tokio::time::interval(SUBMISSIONS_PRUNE_INTERVAL);
+	loop {
+		interval.tick().await;
+		let expired = get_submissions_from_db(db.clone())
+			.map(|submissions| expired_submissions(&submissions, unix_timestamp()))
+			.and_then(|expired| {
+				remove_submissions_from_db(db.clone(), &expired)?;
+				Ok(expired.len())
+			});
+		match expired {
+			Ok(0) => (),
+			Ok(count) => debug!("Removed {count} expired submissions"),
+			Err(error) => warn!("Cannot prune submissions: {error:#}"),
+		}
+	}
+}
+
 #[derive(Clone)]
 pub struct Submitter {
 	pub node_client: rpc::Client,
-	pub app_id: u32,
-	pub pair_signer: Option<AvailSigner>,
+	/// Application ID used if it is not set in the request
+	pub app_id: Option<u32>,
+	pub signers: Signers,
+	/// Data larger than the chunk size is submitted as a blob
+	pub blob_chunk_size: usize,
+	/// Without tracker, submission statuses are neither stored nor published
+	pub tracker: Option<Tracker>,
+}
+
+impl Submitter {
+	fn app_id(&self, app_id: Option<u32>) -> Result<u32> {
+		app_id
+			.or(self.app_id)
+			.ok_or_else(|| anyhow!("Application ID is required, since it is not configured"))
+	}
+
+	async fn submit_data(&self, signer: &Signer, app_id: u32, data: Vec<u8>) -> Result<Progress> {
+		let extrinsic = api::tx().data_availability().submit_data(BoundedVec(data));
+		let params = AvailExtrinsicParams::new_with_app_id(app_id.into());
+		let account_id = signer.pair_signer.account_id().clone();
+		signer
+			.nonce
+			.submit(self.node_client.get_account_nonce(account_id), |nonce| {
+				self.node_client.submit_signed_and_watch(
+					extrinsic,
+					signer.pair_signer.clone(),
+					params,
+					nonce,
+				)
+			})
+			.await
+	}
+
+	/// Signs data transactions with consecutive nonces, starting from the next signer nonce.
+	/// Data larger than the chunk size is signed as blob chunks followed by the manifest.
+	async fn sign_data(&self, signer: &Signer, app_id: u32, data: Vec<u8>) -> Result<Vec<Vec<u8>>> {
+		let data = if data.len() > self.blob_chunk_size {
+			let (manifest, chunks) = blobs::split(&data, self.blob_chunk_size)?;
+			let manifest = manifest.to_data();
+			chunks
+				.into_iter()
+				.map(blobs::chunk_to_data)
+				.chain([manifest])
+				.collect()
+		} else {
+			vec![data]
+		};
+
+		let account_id = signer.pair_signer.account_id().clone();
+		let nonce = signer
+			.nonce
+			.next(self.node_client.get_account_nonce(account_id))
+			.await?;
+
+		let mut transactions = vec![];
+		for (data, nonce) in data.into_iter().zip(nonce..) {
+			let extrinsic = api::tx().data_availability().submit_data(BoundedVec(data));
+			let params = AvailExtrinsicParams::new_with_app_id(app_id.into());
+			let transaction = self
+				.node_client
+				.create_signed(extrinsic, signer.pair_signer.clone(), params, nonce)
+				.await?;
+			transactions.push(transaction);
+		}
+		Ok(transactions)
+	}
+
+	/// Creates submission of the transaction accepted into the pool, and follows its progress.
+	/// Progress is followed without tracker as well, so the signer nonce is resynchronized if needed.
+	fn track(
+		&self,
+		tx_progress: Progress,
+		nonce: Option<Nonce>,
+		app_id: Option<u32>,
+	) -> Submission {
+		let mut submission = Submission::new(Uuid::new_v4(), tx_progress.extrinsic_hash());
+		match self.tracker.clone() {
+			Some(tracker) => {
+				tracker.update(&mut submission);
+				tokio::spawn(tracker.track(tx_progress, submission.clone(), nonce, app_id));
+			},
+			None => {
+				let submission = submission.clone();
+				tokio::spawn(async move {
+					let updates = Box::pin(updates(tx_progress));
+					follow(updates, submission, nonce.as_ref(), |_| ()).await;
+				});
+			},
+		}
+		submission
+	}
+
+	/// Submits blob chunks followed by the manifest. Chunks are submitted with consecutive nonces,
+	/// so the manifest is included after all of its chunks. If submission fails part way,
+	/// error contains submission IDs of the already submitted chunks.
+	async fn submit_blob(
+		&self,
+		signer: &Signer,
+		app_id: u32,
+		data: &[u8],
+	) -> Result<SubmitResponse> {
+		let (manifest, chunks) = blobs::split(data, self.blob_chunk_size)?;
+		let manifest_hash = manifest.manifest_hash();
+		let mut submitted = vec![];
+		let chunks = chunks.into_iter().map(blobs::chunk_to_data);
+		for data in chunks.chain([manifest.to_data()]) {
+			let tx_progress = match self.submit_data(signer, app_id, data).await {
+				Ok(tx_progress) => tx_progress,
+				Err(error) if submitted.is_empty() => return Err(error),
+				Err(error) => {
+					let ids = submitted
+						.iter()
+						.map(|submission: &Submission| submission.id.to_string())
+						.collect::<Vec<_>>()
+						.join(", ");
+					error!(%manifest_hash, submitted = %ids, "Blob is partially submitted: {error:#}");
+					return Err(error.context(format!(
+						"Blob is partially submitted, submitted chunk IDs: {ids}"
+					)));
+				},
+			};
+			submitted.push(self.track(tx_progress, Some(signer.nonce.clone()), Some(app_id)));
+		}
+
+		let submission = submitted.pop().expect("Manifest is submitted");
+		debug!(id = %submission.id, %manifest_hash, "Blob submitted");
+
+		Ok(SubmitResponse {
+			id: submission.id,
+			hash: submission.hash,
+			manifest_hash: Some(manifest_hash),
+		})
+	}
 }
 
 #[async_trait]
 impl Submit for Submitter {
-	async fn submit(&self, transaction: Transaction) -> Result<SubmitResponse> {
-		let tx_progress = match transaction {
+	async fn submit(&self, request: SubmitRequest) -> Result<SubmitResponse> {
+		let submission = match request.transaction {
 			Transaction::Data(data) => {
-				let Some(pair_signer) = self.pair_signer.as_ref() else {
-					return Err(anyhow!("Pair signer is not configured"));
-				};
-				let extrinsic = api::tx().data_availability().submit_data(data.into());
-				let params = AvailExtrinsicParams::new_with_app_id(self.app_id.into());
-				self.node_client
-					.submit_signed_and_watch(extrinsic, pair_signer.clone(), params)
-					.await?
+				let app_id = self.app_id(request.app_id)?;
+				let signer = self.signers.find(request.signer.as_deref(), app_id)?;
+				if data.0.len() > self.blob_chunk_size {
+					return self.submit_blob(signer, app_id, &data.0).await;
+				}
+				let tx_progress = self.submit_data(signer, app_id, data.0).await?;
+				self.track(tx_progress, Some(signer.nonce.clone()), Some(app_id))
 			},
 			Transaction::Extrinsic(extrinsic) => {
-				self.node_client
+				let tx_progress = self
+					.node_client
 					.submit_from_bytes_and_watch(extrinsic.into())
-					.await?
+					.await?;
+				self.track(tx_progress, None, None)
 			},
 		};
-		tx_progress
-			.wait_for_finalized_success()
-			.await
-			.map(|event| SubmitResponse {
-				block_hash: event.block_hash(),
-				hash: event.extrinsic_hash(),
-				index: event.extrinsic_index(),
-			})
-			.context("Cannot sign and submit transaction")
+
+		Ok(SubmitResponse {
+			id: submission.id,
+			hash: submission.hash,
+			manifest_hash: None,
+		})
 	}
+
+	async fn estimate(&self, request: EstimateRequest) -> Result<EstimateResponse> {
+		let EstimateRequest { request, dry_run } = request;
+		let transactions = match request.transaction {
+			Transaction::Data(data) => {
+				let app_id = self.app_id(request.app_id)?;
+				let signer = self.signers.find(request.signer.as_deref(), app_id)?;
+				self.sign_data(signer, app_id, data.0).await?
+			},
+			Transaction::Extrinsic(extrinsic) => vec![extrinsic.into()],
+		};
+
+		// Blob chunks and manifest are estimated together
+		let mut fee = 0u128;
+		let mut estimate = EstimateResponse::default();
+		for transaction in transactions {
+			let payment_info = self
+				.node_client
+				.query_payment_info(transaction.clone())
+				.await?;
+			fee += payment_info.partial_fee;
+			estimate.weight.ref_time += payment_info.weight.ref_time;
+			estimate.weight.proof_size += payment_info.weight.proof_size;
+			estimate.length += transaction.len();
+
+			if !dry_run || matches!(estimate.dry_run, Some(DryRunResult { valid: false, .. })) {
+				continue;
+			}
+			let validity = self.node_client.validate_transaction(transaction).await?;
+			estimate.dry_run = Some(DryRunResult {
+				valid: validity.is_ok(),
+				error: validity.err().map(|error| <&str>::from(error).to_string()),
+			});
+		}
+		estimate.fee = fee.to_string();
+		Ok(estimate)
+	}
+
 	fn has_signer(&self) -> bool {
-		self.pair_signer.is_some()
+		!self.signers.is_empty()
+	}
+
+	fn check_signer(&self, signer: Option<&str>, app_id: Option<u32>) -> Result<()> {
+		let app_id = self.app_id(app_id)?;
+		self.signers.find(signer, app_id).map(|_| ())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		expired_submissions, follow, place_rows, MockReseed, Tracker, Update, SUBMISSION_RETENTION,
+	};
+	use crate::{
+		api::v2::{
+			signers::Nonce,
+			types::{Submission, SubmissionStatus},
+		},
+		light_client_commons::init_db,
+	};
+	use anyhow::anyhow;
+	use avail_subxt::utils::H256;
+	use futures::stream;
+	use mockall::{predicate::eq, Sequence};
+	use std::{path::PathBuf, sync::Arc};
+	use test_case::test_case;
+	use tokio::sync::broadcast;
+	use uuid::Uuid;
+
+	async fn follow_updates(
+		updates: Vec<Update>,
+		nonce: &Nonce,
+	) -> (Submission, Vec<SubmissionStatus>) {
+		let submission = Submission::new(Uuid::new_v4(), H256::zero());
+		let mut statuses = vec![];
+		let submission = follow(
+			stream::iter(updates),
+			submission,
+			Some(nonce),
+			|submission| statuses.push(submission.status),
+		)
+		.await;
+		(submission, statuses)
+	}
+
+	async fn used_nonce() -> Nonce {
+		let nonce = Nonce::default();
+		nonce
+			.submit(async { Ok(5) }, |nonce| async move { Ok(nonce) })
+			.await
+			.unwrap();
+		nonce
+	}
+
+	#[tokio::test]
+	async fn follow_in_block_finalized() {
+		let nonce = used_nonce().await;
+		let block_hash = H256::repeat_byte(1);
+		let updates = vec![
+			Update::InBlock {
+				block_hash,
+				result: Ok(2),
+			},
+			Update::Finalized {
+				block_hash,
+				result: Ok(2),
+			},
+		];
+		let (submission, statuses) = follow_updates(updates, &nonce).await;
+		assert_eq!(
+			statuses,
+			vec![SubmissionStatus::InBlock, SubmissionStatus::Finalized]
+		);
+		assert_eq!(submission.block_hash, Some(block_hash));
+		assert_eq!(submission.index, Some(2));
+		assert_eq!(submission.error, None);
+		// Nonce is not resynchronized
+		assert_eq!(nonce.next(async { Ok(10) }).await.unwrap(), 6);
+	}
+
+	#[tokio::test]
+	async fn follow_finalized_failed() {
+		let nonce = used_nonce().await;
+		let block_hash = H256::repeat_byte(1);
+		let updates = vec![
+			Update::InBlock {
+				block_hash,
+				result: Err("Failed".to_string()),
+			},
+			Update::Finalized {
+				block_hash,
+				result: Err("Failed".to_string()),
+			},
+		];
+		let (submission, statuses) = follow_updates(updates, &nonce).await;
+		assert_eq!(
+			statuses,
+			vec![SubmissionStatus::InBlock, SubmissionStatus::Failed]
+		);
+		assert_eq!(submission.error, Some("Failed".to_string()));
+	}
+
+	#[tokio::test]
+	async fn follow_retracted() {
+		let nonce = used_nonce().await;
+		let block_hash = H256::repeat_byte(1);
+		let updates = vec![
+			Update::InBlock {
+				block_hash,
+				result: Ok(2),
+			},
+			Update::Retracted,
+			Update::Finalized {
+				block_hash: H256::repeat_byte(2),
+				result: Ok(3),
+			},
+		];
+		let (submission, statuses) = follow_updates(updates, &nonce).await;
+		assert_eq!(
+			statuses,
+			vec![
+				SubmissionStatus::InBlock,
+				SubmissionStatus::Pending,
+				SubmissionStatus::Finalized
+			]
+		);
+		assert_eq!(submission.block_hash, Some(H256::repeat_byte(2)));
+		assert_eq!(submission.index, Some(3));
+	}
+
+	#[tokio::test]
+	async fn follow_dropped_resyncs_nonce() {
+		let nonce = used_nonce().await;
+		let (_, statuses) = follow_updates(vec![Update::Dropped], &nonce).await;
+		assert_eq!(statuses, vec![SubmissionStatus::Dropped]);
+		assert_eq!(nonce.next(async { Ok(10) }).await.unwrap(), 10);
+	}
+
+	#[tokio::test]
+	async fn follow_invalid_resyncs_nonce() {
+		let nonce = used_nonce().await;
+		let (_, statuses) = follow_updates(vec![Update::Invalid], &nonce).await;
+		assert_eq!(statuses, vec![SubmissionStatus::Invalid]);
+		assert_eq!(nonce.next(async { Ok(10) }).await.unwrap(), 10);
+	}
+
+	#[tokio::test]
+	async fn follow_error() {
+		let nonce = used_nonce().await;
+		let updates = vec![Update::Error("Connection closed".to_string())];
+		let (submission, statuses) = follow_updates(updates, &nonce).await;
+		assert_eq!(statuses, vec![SubmissionStatus::Unknown]);
+		assert_eq!(submission.error, Some("Connection closed".to_string()));
+		assert_eq!(nonce.next(async { Ok(10) }).await.unwrap(), 10);
+	}
+
+	#[tokio::test]
+	async fn follow_stream_ended() {
+		let nonce = used_nonce().await;
+		let updates = vec![Update::InBlock {
+			block_hash: H256::repeat_byte(1),
+			result: Ok(2),
+		}];
+		let (submission, statuses) = follow_updates(updates, &nonce).await;
+		assert_eq!(
+			statuses,
+			vec![SubmissionStatus::InBlock, SubmissionStatus::Unknown]
+		);
+		assert_eq!(
+			submission.error,
+			Some("Transaction progress stream ended".to_string())
+		);
+		assert_eq!(nonce.next(async { Ok(10) }).await.unwrap(), 10);
+	}
+
+	#[test]
+	fn expired_submissions_final_only() {
+		let now = 2 * SUBMISSION_RETENTION.as_secs();
+		let submission = |status, updated_at| Submission {
+			status,
+			updated_at,
+			..Submission::new(Uuid::new_v4(), H256::zero())
+		};
+		let expired = submission(SubmissionStatus::Finalized, 0);
+		let submissions = [
+			expired.clone(),
+			submission(SubmissionStatus::Finalized, now),
+			submission(SubmissionStatus::Pending, 0),
+		];
+		assert_eq!(expired_submissions(&submissions, now), vec![expired.id]);
+	}
+
+	fn tracker(reseeder: MockReseed) -> (Tracker, PathBuf) {
+		let path = std::env::temp_dir().join(format!("avail-light-tracker-{}", Uuid::new_v4()));
+		let db = init_db(path.to_str().unwrap(), false).unwrap();
+		let (sender, _) = broadcast::channel(1);
+		let tracker = Tracker::new(db, sender, Some(Arc::new(reseeder)));
+		(tracker, path)
+	}
+
+	fn included(status: SubmissionStatus, block_hash: H256) -> Submission {
+		let mut submission = Submission::new(Uuid::new_v4(), H256::zero());
+		submission.status = status;
+		submission.block_hash = Some(block_hash);
+		submission
+	}
+
+	#[tokio::test]
+	async fn reseed_finalized_once_per_block() {
+		let (first, second) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		let mut reseeder = MockReseed::new();
+		for block_hash in [first, second] {
+			reseeder
+				.expect_reseed()
+				.with(eq(1), eq(block_hash))
+				.times(1)
+				.returning(|_, _| Ok(()));
+		}
+
+		let (tracker, path) = tracker(reseeder);
+		// Blob chunks included in the same block are reseeded once
+		for block_hash in [first, first, second] {
+			let submission = included(SubmissionStatus::Finalized, block_hash);
+			tracker.reseed(&submission, Some(1)).await;
+		}
+		drop(tracker);
+		std::fs::remove_dir_all(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn reseed_failed_block_again() {
+		let block_hash = H256::repeat_byte(1);
+		let mut reseeder = MockReseed::new();
+		let mut sequence = Sequence::new();
+		reseeder
+			.expect_reseed()
+			.times(1)
+			.in_sequence(&mut sequence)
+			.returning(|_, _| Err(anyhow!("Cannot fetch rows")));
+		reseeder
+			.expect_reseed()
+			.times(1)
+			.in_sequence(&mut sequence)
+			.returning(|_, _| Ok(()));
+
+		let (tracker, path) = tracker(reseeder);
+		for _ in 0..3 {
+			let submission = included(SubmissionStatus::Finalized, block_hash);
+			tracker.reseed(&submission, Some(1)).await;
+		}
+		drop(tracker);
+		std::fs::remove_dir_all(path).unwrap();
+	}
+
+	#[test_case(SubmissionStatus::Finalized, None ; "Finalized without app")]
+	#[test_case(SubmissionStatus::InBlock, Some(1) ; "In block")]
+	#[test_case(SubmissionStatus::Failed, Some(1) ; "Failed")]
+	#[test_case(SubmissionStatus::Dropped, Some(1) ; "Dropped")]
+	#[test_case(SubmissionStatus::Invalid, Some(1) ; "Invalid")]
+	#[test_case(SubmissionStatus::Unknown, Some(1) ; "Unknown")]
+	#[tokio::test]
+	async fn reseed_skipped(status: SubmissionStatus, app_id: Option<u32>) {
+		let mut reseeder = MockReseed::new();
+		reseeder.expect_reseed().never();
+
+		let (tracker, path) = tracker(reseeder);
+		let submission = included(status, H256::repeat_byte(1));
+		tracker.reseed(&submission, app_id).await;
+		drop(tracker);
+		std::fs::remove_dir_all(path).unwrap();
+	}
+
+	#[test]
+	fn place_fetched_rows() {
+		let rows = place_rows(4, vec![1, 3], vec![Some(vec![1]), None]).unwrap();
+		assert_eq!(rows, vec![None, Some(vec![1]), None, None]);
+	}
+
+	#[test_case(vec![1, 3], vec![Some(vec![1])] ; "Less rows fetched")]
+	#[test_case(vec![1], vec![Some(vec![1]), Some(vec![3])] ; "More rows fetched")]
+	#[test_case(vec![4], vec![Some(vec![4])] ; "Row out of range")]
+	fn place_fetched_rows_invalid(app_rows: Vec<u32>, fetched_rows: Vec<Option<Vec<u8>>>) {
+		assert!(place_rows(4, app_rows, fetched_rows).is_err());
 	}
 }
@@ -1,19 +1,37 @@
 use anyhow::{anyhow, Context};
-use avail_subxt::api::runtime_types::{
-	avail_core::{data_lookup::compact::CompactDataLookup, header::extension::HeaderExtension},
-	bounded_collections::bounded_vec::BoundedVec,
+use avail_subxt::{
+	api::runtime_types::{
+		avail_core::{data_lookup::compact::CompactDataLookup, header::extension::HeaderExtension},
+		bounded_collections::bounded_vec::BoundedVec,
+		da_control::pallet::Call,
+		da_runtime::RuntimeCall,
+	},
+	primitives::AppUncheckedExtrinsic,
 };
 use base64::{engine::general_purpose, DecodeError, Engine};
-use codec::Encode;
+use codec::{Decode, Encode};
 use derive_more::From;
 use hyper::{http, StatusCode};
-use kate_recovery::{com::AppData, commitments, config, matrix::Partition};
+use kate_recovery::{
+	com::AppData,
+	commitments, config,
+	data::Cell as DaCell,
+	matrix::{Partition, Position},
+};
 use rocksdb::DB;
+use schemars::{
+	gen::SchemaGenerator,
+	schema::{InstanceType, Schema, SchemaObject},
+	JsonSchema,
+};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
 use sp_core::{blake2_256, H256};
 use std::{
 	collections::{HashMap, HashSet},
+	fmt::{self, Display},
 	sync::Arc,
+	time::Duration,
 };
 use tokio::sync::{mpsc::UnboundedSender, RwLock};
 use uuid::Uuid;
@@ -23,12 +41,15 @@ use warp::{
 };
 
 use crate::{
+	blobs::Blob,
 	data::{get_blocks_list, get_confidence_achieved_blocks},
-	network::rpc::{Event as RpcEvent, Node},
+	network::rpc::{Event as RpcEvent, Node, Weight},
+	proof::DataInclusionProof,
 	types::{
-		self, block_matrix_partition_format, BlockVerified, OptionBlockRange, RuntimeConfig, State,
+		self, block_matrix_partition_format, BlockVerified, DataVerified, OptionBlockRange,
+		RuntimeConfig, State, CELL_WITH_PROOF_SIZE,
 	},
-	utils::decode_app_data,
+	utils::{signer_address, unix_timestamp},
 };
 
 #[derive(Debug)]
@@ -36,7 +57,7 @@ pub struct InternalServerError {}
 
 impl warp::reject::Reject for InternalServerError {}
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct Version {
 	pub version: String,
 	pub network_version: String,
@@ -48,7 +69,7 @@ impl Reply for Version {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct BlockRange {
 	pub first: u32,
 	pub last: u32,
@@ -63,7 +84,7 @@ impl From<&types::BlockRange> for BlockRange {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct HistoricalSync {
 	pub synced: bool,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -72,7 +93,7 @@ pub struct HistoricalSync {
 	pub app_data: Option<BlockRange>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Blocks {
 	pub latest: u32,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -83,7 +104,7 @@ pub struct Blocks {
 	pub historical_sync: Option<HistoricalSync>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Status {
 	pub modes: Vec<Mode>,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -95,6 +116,7 @@ pub struct Status {
 		skip_serializing_if = "Option::is_none",
 		with = "block_matrix_partition_format"
 	)]
+	#[schemars(with = "Option<String>")]
 	pub partition: Option<Partition>,
 }
 
@@ -128,7 +150,27 @@ impl From<Base64> for String {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// Schema of the string with given format, used for types with custom serialization
+fn string_schema(format: &str) -> Schema {
+	SchemaObject {
+		instance_type: Some(InstanceType::String.into()),
+		format: Some(format.to_string()),
+		..Default::default()
+	}
+	.into()
+}
+
+impl JsonSchema for Base64 {
+	fn schema_name() -> String {
+		"Base64".to_string()
+	}
+
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		string_schema("byte")
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum Transaction {
 	Data(Base64),
@@ -144,11 +186,136 @@ impl Transaction {
 	}
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Transaction is deserialized from exactly one of the `data` or `extrinsic` fields
+#[derive(Clone, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubmitRequest {
+	#[serde(flatten)]
+	pub transaction: Transaction,
+	/// Name of the signer used to sign the data transaction (default: first signer allowed for the app)
+	pub signer: Option<String>,
+	/// Application ID of the data transaction (default: configured app_id)
+	pub app_id: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubmitRequestFields {
+	data: Option<Base64>,
+	extrinsic: Option<Base64>,
+	signer: Option<String>,
+	app_id: Option<u32>,
+}
+
+impl<'de> Deserialize<'de> for SubmitRequest {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let fields = SubmitRequestFields::deserialize(deserializer)?;
+		let transaction = match (fields.data, fields.extrinsic) {
+			(Some(data), None) => Transaction::Data(data),
+			(None, Some(extrinsic)) => Transaction::Extrinsic(extrinsic),
+			(None, None) => {
+				return Err(de::Error::custom(
+					"Either data or extrinsic field is required",
+				))
+			},
+			(Some(_), Some(_)) => {
+				return Err(de::Error::custom(
+					"Only one of data or extrinsic fields is allowed",
+				))
+			},
+		};
+
+		Ok(SubmitRequest {
+			transaction,
+			signer: fields.signer,
+			app_id: fields.app_id,
+		})
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SubmitResponse {
-	pub block_hash: H256,
+	pub id: Uuid,
+	#[schemars(with = "String")]
 	pub hash: H256,
-	pub index: u32,
+	/// Hash of the blob manifest, if data is submitted in multiple chunks
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub manifest_hash: Option<H256>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubmissionStatus {
+	/// Transaction is accepted into the transaction pool
+	Pending,
+	/// Transaction is included in the block which is not finalized yet
+	InBlock,
+	/// Transaction is included in the finalized block
+	Finalized,
+	/// Transaction is included in the block, but its execution failed
+	Failed,
+	/// Transaction is dropped from the transaction pool
+	Dropped,
+	/// Transaction is no longer valid
+	Invalid,
+	/// Transaction progress cannot be followed, so it is not known if the transaction is included
+	Unknown,
+}
+
+impl SubmissionStatus {
+	pub fn is_final(&self) -> bool {
+		!matches!(self, SubmissionStatus::Pending | SubmissionStatus::InBlock)
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct Submission {
+	pub id: Uuid,
+	#[schemars(with = "String")]
+	pub hash: H256,
+	pub status: SubmissionStatus,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub block_hash: Option<H256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub index: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// UNIX timestamp (in seconds) of the latest status update
+	#[serde(default)]
+	pub updated_at: u64,
+}
+
+impl Submission {
+	pub fn new(id: Uuid, hash: H256) -> Self {
+		Submission {
+			id,
+			hash,
+			status: SubmissionStatus::Pending,
+			block_hash: None,
+			index: None,
+			error: None,
+			updated_at: unix_timestamp(),
+		}
+	}
+}
+
+impl Reply for Submission {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
+impl TryFrom<Submission> for PublishMessage {
+	type Error = anyhow::Error;
+
+	fn try_from(value: Submission) -> Result<Self, Self::Error> {
+		Ok(PublishMessage::TransactionStatus(value))
+	}
 }
 
 impl Reply for SubmitResponse {
@@ -157,6 +324,89 @@ impl Reply for SubmitResponse {
 	}
 }
 
+/// Submit request with the estimate options, unknown fields are rejected as in the submit request
+#[derive(Clone, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateRequest {
+	#[serde(flatten)]
+	pub request: SubmitRequest,
+	/// Validates transaction signature and nonce against the best block
+	#[serde(default)]
+	pub dry_run: bool,
+}
+
+impl<'de> Deserialize<'de> for EstimateRequest {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let mut fields = serde_json::Map::deserialize(deserializer)?;
+		let dry_run = fields
+			.remove("dry_run")
+			.map(bool::deserialize)
+			.transpose()
+			.map_err(de::Error::custom)?
+			.unwrap_or_default();
+		let request = SubmitRequest::deserialize(serde_json::Value::Object(fields))
+			.map_err(de::Error::custom)?;
+
+		Ok(EstimateRequest { request, dry_run })
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EstimateResponse {
+	/// Partial fee (without tip) in the smallest unit, as decimal string
+	pub fee: String,
+	pub weight: Weight,
+	/// Length of the encoded extrinsic in bytes
+	pub length: usize,
+	/// Result of the transaction validation, if dry run is requested
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub dry_run: Option<DryRunResult>,
+}
+
+impl Reply for EstimateResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct DryRunResult {
+	pub valid: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct BlobResponse {
+	#[schemars(with = "String")]
+	pub manifest_hash: H256,
+	pub app_id: u32,
+	/// Block in which the blob manifest is included
+	pub block_number: u32,
+	#[schemars(with = "String")]
+	pub data: Base64,
+}
+
+impl BlobResponse {
+	pub fn new(manifest_hash: H256, blob: Blob) -> Self {
+		BlobResponse {
+			manifest_hash,
+			app_id: blob.app_id,
+			block_number: blob.block_number,
+			data: Base64(blob.data),
+		}
+	}
+}
+
+impl Reply for BlobResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
 impl Status {
 	pub fn new(config: &RuntimeConfig, node: &Node, state: &State) -> Self {
 		let historical_sync = state.synced.map(|synced| HistoricalSync {
@@ -209,12 +459,13 @@ impl Status {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum Mode {
 	Light,
 	App,
 	Partition,
+	Archival,
 }
 
 impl From<&RuntimeConfig> for Vec<Mode> {
@@ -227,6 +478,9 @@ impl From<&RuntimeConfig> for Vec<Mode> {
 		if value.block_matrix_partition.is_some() {
 			result.push(Mode::Partition)
 		}
+		if value.app_id.is_some() && value.archival_mode {
+			result.push(Mode::Archival)
+		}
 		result
 	}
 }
@@ -237,34 +491,55 @@ impl Reply for Status {
 	}
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum Topic {
 	HeaderVerified,
 	ConfidenceAchieved,
 	DataVerified,
+	TransactionStatus,
+}
+
+impl Display for Topic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Topic::HeaderVerified => write!(f, "header-verified"),
+			Topic::ConfidenceAchieved => write!(f, "confidence-achieved"),
+			Topic::DataVerified => write!(f, "data-verified"),
+			Topic::TransactionStatus => write!(f, "transaction-status"),
+		}
+	}
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum DataField {
 	Data,
 	Extrinsic,
+	Hash,
+	Index,
+	Signer,
+	Nonce,
+	Tip,
+	Call,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 pub struct Subscription {
 	pub topics: HashSet<Topic>,
 	pub data_fields: HashSet<DataField>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct HeaderMessage {
 	block_number: u32,
 	header: Header,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+/// Block status, ordered by the processing progress
+#[derive(
+	Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, JsonSchema,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum BlockStatus {
 	Unavailable,
@@ -275,6 +550,47 @@ pub enum BlockStatus {
 	Finished,
 }
 
+impl BlockStatus {
+	/// Returns true if block reached the given status, or if block is unavailable and the status cannot be reached
+	pub fn has_reached(&self, status: BlockStatus) -> bool {
+		*self == BlockStatus::Unavailable || *self >= status
+	}
+}
+
+/// Timeout in seconds (e.g. `30s` or `30`), or milliseconds (e.g. `500ms`)
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub struct Timeout(pub Duration);
+
+impl TryFrom<String> for Timeout {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		let (number, to_duration): (&str, fn(u64) -> Duration) =
+			match (value.strip_suffix("ms"), value.strip_suffix('s')) {
+				(Some(millis), _) => (millis, Duration::from_millis),
+				(None, Some(secs)) => (secs, Duration::from_secs),
+				(None, None) => (&value, Duration::from_secs),
+			};
+		number
+			.parse::<u64>()
+			.map(|number| Timeout(to_duration(number)))
+			.context("Cannot parse timeout")
+	}
+}
+
+#[derive(Deserialize, Default)]
+pub struct BlockQuery {
+	pub wait_for: Option<BlockStatus>,
+	pub timeout: Option<Timeout>,
+}
+
+#[derive(Deserialize)]
+pub struct BlocksQuery {
+	pub from: u32,
+	pub to: u32,
+}
+
 pub fn block_status(
 	sync_start_block: &Option<u32>,
 	state: &State,
@@ -323,10 +639,13 @@ pub fn block_status(
 	Some(BlockStatus::Pending)
 }
 
-#[derive(Serialize, Deserialize, PartialEq)]
+#[derive(Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Block {
 	pub status: BlockStatus,
 	pub confidence: Option<f64>,
+	/// Columns recoverable from DHT alone, if the block is archived
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub dht_columns: Option<Vec<u16>>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq)]
@@ -342,7 +661,11 @@ impl BlockAndConfidence {
 
 impl Block {
 	pub fn new(status: BlockStatus, confidence: Option<f64>) -> Self {
-		Self { status, confidence }
+		Self {
+			status,
+			confidence,
+			dht_columns: None,
+		}
 	}
 }
 
@@ -352,6 +675,192 @@ impl Reply for Block {
 	}
 }
 
+#[derive(Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct NumberedBlock {
+	pub block_number: u32,
+	#[serde(flatten)]
+	pub block: Block,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct BlocksResponse {
+	pub blocks: Vec<NumberedBlock>,
+}
+
+impl Reply for BlocksResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
+/// Position of the cell in the extended matrix
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+pub struct CellPosition {
+	pub row: u32,
+	pub col: u16,
+}
+
+impl From<Position> for CellPosition {
+	fn from(Position { row, col }: Position) -> Self {
+		CellPosition { row, col }
+	}
+}
+
+impl From<CellPosition> for Position {
+	fn from(CellPosition { row, col }: CellPosition) -> Self {
+		Position { row, col }
+	}
+}
+
+/// Cell proof (48 bytes) followed by the cell data (32 bytes)
+#[derive(Debug, Clone)]
+struct CellContent([u8; CELL_WITH_PROOF_SIZE]);
+
+impl Serialize for CellContent {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let hex_string = format!("0x{}", hex::encode(self.0));
+		serializer.serialize_str(&hex_string)
+	}
+}
+
+impl<'de> Deserialize<'de> for CellContent {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s = String::deserialize(deserializer)?;
+
+		let Some(hex_string) = s.strip_prefix("0x") else {
+			return Err(de::Error::custom("Expected a hex string with 0x prefix"));
+		};
+
+		let decoded = hex::decode(hex_string).map_err(de::Error::custom)?;
+		let decoded_len = decoded.len();
+		let bytes: [u8; CELL_WITH_PROOF_SIZE] = decoded
+			.try_into()
+			.map_err(|_| de::Error::invalid_length(decoded_len, &"Expected vector of 80 bytes"))?;
+
+		Ok(CellContent(bytes))
+	}
+}
+
+impl JsonSchema for CellContent {
+	fn schema_name() -> String {
+		"CellContent".to_string()
+	}
+
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		string_schema("hex")
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Cell {
+	pub position: CellPosition,
+	content: CellContent,
+}
+
+impl From<DaCell> for Cell {
+	fn from(cell: DaCell) -> Self {
+		Cell {
+			position: cell.position.into(),
+			content: CellContent(cell.content),
+		}
+	}
+}
+
+impl From<Cell> for DaCell {
+	fn from(cell: Cell) -> Self {
+		DaCell {
+			position: cell.position.into(),
+			content: cell.content.0,
+		}
+	}
+}
+
+/// Comma separated list of the cell positions, in `{row}:{col}` format
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+pub struct PositionsQueryParameter(pub Vec<Position>);
+
+impl TryFrom<String> for PositionsQueryParameter {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value
+			.split(',')
+			.map(|part| {
+				let (row, col) = part
+					.split_once(':')
+					.ok_or_else(|| anyhow!("Expected position in row:col format"))?;
+				Ok(Position {
+					row: row.parse().context("Cannot parse row")?,
+					col: col.parse().context("Cannot parse column")?,
+				})
+			})
+			.collect::<anyhow::Result<Vec<_>>>()
+			.map(PositionsQueryParameter)
+	}
+}
+
+#[derive(Deserialize)]
+pub struct CellsQuery {
+	pub positions: PositionsQueryParameter,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CellsResponse {
+	pub block_number: u32,
+	commitments: Vec<Commitment>,
+	pub cells: Vec<Cell>,
+	/// Positions of the cells which cannot be fetched
+	pub missing: Vec<CellPosition>,
+}
+
+impl CellsResponse {
+	pub fn new(
+		block_number: u32,
+		commitments: Vec<[u8; config::COMMITMENT_SIZE]>,
+		cells: Vec<DaCell>,
+		missing: Vec<Position>,
+	) -> Self {
+		CellsResponse {
+			block_number,
+			commitments: commitments.into_iter().map(Commitment).collect(),
+			cells: cells.into_iter().map(From::from).collect(),
+			missing: missing.into_iter().map(From::from).collect(),
+		}
+	}
+}
+
+impl Reply for CellsResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct VerifyCellsRequest {
+	pub block_number: u32,
+	pub cells: Vec<Cell>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct VerifyCellsResponse {
+	pub block_number: u32,
+	pub verified: Vec<CellPosition>,
+	pub unverified: Vec<CellPosition>,
+}
+
+impl Reply for VerifyCellsResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
 impl TryFrom<avail_subxt::primitives::Header> for HeaderMessage {
 	type Error = anyhow::Error;
 
@@ -364,12 +873,16 @@ impl TryFrom<avail_subxt::primitives::Header> for HeaderMessage {
 	}
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Header {
+	#[schemars(with = "String")]
 	hash: H256,
+	#[schemars(with = "String")]
 	parent_hash: H256,
 	pub number: u32,
+	#[schemars(with = "String")]
 	state_root: H256,
+	#[schemars(with = "String")]
 	extrinsics_root: H256,
 	extension: Extension,
 }
@@ -380,6 +893,12 @@ impl Reply for Header {
 	}
 }
 
+impl Reply for DataInclusionProof {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
 #[derive(Debug, Clone)]
 struct Commitment([u8; config::COMMITMENT_SIZE]);
 
@@ -419,12 +938,41 @@ impl<'de> Deserialize<'de> for Commitment {
 	}
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl JsonSchema for Commitment {
+	fn schema_name() -> String {
+		"Commitment".to_string()
+	}
+
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		string_schema("hex")
+	}
+}
+
+/// Schema of the serialized [`CompactDataLookup`]
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "CompactDataLookup")]
+struct CompactDataLookupSchema {
+	size: u32,
+	index: Vec<DataLookupItemSchema>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "DataLookupItem")]
+struct DataLookupItemSchema {
+	app_id: u32,
+	start: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 struct Extension {
 	rows: u16,
 	cols: u16,
+	#[schemars(with = "String")]
 	data_root: H256,
 	commitments: Vec<Commitment>,
+	#[schemars(with = "CompactDataLookupSchema")]
 	app_lookup: CompactDataLookup,
 }
 
@@ -493,7 +1041,7 @@ impl TryFrom<RpcEvent> for PublishMessage {
 	}
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ConfidenceMessage {
 	block_number: u32,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -533,10 +1081,44 @@ pub struct DataQuery {
 	pub fields: Option<FieldsQueryParameter>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+pub struct TopicsQueryParameter(pub HashSet<Topic>);
+
+impl TryFrom<String> for TopicsQueryParameter {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value
+			.split(',')
+			.map(|part| format!(r#""{part}""#))
+			.map(|part| serde_json::from_str(&part).context("Cannot deserialize topic"))
+			.collect::<anyhow::Result<HashSet<_>>>()
+			.map(TopicsQueryParameter)
+	}
+}
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+	pub topics: Option<TopicsQueryParameter>,
+	pub fields: Option<FieldsQueryParameter>,
+}
+
+impl EventsQuery {
+	pub fn is_subscribed(&self, topic: &Topic) -> bool {
+		let Some(TopicsQueryParameter(topics)) = &self.topics else {
+			return true;
+		};
+		topics.contains(topic)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct DataResponse {
 	pub block_number: u32,
 	pub data_transactions: Vec<DataTransaction>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub confidence: Option<f64>,
 }
 
 impl Reply for DataResponse {
@@ -545,72 +1127,252 @@ impl Reply for DataResponse {
 	}
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct DataMessage {
 	block_number: u32,
 	data_transactions: Vec<DataTransaction>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct DataTransaction {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	data: Option<Base64>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	extrinsic: Option<Base64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	hash: Option<H256>,
+	/// Index of the transaction in the block, if known
+	#[serde(skip_serializing_if = "Option::is_none")]
+	index: Option<u32>,
+	/// SS58 encoded address of the signer
+	#[serde(skip_serializing_if = "Option::is_none")]
+	signer: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	nonce: Option<u32>,
+	/// Decimal encoded tip, since it can exceed the JSON number precision
+	#[serde(skip_serializing_if = "Option::is_none")]
+	tip: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	call: Option<DataCall>,
 }
 
-impl TryFrom<Vec<u8>> for DataTransaction {
-	type Error = anyhow::Error;
+#[derive(Deserialize)]
+pub struct AppDataQuery {
+	pub from: u32,
+	pub to: u32,
+}
 
-	fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
-		Ok(DataTransaction {
-			data: decode_app_data(&value)?.map(Base64),
-			extrinsic: Some(Base64(value)),
-		})
+#[derive(Deserialize)]
+pub struct TransactionsQuery {
+	pub signer: String,
+	pub from: Option<u32>,
+	pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct TransactionResponse {
+	pub app_id: u32,
+	pub block_number: u32,
+	#[serde(flatten)]
+	pub block: Block,
+	pub transaction: DataTransaction,
+}
+
+impl Reply for TransactionResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
 	}
 }
 
-pub fn filter_fields(data_transactions: &mut [DataTransaction], fields: &HashSet<DataField>) {
-	if !fields.contains(&DataField::Extrinsic) {
-		for transaction in data_transactions.iter_mut() {
-			transaction.extrinsic = None
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct TransactionsResponse {
+	pub transactions: Vec<TransactionResponse>,
+}
+
+impl Reply for TransactionsResponse {
+	fn into_response(self) -> warp::reply::Response {
+		warp::reply::json(&self).into_response()
+	}
+}
+
+/// Decoded call of the application transaction
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct DataCall {
+	/// Index of the pallet in the runtime
+	pub pallet_index: u8,
+	/// Index of the call in the pallet
+	pub call_index: u8,
+	/// Pallet name, known for the data availability calls
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pallet: Option<String>,
+	/// Call name, known for the data availability calls
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// Call arguments, decoded for the data availability calls
+	pub args: serde_json::Value,
+}
+
+impl From<&RuntimeCall> for DataCall {
+	fn from(call: &RuntimeCall) -> Self {
+		// Encoded call starts with the pallet index, followed by the call index
+		let encoded = call.encode();
+		let pallet_index = encoded.first().copied().unwrap_or_default();
+		let call_index = encoded.get(1).copied().unwrap_or_default();
+
+		let (pallet, name, args) = match call {
+			RuntimeCall::DataAvailability(Call::submit_data { data, .. }) => (
+				Some("DataAvailability"),
+				Some("submit_data"),
+				json!({ "data": Base64(data.0.clone()) }),
+			),
+			RuntimeCall::DataAvailability(_) => {
+				(Some("DataAvailability"), None, serde_json::Value::Null)
+			},
+			_ => (None, None, serde_json::Value::Null),
+		};
+
+		DataCall {
+			pallet_index,
+			call_index,
+			pallet: pallet.map(String::from),
+			name: name.map(String::from),
+			args,
 		}
 	}
-	if !fields.contains(&DataField::Data) && fields.contains(&DataField::Extrinsic) {
-		for transaction in data_transactions.iter_mut() {
-			transaction.data = None
+}
+
+impl DataTransaction {
+	/// Decodes application transaction, with its index in the block if known
+	pub fn new(index: Option<u32>, extrinsic: Vec<u8>) -> anyhow::Result<Self> {
+		let decoded = <AppUncheckedExtrinsic as Decode>::decode(&mut &extrinsic[..])
+			.context("Couldn't decode AvailExtrinsic")?;
+
+		let data = match &decoded.function {
+			RuntimeCall::DataAvailability(Call::submit_data { data, .. }) => {
+				Some(Base64(data.0.clone()))
+			},
+			_ => None,
+		};
+
+		let (signer, nonce, tip) = match &decoded.signature {
+			Some((address, _, extra)) => (
+				Some(signer_address(address)),
+				Some(extra.nonce),
+				Some(extra.tip.to_string()),
+			),
+			None => (None, None, None),
+		};
+
+		Ok(DataTransaction {
+			data,
+			hash: Some(blake2_256(&extrinsic).into()),
+			extrinsic: Some(Base64(extrinsic)),
+			index,
+			signer,
+			nonce,
+			tip,
+			call: Some(DataCall::from(&decoded.function)),
+		})
+	}
+
+	/// Returns data of the data availability `submit_data` transaction
+	pub fn data(&self) -> Option<&[u8]> {
+		self.data.as_ref().map(|data| data.0.as_slice())
+	}
+
+	/// Removes fields which are not in the given set.
+	/// Data is kept if no fields are given.
+	fn retain(&mut self, fields: &HashSet<DataField>) {
+		if !fields.is_empty() && !fields.contains(&DataField::Data) {
+			self.data = None;
+		}
+		if !fields.contains(&DataField::Extrinsic) {
+			self.extrinsic = None;
+		}
+		if !fields.contains(&DataField::Hash) {
+			self.hash = None;
+		}
+		if !fields.contains(&DataField::Index) {
+			self.index = None;
 		}
+		if !fields.contains(&DataField::Signer) {
+			self.signer = None;
+		}
+		if !fields.contains(&DataField::Nonce) {
+			self.nonce = None;
+		}
+		if !fields.contains(&DataField::Tip) {
+			self.tip = None;
+		}
+		if !fields.contains(&DataField::Call) {
+			self.call = None;
+		}
+	}
+}
+
+/// Decodes application transactions of the block.
+/// Indices of the transactions in the block are set only if known for every transaction.
+pub fn data_transactions(
+	app_data: AppData,
+	extrinsic_indices: Option<Vec<u32>>,
+) -> anyhow::Result<Vec<DataTransaction>> {
+	let extrinsic_indices = extrinsic_indices
+		.filter(|indices| indices.len() == app_data.len())
+		.map(|indices| indices.into_iter().map(Some).collect::<Vec<_>>())
+		.unwrap_or_else(|| vec![None; app_data.len()]);
+
+	app_data
+		.into_iter()
+		.zip(extrinsic_indices)
+		.map(|(extrinsic, index)| DataTransaction::new(index, extrinsic))
+		.collect()
+}
+
+pub fn filter_fields(data_transactions: &mut [DataTransaction], fields: &HashSet<DataField>) {
+	for transaction in data_transactions.iter_mut() {
+		transaction.retain(fields);
 	}
 }
 
-impl TryFrom<(u32, AppData)> for PublishMessage {
+impl TryFrom<DataVerified> for PublishMessage {
 	type Error = anyhow::Error;
 
-	fn try_from((block_number, app_data): (u32, AppData)) -> Result<Self, Self::Error> {
-		let data_transactions = app_data
-			.into_iter()
-			.map(TryFrom::try_from)
-			.collect::<anyhow::Result<Vec<_>>>()?;
+	fn try_from(data_verified: DataVerified) -> Result<Self, Self::Error> {
 		Ok(PublishMessage::DataVerified(DataMessage {
-			block_number,
-			data_transactions,
+			block_number: data_verified.block_number,
+			data_transactions: data_transactions(
+				data_verified.data,
+				data_verified.extrinsic_indices,
+			)?,
 		}))
 	}
 }
 
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, JsonSchema)]
 #[serde(tag = "topic", content = "message", rename_all = "kebab-case")]
 pub enum PublishMessage {
 	HeaderVerified(Box<HeaderMessage>),
 	ConfidenceAchieved(ConfidenceMessage),
 	DataVerified(DataMessage),
+	TransactionStatus(Submission),
 }
 
 impl PublishMessage {
-	fn apply_filter(&mut self, fields: &HashSet<DataField>) {
+	pub fn topic(&self) -> Topic {
+		match self {
+			PublishMessage::HeaderVerified(_) => Topic::HeaderVerified,
+			PublishMessage::ConfidenceAchieved(_) => Topic::ConfidenceAchieved,
+			PublishMessage::DataVerified(_) => Topic::DataVerified,
+			PublishMessage::TransactionStatus(_) => Topic::TransactionStatus,
+		}
+	}
+
+	pub fn apply_filter(&mut self, fields: &HashSet<DataField>) {
 		match self {
 			PublishMessage::HeaderVerified(_) => (),
 			PublishMessage::ConfidenceAchieved(_) => (),
+			PublishMessage::TransactionStatus(_) => (),
 			PublishMessage::DataVerified(data) => {
 				filter_fields(&mut data.data_transactions, fields)
 			},
@@ -675,6 +1437,20 @@ impl WsClients {
 		clients.insert(subscription_id.to_string(), WsClient::new(subscription));
 	}
 
+	/// Replaces topics and data fields of the existing subscription, keeping the connected sender
+	pub async fn update_subscription(
+		&self,
+		subscription_id: &str,
+		subscription: Subscription,
+	) -> anyhow::Result<()> {
+		let mut clients = self.0.write().await;
+		let Some(client) = clients.get_mut(subscription_id) else {
+			return Err(anyhow!("Client is not subscribed"));
+		};
+		client.subscription = subscription;
+		Ok(())
+	}
+
 	pub async fn publish(
 		&self,
 		topic: &Topic,
@@ -705,7 +1481,7 @@ impl Default for WsClients {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct SubscriptionId {
 	pub subscription_id: String,
 }
@@ -716,22 +1492,33 @@ impl Reply for SubscriptionId {
 	}
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "message", rename_all = "kebab-case")]
 pub enum Payload {
 	Version,
 	Status,
-	Submit(Transaction),
+	Block {
+		block_number: u32,
+	},
+	Header {
+		block_number: u32,
+	},
+	Data {
+		block_number: u32,
+		fields: Option<HashSet<DataField>>,
+	},
+	Subscription(Subscription),
+	Submit(SubmitRequest),
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct Request {
 	#[serde(flatten)]
 	pub payload: Payload,
 	pub request_id: Uuid,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Response<T> {
 	pub request_id: Uuid,
 	pub message: T,
@@ -754,15 +1541,18 @@ impl TryFrom<ws::Message> for Request {
 	}
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ErrorCode {
 	NotFound,
 	BadRequest,
+	Unauthorized,
+	Forbidden,
+	TooManyRequests,
 	InternalServerError,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Error {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub request_id: Option<Uuid>,
@@ -808,10 +1598,33 @@ impl Error {
 		Self::new(Some(request_id), None, ErrorCode::BadRequest, message)
 	}
 
+	pub fn unauthorized() -> Self {
+		Self::new(None, None, ErrorCode::Unauthorized, "Unauthorized")
+	}
+
+	pub fn forbidden(request_id: Option<Uuid>) -> Self {
+		Self::new(request_id, None, ErrorCode::Forbidden, "Forbidden")
+	}
+
+	pub fn too_many_requests() -> Self {
+		Self::new(None, None, ErrorCode::TooManyRequests, "Too Many Requests")
+	}
+
+	/// Correlates error with the websocket request
+	pub fn with_request_id(self, request_id: Uuid) -> Self {
+		Error {
+			request_id: Some(request_id),
+			..self
+		}
+	}
+
 	fn status(&self) -> StatusCode {
 		match self.error_code {
 			ErrorCode::NotFound => StatusCode::NOT_FOUND,
 			ErrorCode::BadRequest => StatusCode::BAD_REQUEST,
+			ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+			ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+			ErrorCode::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
 			ErrorCode::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
 		}
 	}
@@ -833,15 +1646,19 @@ impl From<Error> for String {
 	}
 }
 
-#[derive(Serialize, Deserialize, From)]
+#[derive(Serialize, Deserialize, From, JsonSchema)]
 #[serde(tag = "topic", rename_all = "kebab-case")]
 pub enum WsResponse {
 	Version(Response<Version>),
 	Status(Response<Status>),
+	Block(Response<Block>),
+	Header(Response<Header>),
+	Data(Response<DataResponse>),
+	SubscriptionUpdated(Response<Subscription>),
 	DataTransactionSubmitted(Response<SubmitResponse>),
 }
 
-#[derive(Serialize, Deserialize, From)]
+#[derive(Serialize, Deserialize, From, JsonSchema)]
 #[serde(tag = "topic", rename_all = "kebab-case")]
 pub enum WsError {
 	Error(Error),
@@ -861,9 +1678,10 @@ mod tests {
 	};
 
 	use super::{
-		block_status, Base64, ConfidenceMessage, DataField, DataMessage, DataTransaction,
-		Subscription, Topic, WsClients,
+		block_status, data_transactions, filter_fields, Base64, ConfidenceMessage, DataField,
+		DataMessage, DataTransaction, Subscription, Timeout, Topic, WsClients,
 	};
+	use test_case::test_case;
 
 	fn subscription(topics: Vec<Topic>, fields: Vec<DataField>) -> Subscription {
 		Subscription {
@@ -912,6 +1730,12 @@ mod tests {
 			data_transactions: vec![DataTransaction {
 				data: transaction_data(),
 				extrinsic: transaction_data(),
+				hash: None,
+				index: None,
+				signer: None,
+				nonce: None,
+				tip: None,
+				call: None,
 			}],
 		})
 	}
@@ -982,6 +1806,58 @@ mod tests {
 		};
 	}
 
+	fn extrinsic() -> Vec<u8> {
+		let extrinsic = "vQGEANQ1k8cV/dMcYRQavQSpn9aCLIVYhUzN45pWhOelbaJ9ATIMK7ATKhdJRt/GtGciPPa4MYxxrurlX0cSXJ65qIx+DL+cMuoIBESJBZxe0QepaT4/AXr9w3Ct7xVJo/BqbYMABAAEHQEUdGVzdAo=";
+		Base64::try_from(extrinsic.to_string()).unwrap().0
+	}
+
+	#[test]
+	fn data_transaction_decoded() {
+		let transaction = DataTransaction::new(Some(2), extrinsic()).unwrap();
+		assert_eq!(transaction.data, Some(Base64(b"test\n".to_vec())));
+		assert_eq!(transaction.extrinsic, Some(Base64(extrinsic())));
+		assert_eq!(
+			transaction.hash,
+			Some(H256(hex_literal::hex!(
+				"2cc86c8e42a3f0cfb45c2e8b48e3be2a73aa7f9439cfe4cf95725e9f7cb71007"
+			)))
+		);
+		assert_eq!(transaction.index, Some(2));
+		assert_eq!(
+			transaction.signer.as_deref(),
+			Some("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+		);
+		assert_eq!(transaction.nonce, Some(1));
+		assert_eq!(transaction.tip.as_deref(), Some("0"));
+		let call = transaction.call.unwrap();
+		assert_eq!(call.pallet_index, 29);
+		assert_eq!(call.call_index, 1);
+		assert_eq!(call.pallet.as_deref(), Some("DataAvailability"));
+		assert_eq!(call.name.as_deref(), Some("submit_data"));
+		assert_eq!(call.args, serde_json::json!({ "data": "dGVzdAo=" }));
+	}
+
+	#[test_case(vec![] => r#"{"data":"dGVzdAo="}"# ; "No fields")]
+	#[test_case(vec![DataField::Extrinsic] => r#"{"extrinsic":"vQGEANQ1k8cV/dMcYRQavQSpn9aCLIVYhUzN45pWhOelbaJ9ATIMK7ATKhdJRt/GtGciPPa4MYxxrurlX0cSXJ65qIx+DL+cMuoIBESJBZxe0QepaT4/AXr9w3Ct7xVJo/BqbYMABAAEHQEUdGVzdAo="}"# ; "Extrinsic")]
+	#[test_case(vec![DataField::Index, DataField::Nonce, DataField::Tip] => r#"{"index":0,"nonce":1,"tip":"0"}"# ; "Signed extension fields")]
+	#[test_case(vec![DataField::Data, DataField::Call] => r#"{"data":"dGVzdAo=","call":{"pallet_index":29,"call_index":1,"pallet":"DataAvailability","name":"submit_data","args":{"data":"dGVzdAo="}}}"# ; "Data and call")]
+	fn data_transaction_filter_fields(fields: Vec<DataField>) -> String {
+		let mut transactions = vec![DataTransaction::new(Some(0), extrinsic()).unwrap()];
+		filter_fields(&mut transactions, &fields.into_iter().collect());
+		serde_json::to_string(&transactions[0]).unwrap()
+	}
+
+	#[test_case(None => vec![None, None] ; "Unknown indices")]
+	#[test_case(Some(vec![3]) => vec![None, None] ; "Indices count mismatch")]
+	#[test_case(Some(vec![3, 5]) => vec![Some(3), Some(5)] ; "Known indices")]
+	fn data_transactions_indices(indices: Option<Vec<u32>>) -> Vec<Option<u32>> {
+		data_transactions(vec![extrinsic(), extrinsic()], indices)
+			.unwrap()
+			.into_iter()
+			.map(|transaction| transaction.index)
+			.collect()
+	}
+
 	#[test]
 	fn block_status_none() {
 		let mut state = State::default();
@@ -1127,4 +2003,23 @@ mod tests {
 		assert_eq!(block_status(&Some(1), &state, 5), finished);
 		assert_ne!(block_status(&Some(1), &state, 6), finished);
 	}
+
+	#[test_case("30s" => Some(Duration::from_secs(30)) ; "Seconds")]
+	#[test_case("30" => Some(Duration::from_secs(30)) ; "Seconds without unit")]
+	#[test_case("500ms" => Some(Duration::from_millis(500)) ; "Milliseconds")]
+	#[test_case("30m" => None ; "Unsupported unit")]
+	#[test_case("s" => None ; "Missing number")]
+	fn timeout_parse(value: &str) -> Option<Duration> {
+		Timeout::try_from(value.to_string())
+			.ok()
+			.map(|timeout| timeout.0)
+	}
+
+	#[test_case(BlockStatus::Pending, BlockStatus::Finished => false ; "Pending")]
+	#[test_case(BlockStatus::VerifyingData, BlockStatus::VerifyingConfidence => true ; "Passed")]
+	#[test_case(BlockStatus::Finished, BlockStatus::Finished => true ; "Finished")]
+	#[test_case(BlockStatus::Unavailable, BlockStatus::Finished => true ; "Unavailable")]
+	fn block_status_has_reached(status: BlockStatus, target: BlockStatus) -> bool {
+		status.has_reached(target)
+	}
 }
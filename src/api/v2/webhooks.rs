@@ -0,0 +1,424 @@
+//! Webhook delivery of the published messages.
+//!
+//! Each configured webhook receives messages on the subscribed topics as JSON encoded HTTP POST requests, in the same
+//! format as the websocket messages. Requests contain `X-Avail-Timestamp` header with the UNIX timestamp of the
+//! delivery attempt, and if webhook secret is configured, `X-Avail-Signature` header with the HMAC-SHA256 signature
+//! of the `{timestamp}.{body}` string, formatted as `sha256={hex-encoded-signature}`.
+//!
+//! Failed deliveries are retried with exponential backoff. Messages which cannot be delivered after the configured
+//! number of retries are written to the dead-letter log.
+//!
+//! Messages are queued per webhook in a bounded queue, so a slow webhook does not delay the others.
+//! Messages dropped because the queue is full are written to the dead-letter log as well.
+
+use anyhow::{anyhow, Context, Result};
+use hmac::{Hmac, Mac};
+use hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Request};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use serde::Serialize;
+use sha2::Sha256;
+use std::{fs::OpenOptions, io::Write, time::Duration};
+use tokio::sync::{
+	broadcast::{self, error::RecvError},
+	mpsc::{self, error::TrySendError},
+};
+use tracing::{error, info, warn};
+
+use super::types::PublishMessage;
+use crate::{
+	types::{WebhookConfig, WebhooksConfig},
+	utils::unix_timestamp,
+};
+
+const TIMESTAMP_HEADER: &str = "X-Avail-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Avail-Signature";
+/// Maximum time to wait for the webhook response
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum delay between the delivery retries
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+/// Maximum number of messages queued for delivery per webhook
+const QUEUE_SIZE: usize = 1000;
+
+type HttpClient = Client<HttpsConnector<HttpConnector>>;
+
+fn http_client() -> HttpClient {
+	let connector = HttpsConnectorBuilder::new()
+		.with_native_roots()
+		.https_or_http()
+		.enable_http1()
+		.build();
+	Client::builder().build(connector)
+}
+
+/// Returns HMAC-SHA256 signature of the request body and timestamp
+fn signature(secret: &str, timestamp: u64, body: &[u8]) -> String {
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+	mac.update(format!("{timestamp}.").as_bytes());
+	mac.update(body);
+	format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Returns delay before the given retry, doubling the initial delay on each retry
+fn backoff(retry_delay: Duration, retry: u32) -> Duration {
+	retry_delay
+		.saturating_mul(2u32.saturating_pow(retry))
+		.min(MAX_RETRY_DELAY)
+}
+
+#[derive(Serialize)]
+struct DeadLetter<'a> {
+	url: &'a str,
+	timestamp: u64,
+	error: String,
+	/// Undelivered message, not known if messages were skipped before being received
+	message: Option<&'a PublishMessage>,
+}
+
+#[derive(Clone)]
+struct Delivery {
+	client: HttpClient,
+	max_retries: u32,
+	retry_delay: Duration,
+	dead_letter_path: Option<String>,
+	queue_size: usize,
+}
+
+impl Delivery {
+	async fn post(&self, webhook: &WebhookConfig, body: &[u8]) -> Result<()> {
+		let timestamp = unix_timestamp();
+		let mut request = Request::post(&webhook.url)
+			.header(CONTENT_TYPE, "application/json")
+			.header(TIMESTAMP_HEADER, timestamp);
+		if let Some(secret) = &webhook.secret {
+			request = request.header(
+				SIGNATURE_HEADER,
+				signature(secret.expose(), timestamp, body),
+			);
+		}
+		let request = request
+			.body(Body::from(body.to_vec()))
+			.context("Cannot create request")?;
+
+		let response = tokio::time::timeout(REQUEST_TIMEOUT, self.client.request(request))
+			.await
+			.context("Request timed out")?
+			.context("Request failed")?;
+
+		if !response.status().is_success() {
+			return Err(anyhow!("Webhook responded with {}", response.status()));
+		}
+		Ok(())
+	}
+
+	/// Delivers message to the webhook, retrying with exponential backoff on failure
+	async fn deliver(&self, webhook: &WebhookConfig, message: &PublishMessage) -> Result<()> {
+		let body = serde_json::to_vec(message).context("Cannot serialize message")?;
+		let mut retry = 0;
+		loop {
+			match self.post(webhook, &body).await {
+				Ok(()) => return Ok(()),
+				Err(error) if retry >= self.max_retries => return Err(error),
+				Err(error) => {
+					let delay = backoff(self.retry_delay, retry);
+					warn!(url = %webhook.url, "Webhook delivery failed, retrying in {delay:?}: {error:#}");
+					tokio::time::sleep(delay).await;
+					retry += 1;
+				},
+			}
+		}
+	}
+
+	fn write_dead_letter(&self, letter: &DeadLetter) -> Result<()> {
+		let Some(path) = &self.dead_letter_path else {
+			return Ok(());
+		};
+		let mut line = serde_json::to_vec(letter).context("Cannot serialize dead letter")?;
+		line.push(b'\n');
+		OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(path)
+			.and_then(|mut file| file.write_all(&line))
+			.with_context(|| format!("Cannot write dead letter to {path}"))
+	}
+
+	fn dead_letter(
+		&self,
+		webhook: &WebhookConfig,
+		error: String,
+		message: Option<&PublishMessage>,
+	) {
+		error!(url = %webhook.url, "Cannot deliver message to webhook: {error}");
+		let letter = DeadLetter {
+			url: &webhook.url,
+			timestamp: unix_timestamp(),
+			error,
+			message,
+		};
+		if let Err(error) = self.write_dead_letter(&letter) {
+			error!("{error:#}");
+		}
+	}
+
+	/// Delivers queued messages, until the queue is closed
+	async fn deliver_queued(
+		self,
+		webhook: WebhookConfig,
+		mut queue: mpsc::Receiver<PublishMessage>,
+	) {
+		while let Some(message) = queue.recv().await {
+			if let Err(error) = self.deliver(&webhook, &message).await {
+				self.dead_letter(&webhook, format!("{error:#}"), Some(&message));
+			}
+		}
+	}
+
+	/// Queues subscribed messages for delivery.
+	/// Messages which cannot be queued, or are skipped on lagging, are written to the dead-letter log.
+	async fn run(self, webhook: WebhookConfig, mut receiver: broadcast::Receiver<PublishMessage>) {
+		let (sender, queue) = mpsc::channel(self.queue_size);
+		tokio::spawn(self.clone().deliver_queued(webhook.clone(), queue));
+
+		loop {
+			let message = match receiver.recv().await {
+				Ok(message) => message,
+				Err(RecvError::Lagged(skipped)) => {
+					let error = format!("Webhook is lagging, {skipped} messages skipped");
+					self.dead_letter(&webhook, error, None);
+					continue;
+				},
+				Err(RecvError::Closed) => return,
+			};
+
+			if !webhook.topics.is_empty() && !webhook.topics.contains(&message.topic()) {
+				continue;
+			}
+
+			match sender.try_send(message) {
+				Ok(()) => (),
+				Err(TrySendError::Full(message)) => {
+					let error = "Webhook delivery queue is full".to_string();
+					self.dead_letter(&webhook, error, Some(&message));
+				},
+				Err(TrySendError::Closed(_)) => return,
+			}
+		}
+	}
+}
+
+/// Spawns delivery of the published messages to each of the configured webhooks
+pub fn run(cfg: WebhooksConfig, events: &broadcast::Sender<PublishMessage>) {
+	let delivery = Delivery {
+		client: http_client(),
+		max_retries: cfg.max_retries,
+		retry_delay: cfg.retry_delay,
+		dead_letter_path: cfg.dead_letter_path,
+		queue_size: QUEUE_SIZE,
+	};
+
+	for webhook in cfg.webhooks {
+		info!(url = %webhook.url, topics = ?webhook.topics, "Delivering messages to webhook");
+		tokio::spawn(delivery.clone().run(webhook, events.subscribe()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		backoff, http_client, signature, DeadLetter, Delivery, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+	};
+	use crate::{api::v2::types::PublishMessage, types::WebhookConfig};
+	use hyper::body::Bytes;
+	use std::{
+		fs,
+		net::SocketAddr,
+		sync::{
+			atomic::{AtomicU32, Ordering},
+			Arc,
+		},
+		time::Duration,
+	};
+	use test_case::test_case;
+	use tokio::sync::broadcast;
+	use warp::{http::StatusCode, Filter};
+
+	fn message() -> PublishMessage {
+		let message =
+			r#"{"topic":"confidence-achieved","message":{"block_number":1,"confidence":93.75}}"#;
+		serde_json::from_str(message).unwrap()
+	}
+
+	fn delivery(max_retries: u32, dead_letter_path: Option<String>) -> Delivery {
+		Delivery {
+			client: http_client(),
+			max_retries,
+			retry_delay: Duration::from_millis(10),
+			dead_letter_path,
+			queue_size: 1,
+		}
+	}
+
+	fn dead_letter_path(name: &str) -> String {
+		let file_name = format!("avail_light_{name}_{}", std::process::id());
+		std::env::temp_dir()
+			.join(file_name)
+			.to_string_lossy()
+			.to_string()
+	}
+
+	/// Waits until dead-letter log has the given number of lines, and returns them
+	async fn dead_letters(path: &str, count: usize) -> Vec<serde_json::Value> {
+		for _ in 0..100 {
+			let content = fs::read_to_string(path).unwrap_or_default();
+			if content.lines().count() >= count {
+				return content
+					.lines()
+					.map(|line| serde_json::from_str(line).unwrap())
+					.collect();
+			}
+			tokio::time::sleep(Duration::from_millis(20)).await;
+		}
+		panic!("Dead letters are not written");
+	}
+
+	/// Starts webhook server which fails given number of requests, and returns number of received requests
+	fn webhook_server(failures: u32, secret: &'static str) -> (SocketAddr, Arc<AtomicU32>) {
+		let requests = Arc::new(AtomicU32::new(0));
+		let received = requests.clone();
+		let route = warp::post()
+			.and(warp::header::<u64>(TIMESTAMP_HEADER))
+			.and(warp::header::<String>(SIGNATURE_HEADER))
+			.and(warp::body::bytes())
+			.map(move |timestamp, signature_header: String, body: Bytes| {
+				if signature_header != signature(secret, timestamp, &body) {
+					return StatusCode::BAD_REQUEST;
+				}
+				match received.fetch_add(1, Ordering::SeqCst) < failures {
+					true => StatusCode::INTERNAL_SERVER_ERROR,
+					false => StatusCode::OK,
+				}
+			});
+		let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+		tokio::spawn(server);
+		(addr, requests)
+	}
+
+	fn webhook(addr: SocketAddr) -> WebhookConfig {
+		WebhookConfig {
+			url: format!("http://{addr}/webhook"),
+			topics: vec![],
+			secret: Some("secret".to_string().into()),
+		}
+	}
+
+	#[test]
+	fn signature_is_hmac_of_timestamp_and_body() {
+		let body = br#"{"topic":"confidence-achieved"}"#;
+		assert_eq!(
+			signature("secret", 1700000000, body),
+			"sha256=dcd4651066c601083005d406cb12afbdac7a979d7a173cab21bd3b179d8349be"
+		);
+	}
+
+	#[test_case(0, 1 ; "First retry")]
+	#[test_case(3, 8 ; "Fourth retry")]
+	#[test_case(20, 300 ; "Maximum delay")]
+	fn backoff_doubles_delay(retry: u32, expected_secs: u64) {
+		let delay = backoff(Duration::from_secs(1), retry);
+		assert_eq!(delay, Duration::from_secs(expected_secs));
+	}
+
+	#[tokio::test]
+	async fn deliver_retries_failed_requests() {
+		let (addr, requests) = webhook_server(2, "secret");
+		let result = delivery(2, None).deliver(&webhook(addr), &message()).await;
+		assert!(result.is_ok());
+		assert_eq!(requests.load(Ordering::SeqCst), 3);
+	}
+
+	#[tokio::test]
+	async fn deliver_fails_after_max_retries() {
+		let (addr, requests) = webhook_server(u32::MAX, "secret");
+		let result = delivery(1, None).deliver(&webhook(addr), &message()).await;
+		assert!(result.is_err());
+		assert_eq!(requests.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn dead_letters_are_appended() {
+		let path = dead_letter_path("dead_letters");
+		let delivery = delivery(0, Some(path.clone()));
+
+		let message = message();
+		let letter = DeadLetter {
+			url: "http://localhost/webhook",
+			timestamp: 1700000000,
+			error: "Webhook responded with 500 Internal Server Error".to_string(),
+			message: Some(&message),
+		};
+		delivery.write_dead_letter(&letter).unwrap();
+		delivery.write_dead_letter(&letter).unwrap();
+
+		let content = fs::read_to_string(&path).unwrap();
+		let lines = content.lines().collect::<Vec<_>>();
+		assert_eq!(lines.len(), 2);
+		let letter: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+		assert_eq!(letter["url"], "http://localhost/webhook");
+		assert_eq!(letter["message"]["topic"], "confidence-achieved");
+
+		fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn skipped_messages_are_dead_lettered() {
+		let path = dead_letter_path("skipped_messages");
+		let (addr, requests) = webhook_server(0, "secret");
+		let (sender, receiver) = broadcast::channel(1);
+		for _ in 0..3 {
+			sender.send(message()).unwrap();
+		}
+
+		tokio::spawn(delivery(0, Some(path.clone())).run(webhook(addr), receiver));
+
+		let letters = dead_letters(&path, 1).await;
+		assert_eq!(
+			letters[0]["error"],
+			"Webhook is lagging, 2 messages skipped"
+		);
+		assert!(letters[0]["message"].is_null());
+		drop(sender);
+		tokio::time::sleep(Duration::from_millis(100)).await;
+		assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+		fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn messages_over_queue_size_are_dead_lettered() {
+		let path = dead_letter_path("queue_full");
+		// Webhook which does not respond in time keeps the delivery busy
+		let route = warp::post().then(|| async {
+			tokio::time::sleep(Duration::from_secs(60)).await;
+			StatusCode::OK
+		});
+		let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+		tokio::spawn(server);
+
+		let (sender, receiver) = broadcast::channel(10);
+		tokio::spawn(delivery(0, Some(path.clone())).run(webhook(addr), receiver));
+		// First message is being delivered, second is queued, and the rest is dropped
+		for _ in 0..4 {
+			sender.send(message()).unwrap();
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+
+		let letters = dead_letters(&path, 2).await;
+		for letter in letters {
+			assert_eq!(letter["error"], "Webhook delivery queue is full");
+			assert_eq!(letter["message"]["topic"], "confidence-achieved");
+		}
+
+		fs::remove_file(path).unwrap();
+	}
+}
@@ -1,11 +1,16 @@
 use super::{
-	transactions,
+	handlers, transactions,
 	types::{
-		Payload, Request, Response, Status, Transaction, Version, WsClients, WsError, WsResponse,
+		BlockQuery, DataQuery, FieldsQueryParameter, Payload, Request, Response, Status,
+		Transaction, Version, WsClients, WsError, WsResponse,
 	},
 };
 use crate::{
-	api::v2::types::{Error, Sender},
+	api::{
+		auth::{Access, Scope},
+		v2::types::{Error, Sender},
+	},
+	data::Database,
 	network::rpc::Node,
 	types::{RuntimeConfig, State},
 };
@@ -28,6 +33,8 @@ pub async fn connect(
 	node: Node,
 	submitter: Option<Arc<impl transactions::Submit + Clone + Send + Sync + 'static>>,
 	state: Arc<Mutex<State>>,
+	db: impl Database + 'static,
+	access: Access,
 ) {
 	let (web_socket_sender, mut web_socket_receiver) = web_socket.split();
 	let (sender, receiver) = mpsc::unbounded_channel();
@@ -67,16 +74,25 @@ pub async fn connect(
 		let submitter = submitter.clone();
 		let state = state.clone();
 
-		let send_result =
-			match handle_request(message, &version, &config, &node, submitter, state).await {
-				Ok(response) => send(sender.clone(), response),
-				Err(error) => {
-					if let Some(cause) = error.cause.as_ref() {
-						error!("Failed to handle request: {cause:#}");
-					};
-					send::<WsError>(sender.clone(), error.into())
-				},
-			};
+		let context = RequestContext {
+			subscription_id: &subscription_id,
+			clients: &clients,
+			version: &version,
+			config: &config,
+			node: &node,
+			access: &access,
+		};
+
+		let send_result = match handle_request(message, context, submitter, state, db.clone()).await
+		{
+			Ok(response) => send(sender.clone(), response),
+			Err(error) => {
+				if let Some(cause) = error.cause.as_ref() {
+					error!("Failed to handle request: {cause:#}");
+				};
+				send::<WsError>(sender.clone(), error.into())
+			},
+		};
 
 		if let Err(error) = send_result {
 			warn!("Error sending message: {error:#}");
@@ -84,39 +100,88 @@ pub async fn connect(
 	}
 }
 
+/// Connection scoped parameters of the websocket request
+struct RequestContext<'a> {
+	subscription_id: &'a str,
+	clients: &'a WsClients,
+	version: &'a Version,
+	config: &'a RuntimeConfig,
+	node: &'a Node,
+	access: &'a Access,
+}
+
 async fn handle_request(
 	message: Message,
-	version: &Version,
-	config: &RuntimeConfig,
-	node: &Node,
+	context: RequestContext<'_>,
 	submitter: Option<Arc<impl transactions::Submit>>,
 	state: Arc<Mutex<State>>,
+	db: impl Database,
 ) -> Result<WsResponse, Error> {
 	let request = Request::try_from(message).map_err(|error| {
 		Error::bad_request_unknown(&format!("Failed to parse request: {error}"))
 	})?;
 
 	let request_id = request.request_id;
+	let config = context.config.clone();
 	match request.payload {
-		Payload::Version => Ok(Response::new(request_id, version.clone()).into()),
+		Payload::Version => Ok(Response::new(request_id, context.version.clone()).into()),
 		Payload::Status => {
 			let state = state.lock().expect("State lock can be acquired");
-			let status = Status::new(config, node, &state);
+			let status = Status::new(context.config, context.node, &state);
 			Ok(Response::new(request_id, status).into())
 		},
-		Payload::Submit(transaction) => {
+		Payload::Block { block_number } => {
+			// Waiting is not supported, since it would block other requests on the connection
+			handlers::block(block_number, BlockQuery::default(), config, state, db)
+				.await
+				.map(|block| Response::new(request_id, block).into())
+				.map_err(|error| error.with_request_id(request_id))
+		},
+		Payload::Header { block_number } => handlers::block_header(block_number, config, state, db)
+			.await
+			.map(|header| Response::new(request_id, header).into())
+			.map_err(|error| error.with_request_id(request_id)),
+		Payload::Data {
+			block_number,
+			fields,
+		} => {
+			let query = DataQuery {
+				fields: fields.map(FieldsQueryParameter),
+			};
+			handlers::block_data(block_number, query, config, state, db)
+				.await
+				.map(|data| Response::new(request_id, data).into())
+				.map_err(|error| error.with_request_id(request_id))
+		},
+		Payload::Subscription(subscription) => context
+			.clients
+			.update_subscription(context.subscription_id, subscription.clone())
+			.await
+			.map(|_| Response::new(request_id, subscription).into())
+			.map_err(|error| Error::internal_server_error(error).with_request_id(request_id)),
+		Payload::Submit(request) => {
+			if !context.access.allows(Scope::Submit) {
+				return Err(Error::forbidden(Some(request_id)));
+			}
 			let Some(submitter) = submitter else {
 				return Err(Error::bad_request(request_id, "Submit is not configured."));
 			};
-			if transaction.is_empty() {
+			if request.transaction.is_empty() {
 				return Err(Error::bad_request(request_id, "Transaction is empty."));
 			}
-			if matches!(transaction, Transaction::Data(_)) && !submitter.has_signer() {
-				return Err(Error::bad_request(request_id, "Signer is not configured."));
+			if matches!(request.transaction, Transaction::Data(_)) {
+				if !submitter.has_signer() {
+					return Err(Error::bad_request(request_id, "Signer is not configured."));
+				}
+				if let Err(error) =
+					submitter.check_signer(request.signer.as_deref(), request.app_id)
+				{
+					return Err(Error::bad_request(request_id, &format!("{error}.")));
+				}
 			};
 
 			submitter
-				.submit(transaction)
+				.submit(request)
 				.await
 				.map(|response| Response::new(request_id, response).into())
 				.map_err(Error::internal_server_error)
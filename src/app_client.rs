@@ -8,6 +8,12 @@
 //! Verify commitment equality for each row
 //! Decode app data and store it into local database under the `app_id:block_number` key
 //!
+//! # Archival mode
+//!
+//! If archival mode is enabled, complete data matrix is reconstructed for every block, using cells fetched from DHT,
+//! and RPC as a fallback for columns which cannot be recovered from DHT alone. Data of every application is decoded and stored into local database.
+//! Columns recoverable from DHT alone are stored into local database, and exposed on the `/v2/blocks/{block_number}` endpoint.
+//!
 //! # Notes
 //!
 //! If application client fails to run or stops its execution, error is logged, and other tasks continue with execution.
@@ -16,12 +22,11 @@ use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use avail_core::AppId;
 use avail_subxt::utils::H256;
-use codec::Encode;
 use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
 use kate_recovery::{
 	com::{
-		app_specific_rows, columns_positions, decode_app_extrinsics, reconstruct_columns, AppData,
-		Percent,
+		app_specific_rows, columns_positions, decode_app_extrinsics, reconstruct_columns,
+		reconstruct_extrinsics, AppData, Percent,
 	},
 	commitments,
 	config::{self, CHUNK_SIZE},
@@ -32,19 +37,23 @@ use mockall::automock;
 use rand::SeedableRng as _;
 use rand_chacha::ChaChaRng;
 use rocksdb::DB;
+use sp_core::blake2_256;
 use std::{
 	collections::{HashMap, HashSet},
 	ops::Range,
 	sync::{Arc, Mutex},
 };
 use tokio::sync::{broadcast, mpsc::Sender};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, info_span, instrument, warn, Instrument};
 
 use crate::{
-	data::store_encoded_data_in_db,
+	blobs,
+	cell_cache::CellCache,
+	data::{store_app_data_in_db, store_dht_columns_in_db, store_extrinsic_indices_in_db},
 	network::{p2p::Client as P2pClient, rpc::Client as RpcClient},
-	proof,
-	types::{AppClientConfig, BlockVerified, OptionBlockRange, State},
+	proof::{Priority, Verifier},
+	types::{AppClientConfig, BlockVerified, DataVerified, OptionBlockRange, State},
+	utils::can_reconstruct,
 };
 
 #[async_trait]
@@ -52,7 +61,6 @@ use crate::{
 trait AppClient {
 	async fn reconstruct_rows_from_dht(
 		&self,
-		pp: Arc<PublicParameters>,
 		block_number: u32,
 		dimensions: Dimensions,
 		commitments: &[[u8; config::COMMITMENT_SIZE]],
@@ -73,12 +81,27 @@ trait AppClient {
 		block_hash: H256,
 	) -> Result<Vec<Option<Vec<u8>>>>;
 
-	fn store_encoded_data_in_db<T: Encode + 'static>(
+	async fn fetch_verified_from_dht(
+		&self,
+		block_number: u32,
+		dimensions: Dimensions,
+		commitments: &[[u8; config::COMMITMENT_SIZE]],
+		positions: &[Position],
+	) -> Result<(Vec<Cell>, Vec<Position>)>;
+
+	async fn get_kate_proof(&self, block_hash: H256, positions: &[Position]) -> Result<Vec<Cell>>;
+
+	async fn verify_cells(
 		&self,
-		app_id: AppId,
 		block_number: u32,
-		data: &T,
-	) -> Result<()>;
+		dimensions: Dimensions,
+		cells: &[Cell],
+		commitments: &[[u8; config::COMMITMENT_SIZE]],
+	) -> Result<(Vec<Position>, Vec<Position>)>;
+
+	fn store_app_data_in_db(&self, app_id: AppId, block_number: u32, data: &AppData) -> Result<()>;
+
+	fn store_dht_columns_in_db(&self, block_number: u32, columns: &[u16]) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -86,13 +109,40 @@ struct AppClientImpl {
 	db: Arc<DB>,
 	p2p_client: P2pClient,
 	rpc_client: RpcClient,
+	verifier: Verifier,
+	priority: Priority,
+	cell_cache: CellCache,
+}
+
+impl AppClientImpl {
+	async fn fetch_verified(
+		&self,
+		block_number: u32,
+		dimensions: Dimensions,
+		commitments: &[[u8; config::COMMITMENT_SIZE]],
+		positions: &[Position],
+	) -> Result<(Vec<Cell>, Vec<Position>)> {
+		let (mut fetched, mut unfetched) = self
+			.p2p_client
+			.fetch_cells_from_dht(block_number, positions)
+			.await;
+
+		let (verified, mut unverified) = self
+			.verify_cells(block_number, dimensions, &fetched, commitments)
+			.await
+			.context("Failed to verify fetched cells")?;
+
+		fetched.retain(|cell| verified.contains(&cell.position));
+		unfetched.append(&mut unverified);
+
+		Ok((fetched, unfetched))
+	}
 }
 
 #[async_trait]
 impl AppClient for AppClientImpl {
 	async fn reconstruct_rows_from_dht(
 		&self,
-		pp: Arc<PublicParameters>,
 		block_number: u32,
 		dimensions: Dimensions,
 		commitments: &[[u8; config::COMMITMENT_SIZE]],
@@ -109,15 +159,9 @@ impl AppClient for AppClientImpl {
 			"Fetching {} missing row cells from DHT",
 			missing_cells.len()
 		);
-		let (fetched, unfetched) = fetch_verified(
-			pp.clone(),
-			&self.p2p_client,
-			block_number,
-			dimensions,
-			commitments,
-			&missing_cells,
-		)
-		.await?;
+		let (fetched, unfetched) = self
+			.fetch_verified(block_number, dimensions, commitments, &missing_cells)
+			.await?;
 		debug!(
 			block_number,
 			"Fetched {} row cells, {} row cells is missing",
@@ -129,15 +173,9 @@ impl AppClient for AppClientImpl {
 		let missing_cells =
 			columns_positions(dimensions, &unfetched, Percent::from_percent(66), &mut rng);
 
-		let (missing_fetched, _) = fetch_verified(
-			pp,
-			&self.p2p_client,
-			block_number,
-			dimensions,
-			commitments,
-			&missing_cells,
-		)
-		.await?;
+		let (missing_fetched, _) = self
+			.fetch_verified(block_number, dimensions, commitments, &missing_cells)
+			.await?;
 
 		let reconstructed = reconstruct_columns(dimensions, &missing_fetched)?;
 
@@ -213,15 +251,54 @@ impl AppClient for AppClientImpl {
 		Ok(result)
 	}
 
-	fn store_encoded_data_in_db<T: Encode + 'static>(
+	async fn fetch_verified_from_dht(
+		&self,
+		block_number: u32,
+		dimensions: Dimensions,
+		commitments: &[[u8; config::COMMITMENT_SIZE]],
+		positions: &[Position],
+	) -> Result<(Vec<Cell>, Vec<Position>)> {
+		self.fetch_verified(block_number, dimensions, commitments, positions)
+			.await
+	}
+
+	async fn get_kate_proof(&self, block_hash: H256, positions: &[Position]) -> Result<Vec<Cell>> {
+		self.rpc_client
+			.request_kate_proof(block_hash, positions)
+			.await
+	}
+
+	async fn verify_cells(
 		&self,
-		app_id: AppId,
 		block_number: u32,
-		data: &T,
-	) -> Result<()> {
-		store_encoded_data_in_db(self.db.clone(), app_id, block_number, &data)
+		dimensions: Dimensions,
+		cells: &[Cell],
+		commitments: &[[u8; config::COMMITMENT_SIZE]],
+	) -> Result<(Vec<Position>, Vec<Position>)> {
+		let (verified, unverified) = self
+			.verifier
+			.verify(self.priority, block_number, dimensions, cells, commitments)
+			.await?;
+
+		// Verified cells are kept locally, so they can be served without fetching them again
+		let verified_cells = cells
+			.iter()
+			.filter(|cell| verified.contains(&cell.position))
+			.cloned()
+			.collect::<Vec<_>>();
+		self.cell_cache.insert(block_number, &verified_cells);
+
+		Ok((verified, unverified))
+	}
+
+	fn store_app_data_in_db(&self, app_id: AppId, block_number: u32, data: &AppData) -> Result<()> {
+		store_app_data_in_db(self.db.clone(), app_id, block_number, data)
 			.context("Failed to store data into database")
 	}
+
+	fn store_dht_columns_in_db(&self, block_number: u32, columns: &[u16]) -> Result<()> {
+		store_dht_columns_in_db(self.db.clone(), block_number, columns)
+	}
 }
 
 fn new_data_cell(row: usize, col: usize, data: &[u8]) -> Result<DataCell> {
@@ -267,28 +344,6 @@ fn data_cell(
 		.context("Data cell not found")
 }
 
-async fn fetch_verified(
-	pp: Arc<PublicParameters>,
-	p2p_client: &P2pClient,
-	block_number: u32,
-	dimensions: Dimensions,
-	commitments: &[[u8; config::COMMITMENT_SIZE]],
-	positions: &[Position],
-) -> Result<(Vec<Cell>, Vec<Position>)> {
-	let (mut fetched, mut unfetched) = p2p_client
-		.fetch_cells_from_dht(block_number, positions)
-		.await;
-
-	let (verified, mut unverified) =
-		proof::verify(block_number, dimensions, &fetched, commitments, pp)
-			.context("Failed to verify fetched cells")?;
-
-	fetched.retain(|cell| verified.contains(&cell.position));
-	unfetched.append(&mut unverified);
-
-	Ok((fetched, unfetched))
-}
-
 #[instrument(skip_all, fields(block = block.block_num), level = "trace")]
 async fn process_block(
 	app_client: impl AppClient,
@@ -385,7 +440,7 @@ async fn process_block(
 	);
 
 	let dht_rows = app_client
-		.reconstruct_rows_from_dht(pp, block_number, dimensions, commitments, &missing_rows)
+		.reconstruct_rows_from_dht(block_number, dimensions, commitments, &missing_rows)
 		.await?;
 
 	debug!(
@@ -407,7 +462,7 @@ async fn process_block(
 
 	debug!(block_number, "Storing data into database");
 	app_client
-		.store_encoded_data_in_db(app_id, block_number, &data)
+		.store_app_data_in_db(app_id, block_number, &data)
 		.context("Failed to store data into database")?;
 
 	let bytes_count = data.iter().fold(0usize, |acc, x| acc + x.len());
@@ -416,6 +471,152 @@ async fn process_block(
 	Ok(data)
 }
 
+/// Block data reconstructed in archival mode
+struct ArchivedBlock {
+	/// Decoded data of every application in the block
+	data: Vec<(AppId, AppData)>,
+	/// Columns which were recoverable from DHT alone
+	dht_columns: Vec<u16>,
+}
+
+#[instrument(skip_all, fields(block = block.block_num), level = "trace")]
+async fn process_block_archival(
+	app_client: impl AppClient,
+	cfg: &AppClientConfig,
+	block: &BlockVerified,
+) -> Result<ArchivedBlock> {
+	let block_number = block.block_num;
+	let dimensions = block.dimensions;
+	let commitments = &block.commitments;
+
+	let columns = (0..dimensions.cols().get()).collect::<Vec<_>>();
+	let column_positions = columns
+		.iter()
+		.map(|&col| Position { row: 0, col })
+		.collect::<Vec<_>>();
+
+	let mut rng = ChaChaRng::from_seed(Default::default());
+	let positions = columns_positions(
+		dimensions,
+		&column_positions,
+		Percent::from_percent(66),
+		&mut rng,
+	);
+
+	if positions.len() > cfg.threshold {
+		return Err(anyhow!(
+			"Too many cells are needed for reconstruction: {}",
+			positions.len()
+		));
+	}
+
+	debug!(
+		block_number,
+		"Fetching {} column cells from DHT",
+		positions.len()
+	);
+	let (mut cells, unfetched) = app_client
+		.fetch_verified_from_dht(block_number, dimensions, commitments, &positions)
+		.await?;
+
+	let dht_columns = columns
+		.iter()
+		.cloned()
+		.filter(|&col| can_reconstruct(dimensions, &[col], &cells))
+		.collect::<Vec<_>>();
+
+	info!(
+		block_number,
+		"{} of {} columns are recoverable from DHT",
+		dht_columns.len(),
+		columns.len()
+	);
+
+	let missing_columns = columns
+		.iter()
+		.filter(|col| !dht_columns.contains(col))
+		.collect::<HashSet<_>>();
+
+	if !missing_columns.is_empty() {
+		if cfg.disable_rpc {
+			return Err(anyhow!(
+				"{} columns cannot be reconstructed from DHT",
+				missing_columns.len()
+			));
+		}
+
+		let missing_positions = unfetched
+			.into_iter()
+			.filter(|position| missing_columns.contains(&position.col))
+			.collect::<Vec<_>>();
+
+		debug!(
+			block_number,
+			"Fetching {} missing column cells from RPC",
+			missing_positions.len()
+		);
+
+		let mut rpc_cells = vec![];
+		for positions in missing_positions.chunks(cfg.max_cells_per_rpc) {
+			let mut fetched = app_client
+				.get_kate_proof(block.header_hash, positions)
+				.await?;
+			rpc_cells.append(&mut fetched);
+		}
+
+		let (verified, _) = app_client
+			.verify_cells(block_number, dimensions, &rpc_cells, commitments)
+			.await
+			.context("Failed to verify fetched cells")?;
+
+		rpc_cells.retain(|cell| verified.contains(&cell.position));
+		cells.append(&mut rpc_cells);
+	}
+
+	let reconstructed = reconstruct_columns(dimensions, &cells)?;
+
+	// Original data rows are on the even positions of the extended matrix
+	let data_cells = (0..dimensions.extended_rows())
+		.step_by(config::EXTENSION_FACTOR)
+		.flat_map(|row| columns.iter().map(move |&col| Position { row, col }))
+		.map(|position| data_cell(position, &reconstructed))
+		.collect::<Result<Vec<_>>>()?;
+
+	let data = reconstruct_extrinsics(&block.lookup, dimensions, data_cells)
+		.context("Failed to reconstruct extrinsics")?;
+
+	debug!(
+		block_number,
+		"Storing data of {} apps into database",
+		data.len()
+	);
+	for (app_id, app_data) in &data {
+		app_client
+			.store_app_data_in_db(*app_id, block_number, app_data)
+			.context("Failed to store data into database")?;
+	}
+
+	app_client
+		.store_dht_columns_in_db(block_number, &dht_columns)
+		.context("Failed to store DHT columns into database")?;
+
+	Ok(ArchivedBlock { data, dht_columns })
+}
+
+/// Finds indices of the application transactions in the block, by matching their hashes.
+/// Returns `None` if some of the transactions are not found in the block.
+fn extrinsic_indices(block_extrinsics: &[H256], data: &AppData) -> Option<Vec<u32>> {
+	data.iter()
+		.map(|extrinsic| {
+			let hash: H256 = blake2_256(extrinsic).into();
+			block_extrinsics
+				.iter()
+				.position(|block_extrinsic| *block_extrinsic == hash)
+				.map(|index| index as u32)
+		})
+		.collect()
+}
+
 /// Runs application client.
 ///
 /// # Arguments
@@ -426,7 +627,9 @@ async fn process_block(
 /// * `rpc_client` - Node's RPC subxt client for fetching data unavailable in DHT (if configured)
 /// * `app_id` - Application ID
 /// * `block_receive` - Channel used to receive header of verified block
-/// * `pp` - Public parameters (i.e. SRS) needed for proof verification
+/// * `pp` - Public parameters (i.e. SRS) needed for commitments verification
+/// * `verifier` - Shared proof verifier
+/// * `cell_cache` - Cache of the verified cells, served by the API
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
 	cfg: AppClientConfig,
@@ -436,9 +639,11 @@ pub async fn run(
 	app_id: AppId,
 	mut block_receive: broadcast::Receiver<BlockVerified>,
 	pp: Arc<PublicParameters>,
+	verifier: Verifier,
+	cell_cache: CellCache,
 	state: Arc<Mutex<State>>,
 	sync_range: Range<u32>,
-	data_verified_sender: broadcast::Sender<(u32, AppData)>,
+	data_verified_sender: broadcast::Sender<DataVerified>,
 	error_sender: Sender<anyhow::Error>,
 ) {
 	info!("Starting for app {app_id}...");
@@ -475,7 +680,9 @@ pub async fn run(
 
 		info!(block_number, "Block available: {dimensions:?}");
 
-		if block.lookup.range_of(app_id).is_none() {
+		let has_app_data = block.lookup.range_of(app_id).is_some();
+
+		if !has_app_data && !cfg.archival_mode {
 			info!(
 				block_number,
 				"Skipping block with no cells for app {app_id}"
@@ -489,8 +696,36 @@ pub async fn run(
 			db: db_clone,
 			p2p_client: network_client.clone(),
 			rpc_client: rpc_client.clone(),
+			verifier: verifier.clone(),
+			cell_cache: cell_cache.clone(),
+			priority: match sync_range.contains(&block_number) {
+				true => Priority::Sync,
+				false => Priority::Live,
+			},
 		};
-		let data = match process_block(app_client, &cfg, app_id, &block, pp.clone()).await {
+		let span = info_span!(parent: &block.span, "app_reconstruction", %app_id);
+		let data = if cfg.archival_mode {
+			process_block_archival(app_client, &cfg, &block)
+				.instrument(span)
+				.await
+				.map(|archived| {
+					debug!(
+						block_number,
+						"Columns recoverable from DHT: {:?}", archived.dht_columns
+					);
+					archived
+						.data
+						.into_iter()
+						.find(|(id, _)| *id == app_id)
+						.map(|(_, data)| data)
+						.unwrap_or_default()
+				})
+		} else {
+			process_block(app_client, &cfg, app_id, &block, pp.clone())
+				.instrument(span)
+				.await
+		};
+		let data = match data {
 			Ok(data) => data,
 			Err(error) => {
 				error!(block_number, "Cannot process block: {error}");
@@ -501,7 +736,46 @@ pub async fn run(
 			},
 		};
 		set_data_verified_state(state.clone(), &sync_range, block_number);
-		if let Err(error) = data_verified_sender.send((block_number, data)) {
+		if !has_app_data {
+			debug!(block_number, "Block archived, no cells for app {app_id}");
+			continue;
+		}
+		// Indices of the transactions in the block are known only from the block body
+		let extrinsic_indices = if cfg.disable_rpc {
+			None
+		} else {
+			match rpc_client
+				.get_block_extrinsic_hashes(block.header_hash)
+				.await
+			{
+				Ok(hashes) => extrinsic_indices(&hashes, &data),
+				Err(error) => {
+					warn!(block_number, "Cannot get block extrinsics: {error:#}");
+					None
+				},
+			}
+		};
+		if let Some(indices) = &extrinsic_indices {
+			if let Err(error) =
+				store_extrinsic_indices_in_db(db.clone(), app_id, block_number, indices)
+			{
+				error!(block_number, "Cannot store extrinsic indices: {error:#}");
+			}
+		}
+		match blobs::reassemble(db.clone(), app_id.0, block_number, &data) {
+			Ok(manifest_hashes) => {
+				for manifest_hash in manifest_hashes {
+					info!(block_number, %manifest_hash, "Blob reassembled");
+				}
+			},
+			Err(error) => error!(block_number, "Cannot reassemble blobs: {error:#}"),
+		}
+		let data_verified = DataVerified {
+			block_number,
+			data,
+			extrinsic_indices,
+		};
+		if let Err(error) = data_verified_sender.send(data_verified) {
 			error!("Cannot send data verified message: {error}");
 			if let Err(error) = error_sender.send(error.into()).await {
 				error!("Cannot send error message: {error}");
@@ -555,6 +829,7 @@ mod tests {
 			]
 			.to_vec(),
 			confidence: None,
+			span: tracing::Span::none(),
 		};
 		mock_client
 			.expect_fetch_rows_from_dht()
@@ -567,10 +842,10 @@ mod tests {
 		}
 		mock_client
 			.expect_reconstruct_rows_from_dht()
-			.returning(|_, _, _, _, _| Box::pin(async move { Ok(vec![]) }));
+			.returning(|_, _, _, _| Box::pin(async move { Ok(vec![]) }));
 		mock_client
-			.expect_store_encoded_data_in_db()
-			.returning(|_, _, _: &Vec<Vec<u8>>| Ok(()));
+			.expect_store_app_data_in_db()
+			.returning(|_, _, _| Ok(()));
 		process_block(mock_client, &cfg, AppId(1), &block, pp)
 			.await
 			.unwrap();
@@ -612,6 +887,7 @@ mod tests {
 			]
 			.to_vec(),
 			confidence: None,
+			span: tracing::Span::none(),
 		};
 		mock_client
 			.expect_fetch_rows_from_dht()
@@ -631,12 +907,141 @@ mod tests {
 		}
 		mock_client
 			.expect_reconstruct_rows_from_dht()
-			.returning(|_, _, _, _, _| Box::pin(async move { Ok(vec![]) }));
+			.returning(|_, _, _, _| Box::pin(async move { Ok(vec![]) }));
 		mock_client
-			.expect_store_encoded_data_in_db()
-			.returning(|_, _, _: &Vec<Vec<u8>>| Ok(()));
+			.expect_store_app_data_in_db()
+			.returning(|_, _, _| Ok(()));
 		process_block(mock_client, &cfg, AppId(1), &block, pp)
 			.await
 			.unwrap();
 	}
+
+	#[tokio::test]
+	async fn test_process_block_archival_without_rpc() {
+		let mut cfg = AppClientConfig::from(&RuntimeConfig::default());
+		cfg.disable_rpc = true;
+		cfg.archival_mode = true;
+		let dimensions: Dimensions = Dimensions::new(1, 4).unwrap();
+		let id_lens: Vec<(u32, usize)> = vec![(0, 1), (1, 3)];
+		let lookup = DataLookup::from_id_and_len_iter(id_lens.into_iter()).unwrap();
+		let block = BlockVerified {
+			header_hash: H256::zero(),
+			block_num: 42,
+			dimensions,
+			lookup,
+			commitments: vec![[0u8; config::COMMITMENT_SIZE]; 2],
+			confidence: None,
+			span: tracing::Span::none(),
+		};
+		let mut mock_client = MockAppClient::new();
+		mock_client
+			.expect_fetch_verified_from_dht()
+			.returning(|_, _, _, positions| {
+				let positions = positions.to_vec();
+				Box::pin(async move { Ok((vec![], positions)) })
+			});
+		mock_client.expect_get_kate_proof().never();
+		mock_client.expect_verify_cells().never();
+		mock_client.expect_store_app_data_in_db().never();
+		mock_client.expect_store_dht_columns_in_db().never();
+		let result = process_block_archival(mock_client, &cfg, &block).await;
+		assert!(result.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_process_block_archival_from_dht() {
+		let mut cfg = AppClientConfig::from(&RuntimeConfig::default());
+		cfg.disable_rpc = true;
+		cfg.archival_mode = true;
+		let dimensions: Dimensions = Dimensions::new(1, 16).unwrap();
+		let row = hex!("042c280403000ba3fa0ab887018000000000000000000000000000000000000004d904d1048400d43593c715fdd31c61141abd04a99fd6822c8558854ccde3009a5684e7a56da27d01a8cf58e1e9c735f93ebc7a94086aa27cfd77db173aac00803895886b8a4f49e85c68f469d570f0ed992750bf95329bb90ef56b45abcd009fedef0d9cbdd61c05a181d4013800041d0121033036343265356430346236003632353966363635666431353361613136646637343066323533373237386600613139316565393630343862663839393733343961303137353865346237610032643539663534353338393865626231643233626634353965363637613633003462313663663432326663393335336434623862623630386235393230653400353733663335663037303764333238616661343832316663656631363439660039643532653762353732356533303935643865656561356436633235333830006434658000000000000000000000000000000000000000000000000000000000346080be83f48ad1748c4ad339abdcb803368efdd1f65689619ff8c208755d0084eefcf837b61c479b3332059bc8e89b490a9d502baecaed448433d4e161710000a71cbb1a0387598e509d9fcab511022f437b0caf13591315c3f1bbf04f18009d83f014806210da6ee1d2f80cf0f9c08f1d132be042769015f6174fd2b24c00");
+		let id_lens: Vec<(u32, usize)> = vec![(0, 1), (1, 11)];
+		let lookup = DataLookup::from_id_and_len_iter(id_lens.into_iter()).unwrap();
+		let block = BlockVerified {
+			header_hash: H256::zero(),
+			block_num: 42,
+			dimensions,
+			lookup,
+			commitments: vec![[0u8; config::COMMITMENT_SIZE]; 2],
+			confidence: None,
+			span: tracing::Span::none(),
+		};
+		// Original data row is enough to reconstruct every column
+		let cells = row
+			.chunks_exact(config::CHUNK_SIZE)
+			.enumerate()
+			.map(|(col, data)| Cell {
+				position: Position {
+					row: 0,
+					col: col as u16,
+				},
+				content: [[0u8; config::COMMITMENT_SIZE].as_slice(), data]
+					.concat()
+					.try_into()
+					.unwrap(),
+			})
+			.collect::<Vec<_>>();
+		let mut mock_client = MockAppClient::new();
+		mock_client
+			.expect_fetch_verified_from_dht()
+			.returning(move |_, _, _, _| {
+				let cells = cells.clone();
+				Box::pin(async move { Ok((cells, vec![])) })
+			});
+		mock_client.expect_get_kate_proof().never();
+		mock_client.expect_verify_cells().never();
+		mock_client
+			.expect_store_app_data_in_db()
+			.returning(|_, _, _| Ok(()));
+		mock_client
+			.expect_store_dht_columns_in_db()
+			.withf(|&block_number, columns| {
+				block_number == 42 && columns == (0..16).collect::<Vec<u16>>()
+			})
+			.times(1)
+			.returning(|_, _| Ok(()));
+		let archived = process_block_archival(mock_client, &cfg, &block)
+			.await
+			.unwrap();
+		assert_eq!(archived.dht_columns, (0..16).collect::<Vec<u16>>());
+		assert!(archived
+			.data
+			.iter()
+			.any(|(app_id, data)| *app_id == AppId(1) && !data.is_empty()));
+	}
+
+	#[tokio::test]
+	async fn test_process_block_archival_threshold() {
+		let mut cfg = AppClientConfig::from(&RuntimeConfig::default());
+		cfg.archival_mode = true;
+		cfg.threshold = 1;
+		let dimensions: Dimensions = Dimensions::new(1, 4).unwrap();
+		let lookup = DataLookup::from_id_and_len_iter(vec![(0, 1), (1, 3)].into_iter()).unwrap();
+		let block = BlockVerified {
+			header_hash: H256::zero(),
+			block_num: 42,
+			dimensions,
+			lookup,
+			commitments: vec![[0u8; config::COMMITMENT_SIZE]; 2],
+			confidence: None,
+			span: tracing::Span::none(),
+		};
+		let mut mock_client = MockAppClient::new();
+		mock_client.expect_fetch_verified_from_dht().never();
+		mock_client.expect_store_dht_columns_in_db().never();
+		let result = process_block_archival(mock_client, &cfg, &block).await;
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn test_extrinsic_indices() {
+		let data = vec![b"first".to_vec(), b"second".to_vec()];
+		let hash = |extrinsic: &[u8]| -> H256 { blake2_256(extrinsic).into() };
+		let block_extrinsics = vec![H256::zero(), hash(b"second"), hash(b"first")];
+		assert_eq!(
+			extrinsic_indices(&block_extrinsics, &data),
+			Some(vec![2, 1])
+		);
+		assert_eq!(extrinsic_indices(&block_extrinsics[..2], &data), None);
+	}
 }
@@ -1,12 +1,19 @@
 #![doc = include_str!("../../README.md")]
 
-use std::{fs, path::Path};
+use std::{
+	fs::{self, File},
+	io::{self, BufRead, BufWriter, IsTerminal, Write},
+	path::Path,
+};
 
 use anyhow::{anyhow, Context, Result};
 
 use avail_light::{
-	light_client_commons::run,
-	types::{CliOpts, RuntimeConfig},
+	api::v2::export,
+	data::RocksDB,
+	keystore::{self, Keystore},
+	light_client_commons::{init_db, run},
+	types::{CliOpts, Command, KeysCommand, RuntimeConfig},
 };
 use clap::Parser;
 use tokio::sync::mpsc::channel;
@@ -18,7 +25,28 @@ pub async fn main() -> Result<()> {
 	let opts = CliOpts::parse();
 
 	let mut cfg: RuntimeConfig = RuntimeConfig::default();
-	cfg.load_runtime_config(&opts)?;
+	let sources = cfg.load_runtime_config(&opts)?;
+
+	match &opts.command {
+		Some(Command::ExportAppData {
+			app_id,
+			from,
+			to,
+			output,
+		}) => {
+			let app_id = app_id
+				.or(cfg.app_id)
+				.context("AppID is required for application data export")?;
+			return export_app_data(&cfg.avail_path, app_id, *from, *to, output.clone());
+		},
+		Some(Command::Config) => {
+			let effective_config = cfg.effective_config(&sources)?;
+			println!("{}", serde_json::to_string_pretty(&effective_config)?);
+			return Ok(());
+		},
+		Some(Command::Keys { command }) => return keys(&cfg.keystore_path, command),
+		None => (),
+	}
 
 	if opts.clean && Path::new(&cfg.avail_path).exists() {
 		info!("Cleaning up local state directory");
@@ -40,3 +68,94 @@ pub async fn main() -> Result<()> {
 	// to log terminating condition before sending message to this channel
 	Err(error)
 }
+
+fn export_app_data(
+	avail_path: &str,
+	app_id: u32,
+	from: u32,
+	to: u32,
+	output: Option<String>,
+) -> Result<()> {
+	if from > to {
+		return Err(anyhow!("Range start must not be greater than range end"));
+	}
+
+	let db = init_db(avail_path, true).context("Failed to open local database")?;
+
+	let mut writer: Box<dyn Write> = match output {
+		Some(path) => Box::new(BufWriter::new(
+			File::create(&path).with_context(|| format!("Failed to create {path}"))?,
+		)),
+		None => Box::new(BufWriter::new(io::stdout().lock())),
+	};
+
+	for response in export::app_data(RocksDB(db), app_id, from, to) {
+		writer.write_all(&export::ndjson_line(&response?)?)?;
+	}
+
+	writer.flush().context("Failed to write exported data")
+}
+
+/// Reads keystore password from the environment, or prompts for it
+fn password() -> Result<String> {
+	keystore::password_from_env()
+		.or_else(|_| rpassword::prompt_password("Keystore password: "))
+		.context("Failed to read keystore password")
+}
+
+/// Reads keystore password from the environment, or prompts for it twice
+fn new_password() -> Result<String> {
+	if let Ok(password) = keystore::password_from_env() {
+		return Ok(password);
+	}
+	let password = password()?;
+	let confirmation = rpassword::prompt_password("Repeat keystore password: ")
+		.context("Failed to read keystore password")?;
+	if password != confirmation {
+		return Err(anyhow!("Passwords do not match"));
+	}
+	Ok(password)
+}
+
+/// Prompts for the secret key, or reads it from the standard input if it is not a terminal
+fn read_secret() -> Result<String> {
+	let secret = if io::stdin().is_terminal() {
+		rpassword::prompt_password("Secret key: ")?
+	} else {
+		let mut secret = String::new();
+		io::stdin().lock().read_line(&mut secret)?;
+		secret
+	};
+	let secret = secret.trim().to_string();
+	if secret.is_empty() {
+		return Err(anyhow!("Secret key is empty"));
+	}
+	Ok(secret)
+}
+
+fn keys(keystore_path: &str, command: &KeysCommand) -> Result<()> {
+	let keystore = Keystore::new(keystore_path);
+
+	match command {
+		KeysCommand::Generate { name, kind } => {
+			let key = keystore.generate(name, *kind, &new_password()?)?;
+			println!("Generated {} key {}: {}", key.kind, key.name, key.public);
+		},
+		KeysCommand::Import { name, kind } => {
+			let secret = read_secret().context("Failed to read secret key")?;
+			let key = keystore.import(name, *kind, &secret, &new_password()?)?;
+			println!("Imported {} key {}: {}", key.kind, key.name, key.public);
+		},
+		KeysCommand::Export { name } => {
+			let (_, secret) = keystore.export(name, &password()?)?;
+			println!("{}", *secret);
+		},
+		KeysCommand::List => {
+			for key in keystore.list()? {
+				println!("{}\t{}\t{}", key.name, key.kind, key.public);
+			}
+		},
+	}
+
+	Ok(())
+}
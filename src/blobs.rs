@@ -0,0 +1,428 @@
+//! Submission of the data larger than the maximum data transaction size (blobs), and its reassembly.
+//!
+//! Blob is split into chunks, each submitted as a separate data transaction, followed by the manifest transaction.
+//! Manifest contains the blob size and hash, and hashes of its chunks in order.
+//! Chunk transactions are prefixed, so only the blob chunks are indexed by the app client.
+//! Pending manifest waits for its first missing chunk, and is checked again only once that chunk is verified.
+//! Once the manifest and all of its chunks are verified, blob is reassembled and stored under the manifest hash.
+//! Manifests of blobs which are not reassembled within [`BLOB_EXPIRY_BLOCKS`] are removed.
+
+use anyhow::{anyhow, Context, Result};
+use avail_subxt::utils::H256;
+use codec::{Decode, Encode};
+use kate_recovery::com::AppData;
+use rocksdb::DB;
+use sp_core::blake2_256;
+use std::{collections::BTreeSet, sync::Arc};
+use tracing::warn;
+
+use crate::{
+	api::v2::types::DataTransaction,
+	data::{
+		get_blob_chunk_location_from_db, get_blob_manifest_from_db, get_decoded_data_from_db,
+		get_expired_blob_manifests_from_db, is_blob_in_db, remove_blob_manifest_from_db,
+		store_blob_chunk_in_db, store_blob_in_db, store_blob_manifest_in_db,
+		store_blob_waiting_in_db, take_blob_waiting_from_db,
+	},
+	types::TransactionLocation,
+};
+
+/// Prefix of the data transactions containing blob manifest
+const MANIFEST_PREFIX: &[u8] = b"avail-blob-manifest:v1:";
+/// Prefix of the data transactions containing blob chunk
+const CHUNK_PREFIX: &[u8] = b"avail-blob-chunk:v1:";
+/// Maximum number of chunks in the blob, manifests with more chunks are ignored
+pub const MAX_BLOB_CHUNKS: usize = 1024;
+/// Number of blocks after which manifest of the blob which is not reassembled is removed
+pub const BLOB_EXPIRY_BLOCKS: u32 = 1800;
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct BlobManifest {
+	/// Blob size in bytes
+	pub size: u64,
+	/// Blake2 256 hash of the blob
+	pub hash: H256,
+	/// Blake2 256 hashes of the blob chunks, in order
+	pub chunks: Vec<H256>,
+}
+
+impl BlobManifest {
+	/// Encodes manifest as the data transaction payload
+	pub fn to_data(&self) -> Vec<u8> {
+		[MANIFEST_PREFIX, &self.encode()].concat()
+	}
+
+	/// Decodes manifest from the data transaction payload, if payload is a manifest
+	pub fn from_data(data: &[u8]) -> Option<Self> {
+		let mut encoded = data.strip_prefix(MANIFEST_PREFIX)?;
+		let manifest = Self::decode(&mut encoded).ok()?;
+		encoded.is_empty().then_some(manifest)
+	}
+
+	/// Hash of the manifest data, used to reference the reassembled blob
+	pub fn manifest_hash(&self) -> H256 {
+		blake2_256(&self.to_data()).into()
+	}
+}
+
+/// Blob reassembled from the verified chunks
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct Blob {
+	pub app_id: u32,
+	/// Block in which the manifest is included
+	pub block_number: u32,
+	pub data: Vec<u8>,
+}
+
+/// Encodes blob chunk as the data transaction payload
+pub fn chunk_to_data(chunk: &[u8]) -> Vec<u8> {
+	[CHUNK_PREFIX, chunk].concat()
+}
+
+/// Decodes blob chunk from the data transaction payload, if payload is a chunk
+fn chunk_from_data(data: &[u8]) -> Option<&[u8]> {
+	data.strip_prefix(CHUNK_PREFIX)
+}
+
+/// Splits data into chunks whose transaction payloads are at most `chunk_size` bytes, and creates the blob manifest
+pub fn split(data: &[u8], chunk_size: usize) -> Result<(BlobManifest, Vec<&[u8]>)> {
+	let chunk_size = chunk_size.saturating_sub(CHUNK_PREFIX.len()).max(1);
+	let chunks = data.chunks(chunk_size).collect::<Vec<_>>();
+	if chunks.len() > MAX_BLOB_CHUNKS {
+		return Err(anyhow!(
+			"Blob is split into {} chunks, maximum is {MAX_BLOB_CHUNKS}",
+			chunks.len()
+		));
+	}
+	let manifest = BlobManifest {
+		size: data.len() as u64,
+		hash: blake2_256(data).into(),
+		chunks: chunks
+			.iter()
+			.map(|chunk| blake2_256(chunk).into())
+			.collect(),
+	};
+	Ok((manifest, chunks))
+}
+
+fn chunk_data(db: Arc<DB>, location: TransactionLocation) -> Result<Option<Vec<u8>>> {
+	let TransactionLocation {
+		app_id,
+		block_number,
+		index,
+	} = location;
+	let Some(app_data) = get_decoded_data_from_db::<AppData>(db, app_id, block_number)? else {
+		return Ok(None);
+	};
+	let Some(extrinsic) = app_data.get(index as usize) else {
+		return Ok(None);
+	};
+	let transaction = DataTransaction::new(None, extrinsic.clone())?;
+	Ok(transaction
+		.data()
+		.and_then(chunk_from_data)
+		.map(<[u8]>::to_vec))
+}
+
+/// Returns hash of the first blob chunk which is not verified yet
+fn missing_chunk(db: Arc<DB>, app_id: u32, manifest: &BlobManifest) -> Result<Option<H256>> {
+	for hash in &manifest.chunks {
+		if get_blob_chunk_location_from_db(db.clone(), app_id, *hash)?.is_none() {
+			return Ok(Some(*hash));
+		}
+	}
+	Ok(None)
+}
+
+/// Reassembles blob from the verified chunks, or returns `None` if some of the chunks data is not found
+fn reassemble_blob(db: Arc<DB>, app_id: u32, manifest: &BlobManifest) -> Result<Option<Vec<u8>>> {
+	let mut blob = vec![];
+	for hash in &manifest.chunks {
+		let Some(location) = get_blob_chunk_location_from_db(db.clone(), app_id, *hash)? else {
+			return Ok(None);
+		};
+		let Some(chunk) = chunk_data(db.clone(), location)? else {
+			return Ok(None);
+		};
+		blob.extend(chunk);
+	}
+	Ok(Some(blob))
+}
+
+/// Indexes blob chunks and manifests of the verified application data, and reassembles blobs
+/// whose chunks are all verified. Only new manifests and manifests waiting for the chunks
+/// verified in this block are checked. Returns manifest hashes of the reassembled blobs.
+pub fn reassemble(
+	db: Arc<DB>,
+	app_id: u32,
+	block_number: u32,
+	data: &AppData,
+) -> Result<Vec<H256>> {
+	let mut candidates = BTreeSet::new();
+	for (index, extrinsic) in data.iter().enumerate() {
+		let index = index as u32;
+		let Ok(transaction) = DataTransaction::new(None, extrinsic.clone()) else {
+			continue;
+		};
+		let Some(data) = transaction.data() else {
+			continue;
+		};
+
+		if let Some(chunk) = chunk_from_data(data) {
+			let hash = blake2_256(chunk).into();
+			let location = TransactionLocation {
+				app_id,
+				block_number,
+				index,
+			};
+			store_blob_chunk_in_db(db.clone(), hash, &location)?;
+			candidates.extend(take_blob_waiting_from_db(db.clone(), app_id, hash)?);
+			continue;
+		}
+
+		let Some(manifest) = BlobManifest::from_data(data) else {
+			continue;
+		};
+		let manifest_hash = manifest.manifest_hash();
+		if manifest.chunks.len() > MAX_BLOB_CHUNKS {
+			warn!(%manifest_hash, "Blob manifest has too many chunks");
+			continue;
+		}
+		if is_blob_in_db(db.clone(), manifest_hash)?
+			|| get_blob_manifest_from_db(db.clone(), app_id, manifest_hash)?.is_some()
+		{
+			continue;
+		}
+		store_blob_manifest_in_db(db.clone(), app_id, block_number, &manifest)?;
+		candidates.insert(manifest_hash);
+	}
+
+	let mut reassembled = vec![];
+	for manifest_hash in candidates {
+		// Manifest is already reassembled, removed or expired
+		let Some((manifest_block_number, manifest)) =
+			get_blob_manifest_from_db(db.clone(), app_id, manifest_hash)?
+		else {
+			continue;
+		};
+
+		if let Some(chunk_hash) = missing_chunk(db.clone(), app_id, &manifest)? {
+			store_blob_waiting_in_db(db.clone(), app_id, chunk_hash, manifest_hash)?;
+			continue;
+		}
+
+		let Some(data) =
+			reassemble_blob(db.clone(), app_id, &manifest).context("Failed to reassemble blob")?
+		else {
+			warn!(%manifest_hash, "Blob chunks data is not found");
+			continue;
+		};
+
+		if data.len() as u64 != manifest.size || H256::from(blake2_256(&data)) != manifest.hash {
+			warn!(%manifest_hash, "Reassembled blob doesn't match the manifest");
+			remove_blob_manifest_from_db(db.clone(), app_id, manifest_block_number, manifest_hash)?;
+			continue;
+		}
+
+		let blob = Blob {
+			app_id,
+			block_number: manifest_block_number,
+			data,
+		};
+		store_blob_in_db(db.clone(), manifest_hash, &blob)?;
+		reassembled.push(manifest_hash);
+	}
+
+	let expired_before = block_number.saturating_sub(BLOB_EXPIRY_BLOCKS);
+	for (manifest_block_number, manifest_hash) in
+		get_expired_blob_manifests_from_db(db.clone(), app_id, expired_before)?
+	{
+		warn!(%manifest_hash, "Blob is not reassembled in time, removing manifest");
+		remove_blob_manifest_from_db(db.clone(), app_id, manifest_block_number, manifest_hash)?;
+	}
+
+	Ok(reassembled)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		chunk_to_data, reassemble, split, BlobManifest, BLOB_EXPIRY_BLOCKS, CHUNK_PREFIX,
+		MAX_BLOB_CHUNKS,
+	};
+	use crate::{
+		data::{get_blob_from_db, get_blob_manifest_from_db, store_app_data_in_db},
+		light_client_commons::init_db,
+	};
+	use avail_core::AppId;
+	use avail_subxt::{
+		api::runtime_types::{
+			bounded_collections::bounded_vec::BoundedVec, da_control::pallet::Call,
+			da_runtime::RuntimeCall,
+		},
+		primitives::AppUncheckedExtrinsic,
+		utils::H256,
+	};
+	use codec::Encode;
+	use rocksdb::DB;
+	use sp_core::blake2_256;
+	use std::sync::Arc;
+	use uuid::Uuid;
+
+	const APP_ID: u32 = 1;
+
+	fn extrinsic(data: Vec<u8>) -> Vec<u8> {
+		AppUncheckedExtrinsic {
+			signature: None,
+			function: RuntimeCall::DataAvailability(Call::submit_data {
+				data: BoundedVec(data),
+			}),
+		}
+		.encode()
+	}
+
+	fn reassemble_block(db: Arc<DB>, block_number: u32, transactions: Vec<Vec<u8>>) -> Vec<H256> {
+		let data = transactions.into_iter().map(extrinsic).collect::<Vec<_>>();
+		store_app_data_in_db(db.clone(), AppId(APP_ID), block_number, &data).unwrap();
+		reassemble(db, APP_ID, block_number, &data).unwrap()
+	}
+
+	fn with_db(test: impl FnOnce(Arc<DB>)) {
+		let path = std::env::temp_dir().join(format!("avail-light-blobs-{}", Uuid::new_v4()));
+		let db = init_db(path.to_str().unwrap(), false).unwrap();
+		test(db);
+		std::fs::remove_dir_all(path).unwrap();
+	}
+
+	fn blob() -> (Vec<u8>, BlobManifest, Vec<Vec<u8>>) {
+		let data = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+		let (manifest, chunks) = split(&data, 300 + CHUNK_PREFIX.len()).unwrap();
+		let chunks = chunks.into_iter().map(chunk_to_data).collect();
+		(data, manifest, chunks)
+	}
+
+	#[test]
+	fn split_and_manifest() {
+		let data = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+		let (manifest, chunks) = split(&data, 300 + CHUNK_PREFIX.len()).unwrap();
+
+		assert_eq!(chunks.len(), 4);
+		assert_eq!(chunks.last().unwrap().len(), 100);
+		assert_eq!(chunks.concat(), data);
+		assert_eq!(chunk_to_data(chunks[0]).len(), 300 + CHUNK_PREFIX.len());
+		assert_eq!(manifest.size, 1000);
+		assert_eq!(manifest.hash, H256::from(blake2_256(&data)));
+		assert_eq!(manifest.chunks[1], H256::from(blake2_256(chunks[1])));
+
+		let manifest_data = manifest.to_data();
+		assert_eq!(
+			BlobManifest::from_data(&manifest_data),
+			Some(manifest.clone())
+		);
+		assert_eq!(
+			manifest.manifest_hash(),
+			H256::from(blake2_256(&manifest_data))
+		);
+		assert_eq!(BlobManifest::from_data(&data), None);
+		assert_eq!(
+			BlobManifest::from_data(&[manifest_data.as_slice(), &[0]].concat()),
+			None
+		);
+	}
+
+	#[test]
+	fn split_too_many_chunks() {
+		let data = vec![0u8; MAX_BLOB_CHUNKS + 1];
+		assert!(split(&data, CHUNK_PREFIX.len() + 1).is_err());
+		assert!(split(&data[1..], CHUNK_PREFIX.len() + 1).is_ok());
+	}
+
+	#[test]
+	fn reassemble_complete() {
+		with_db(|db| {
+			let (data, manifest, mut transactions) = blob();
+			transactions.push(manifest.to_data());
+
+			let manifest_hash = manifest.manifest_hash();
+			assert_eq!(
+				reassemble_block(db.clone(), 1, transactions),
+				vec![manifest_hash]
+			);
+
+			let blob = get_blob_from_db(db.clone(), manifest_hash)
+				.unwrap()
+				.unwrap();
+			assert_eq!(blob.app_id, APP_ID);
+			assert_eq!(blob.block_number, 1);
+			assert_eq!(blob.data, data);
+			assert!(get_blob_manifest_from_db(db, APP_ID, manifest_hash)
+				.unwrap()
+				.is_none());
+		});
+	}
+
+	#[test]
+	fn reassemble_out_of_order_chunks() {
+		with_db(|db| {
+			let (data, manifest, chunks) = blob();
+			let manifest_hash = manifest.manifest_hash();
+
+			let transactions = vec![chunks[3].clone(), manifest.to_data(), chunks[1].clone()];
+			assert!(reassemble_block(db.clone(), 1, transactions).is_empty());
+			assert!(reassemble_block(db.clone(), 2, vec![chunks[2].clone()]).is_empty());
+			assert_eq!(
+				reassemble_block(db.clone(), 3, vec![chunks[0].clone()]),
+				vec![manifest_hash]
+			);
+
+			let blob = get_blob_from_db(db, manifest_hash).unwrap().unwrap();
+			assert_eq!(blob.block_number, 1);
+			assert_eq!(blob.data, data);
+		});
+	}
+
+	#[test]
+	fn reassemble_missing_chunk() {
+		with_db(|db| {
+			let (_, manifest, mut transactions) = blob();
+			let manifest_hash = manifest.manifest_hash();
+			transactions[2] = manifest.to_data();
+
+			assert!(reassemble_block(db.clone(), 1, transactions).is_empty());
+			assert!(get_blob_from_db(db.clone(), manifest_hash)
+				.unwrap()
+				.is_none());
+			assert!(get_blob_manifest_from_db(db.clone(), APP_ID, manifest_hash)
+				.unwrap()
+				.is_some());
+
+			assert!(reassemble_block(db.clone(), BLOB_EXPIRY_BLOCKS + 1, vec![]).is_empty());
+			assert!(get_blob_manifest_from_db(db.clone(), APP_ID, manifest_hash)
+				.unwrap()
+				.is_some());
+
+			assert!(reassemble_block(db.clone(), BLOB_EXPIRY_BLOCKS + 2, vec![]).is_empty());
+			assert!(get_blob_manifest_from_db(db, APP_ID, manifest_hash)
+				.unwrap()
+				.is_none());
+		});
+	}
+
+	#[test]
+	fn reassemble_hash_mismatch() {
+		with_db(|db| {
+			let (_, mut manifest, mut transactions) = blob();
+			manifest.hash = H256::zero();
+			let manifest_hash = manifest.manifest_hash();
+			transactions.push(manifest.to_data());
+
+			assert!(reassemble_block(db.clone(), 1, transactions).is_empty());
+			assert!(get_blob_from_db(db.clone(), manifest_hash)
+				.unwrap()
+				.is_none());
+			assert!(get_blob_manifest_from_db(db, APP_ID, manifest_hash)
+				.unwrap()
+				.is_none());
+		});
+	}
+}
@@ -0,0 +1,100 @@
+//! In-memory cache of the cells fetched by the light client.
+//!
+//! Cells of the latest processed blocks are kept, so they can be served to the third parties for verification
+//! without fetching them again from the DHT or RPC. Cells of the oldest blocks are evicted first.
+//! Only verified cells are cached, except for the fat client partition cells, which are fetched from the node RPC.
+
+use kate_recovery::{data::Cell, matrix::Position};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, Mutex},
+};
+
+#[derive(Clone)]
+pub struct CellCache {
+	max_blocks: usize,
+	blocks: Arc<Mutex<BTreeMap<u32, HashMap<(u32, u16), Cell>>>>,
+}
+
+impl CellCache {
+	/// Creates cache which keeps cells of up to `max_blocks` latest blocks
+	pub fn new(max_blocks: usize) -> Self {
+		CellCache {
+			max_blocks,
+			blocks: Arc::new(Mutex::new(BTreeMap::new())),
+		}
+	}
+
+	pub fn insert(&self, block_number: u32, cells: &[Cell]) {
+		if self.max_blocks == 0 || cells.is_empty() {
+			return;
+		}
+		let mut blocks = self.blocks.lock().expect("Lock should be acquired");
+		let block = blocks.entry(block_number).or_default();
+		for cell in cells {
+			block.insert((cell.position.row, cell.position.col), cell.clone());
+		}
+		while blocks.len() > self.max_blocks {
+			blocks.pop_first();
+		}
+	}
+
+	/// Returns cached cells, and positions of the cells which are not in the cache
+	pub fn get(&self, block_number: u32, positions: &[Position]) -> (Vec<Cell>, Vec<Position>) {
+		let blocks = self.blocks.lock().expect("Lock should be acquired");
+		let Some(block) = blocks.get(&block_number) else {
+			return (vec![], positions.to_vec());
+		};
+		let mut cells = vec![];
+		let mut missing = vec![];
+		for &position in positions {
+			match block.get(&(position.row, position.col)) {
+				Some(cell) => cells.push(cell.clone()),
+				None => missing.push(position),
+			}
+		}
+		(cells, missing)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::CellCache;
+	use kate_recovery::{data::Cell, matrix::Position};
+
+	fn cell(row: u32, col: u16) -> Cell {
+		Cell {
+			position: Position { row, col },
+			content: [0u8; 80],
+		}
+	}
+
+	#[test]
+	fn cached_cells_are_returned() {
+		let cache = CellCache::new(2);
+		cache.insert(1, &[cell(0, 0), cell(0, 1)]);
+
+		let positions = [Position { row: 0, col: 1 }, Position { row: 1, col: 1 }];
+		let (cells, missing) = cache.get(1, &positions);
+		assert_eq!(cells.len(), 1);
+		assert_eq!(cells[0].position, Position { row: 0, col: 1 });
+		assert_eq!(missing, vec![Position { row: 1, col: 1 }]);
+
+		let (cells, missing) = cache.get(2, &positions);
+		assert!(cells.is_empty());
+		assert_eq!(missing.len(), 2);
+	}
+
+	#[test]
+	fn oldest_blocks_are_evicted() {
+		let cache = CellCache::new(2);
+		cache.insert(3, &[cell(0, 0)]);
+		cache.insert(1, &[cell(0, 0)]);
+		cache.insert(2, &[cell(0, 0)]);
+
+		let positions = [Position { row: 0, col: 0 }];
+		assert!(cache.get(1, &positions).0.is_empty());
+		assert_eq!(cache.get(2, &positions).0.len(), 1);
+		assert_eq!(cache.get(3, &positions).0.len(), 1);
+	}
+}
@@ -1,6 +1,7 @@
 //! Column family names and other constants.
 
 use crate::network::rpc::ExpectedVersion;
+use std::time::Duration;
 
 /// Column family for confidence factor
 pub const CONFIDENCE_FACTOR_CF: &str = "avail_light_confidence_factor_cf";
@@ -11,6 +12,15 @@ pub const BLOCK_HEADER_CF: &str = "avail_light_block_header_cf";
 /// Column family for app data
 pub const APP_DATA_CF: &str = "avail_light_app_data_cf";
 
+/// Column family for application transaction indices
+pub const TRANSACTIONS_CF: &str = "avail_light_transactions_cf";
+
+/// Column family for submitted transactions
+pub const SUBMISSIONS_CF: &str = "avail_light_submissions_cf";
+
+/// Column family for blob chunk indices, pending manifests and reassembled blobs
+pub const BLOBS_CF: &str = "avail_light_blobs_cf";
+
 /// Column family for state
 pub const STATE_CF: &str = "avail_light_state_cf";
 
@@ -60,3 +70,6 @@ pub const EXPECTED_NETWORK_VERSION: ExpectedVersion = ExpectedVersion {
 	version: "1.8",
 	spec_name: "data-avail",
 };
+
+/// Expected time between two blocks on the network
+pub const EXPECTED_BLOCK_TIME: Duration = Duration::from_secs(20);
@@ -3,6 +3,7 @@ use crate::{
 		p2p::Client,
 		rpc::{self, Event},
 	},
+	sampling::SamplingStrategy,
 	telemetry::{MetricValue, Metrics},
 	types::{self, Delay},
 };
@@ -13,7 +14,7 @@ use std::{
 	time::{Duration, Instant},
 };
 use tokio::sync::broadcast;
-use tracing::{error, info};
+use tracing::{error, info, info_span, Instrument};
 
 const ENTIRE_BLOCK: Partition = Partition {
 	number: 1,
@@ -37,6 +38,8 @@ pub struct CrawlConfig {
 	pub crawl_block_delay: u64,
 	/// Crawl block mode. Available modes are "cells", "rows" and "both" (default: "cells")
 	pub crawl_block_mode: CrawlMode,
+	/// Number of cells to crawl per block, chosen by the configured sampling strategy. If not set, all block cells are crawled (default: None)
+	pub crawl_block_sample_size: Option<u32>,
 }
 
 impl Default for CrawlConfig {
@@ -45,6 +48,7 @@ impl Default for CrawlConfig {
 			crawl_block: false,
 			crawl_block_delay: 20,
 			crawl_block_mode: CrawlMode::Cells,
+			crawl_block_sample_size: None,
 		}
 	}
 }
@@ -55,6 +59,8 @@ pub async fn run(
 	delay: u64,
 	metrics: Arc<impl Metrics>,
 	mode: CrawlMode,
+	sampling: Arc<dyn SamplingStrategy>,
+	sample_size: Option<u32>,
 ) {
 	info!("Starting crawl client...");
 
@@ -63,9 +69,10 @@ pub async fn run(
 	while let Ok(rpc::Event::HeaderUpdate {
 		header,
 		received_at,
+		span,
 	}) = message_rx.recv().await
 	{
-		let block = match types::BlockVerified::try_from((header, None)) {
+		let block = match span.in_scope(|| types::BlockVerified::try_from((header, None))) {
 			Ok(block) => block,
 			Err(error) => {
 				error!("Header is not valid: {error}");
@@ -75,12 +82,7 @@ pub async fn run(
 
 		if let Some(seconds) = delay.sleep_duration(received_at) {
 			info!("Sleeping for {seconds:?} seconds");
-			if let Err(error) = metrics
-				.record(MetricValue::CrawlBlockDelay(seconds.as_secs_f64()))
-				.await
-			{
-				error!("Cannot record crawl block delay: {}", error);
-			}
+			metrics.record(MetricValue::CrawlBlockDelay(seconds.as_secs_f64()));
 			tokio::time::sleep(seconds).await;
 		}
 		let block_number = block.block_num;
@@ -89,14 +91,20 @@ pub async fn run(
 		let start = Instant::now();
 
 		if matches!(mode, CrawlMode::Cells | CrawlMode::Both) {
-			let positions = block
-				.dimensions
-				.iter_extended_partition_positions(&ENTIRE_BLOCK)
-				.collect::<Vec<_>>();
+			let positions = match sample_size {
+				Some(cell_count) => {
+					sampling.positions(block.header_hash, block.dimensions, cell_count)
+				},
+				None => block
+					.dimensions
+					.iter_extended_partition_positions(&ENTIRE_BLOCK)
+					.collect::<Vec<_>>(),
+			};
 
 			let total = positions.len();
 			let fetched = network_client
 				.fetch_cells_from_dht(block_number, &positions)
+				.instrument(info_span!(parent: &block.span, "crawl_cells"))
 				.await
 				.0
 				.len();
@@ -106,9 +114,7 @@ pub async fn run(
 				block_number,
 				success_rate, total, fetched, "Fetched block cells",
 			);
-			let _ = metrics
-				.record(MetricValue::CrawlCellsSuccessRate(success_rate))
-				.await;
+			metrics.record(MetricValue::CrawlCellsSuccessRate(success_rate));
 		}
 
 		if matches!(mode, CrawlMode::Cells | CrawlMode::Both) {
@@ -117,6 +123,7 @@ pub async fn run(
 			let total = rows.len();
 			let fetched = network_client
 				.fetch_rows_from_dht(block_number, dimensions, &rows)
+				.instrument(info_span!(parent: &block.span, "crawl_rows"))
 				.await
 				.iter()
 				.step_by(2)
@@ -128,9 +135,7 @@ pub async fn run(
 				block_number,
 				success_rate, total, fetched, "Fetched block rows"
 			);
-			let _ = metrics
-				.record(MetricValue::CrawlRowsSuccessRate(success_rate))
-				.await;
+			metrics.record(MetricValue::CrawlRowsSuccessRate(success_rate));
 		}
 
 		let elapsed = start.elapsed();
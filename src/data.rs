@@ -2,28 +2,38 @@
 
 use anyhow::{anyhow, Context, Result};
 use avail_core::AppId;
-use avail_subxt::{primitives::Header as DaHeader, utils::H256};
+use avail_subxt::{
+	primitives::{AppUncheckedExtrinsic, Header as DaHeader},
+	utils::H256,
+};
 use codec::{Decode, Encode};
 use kate_recovery::com::AppData;
-use rocksdb::{ColumnFamilyDescriptor, Options, DB};
+use rocksdb::{ColumnFamilyDescriptor, Direction, IteratorMode, Options, WriteBatch, DB};
+use sp_core::blake2_256;
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::{
-	api::v2::types::PublishMessage,
+	api::v2::types::{PublishMessage, Submission},
+	blobs::{Blob, BlobManifest},
 	consts::{
-		APP_DATA_CF, BLOCKS_LIST_CF, BLOCKS_LIST_KEY, BLOCKS_LIST_LENGTH_CF,
+		APP_DATA_CF, BLOBS_CF, BLOCKS_LIST_CF, BLOCKS_LIST_KEY, BLOCKS_LIST_LENGTH_CF,
 		BLOCKS_LIST_LENGTH_KEY, BLOCK_HEADER_CF, CONFIDENCE_ACHIEVED_BLOCKS_CF,
 		CONFIDENCE_ACHIEVED_BLOCKS_KEY, CONFIDENCE_ACHIEVED_MESSAGE_CF,
 		CONFIDENCE_ACHIEVED_MESSAGE_KEY, CONFIDENCE_FACTOR_CF, DATA_VERIFIED_MESSAGE_CF,
 		DATA_VERIFIED_MESSAGE_KEY, HEADER_VERIFIED_MESSAGE_CF, HEADER_VERIFIED_MESSAGE_KEY,
-		LATEST_BLOCK_CF, LATEST_BLOCK_KEY, STATE_CF,
+		LATEST_BLOCK_CF, LATEST_BLOCK_KEY, STATE_CF, SUBMISSIONS_CF, TRANSACTIONS_CF,
 	},
-	types::{FinalitySyncCheckpoint, PublishMessageList},
+	types::{FinalitySyncCheckpoint, PublishMessageList, TransactionLocation},
+	utils::signer_address,
 };
 
 const LAST_FULL_NODE_WS_KEY: &str = "last_full_node_ws";
 const GENESIS_HASH_KEY: &str = "genesis_hash";
 const FINALITY_SYNC_CHECKPOINT_KEY: &str = "finality_sync_checkpoint";
+const SAMPLING_SECRET_KEY: &str = "sampling_secret";
+const HEALTH_CHECK_KEY: &str = "health_check";
+const DHT_COLUMNS_PREFIX: &str = "dht_columns";
 
 fn store_data_in_db(db: Arc<DB>, app_id: AppId, block_number: u32, data: &[u8]) -> Result<()> {
 	let key = format!("{}:{block_number}", app_id.0);
@@ -56,6 +66,15 @@ pub fn init_db(path: &str) -> Result<Arc<DB>> {
 	let mut app_data_cf_opts = Options::default();
 	app_data_cf_opts.set_max_write_buffer_number(16);
 
+	let mut transactions_cf_opts = Options::default();
+	transactions_cf_opts.set_max_write_buffer_number(16);
+
+	let mut submissions_cf_opts = Options::default();
+	submissions_cf_opts.set_max_write_buffer_number(16);
+
+	let mut blobs_cf_opts = Options::default();
+	blobs_cf_opts.set_max_write_buffer_number(16);
+
 	let mut state_cf_opts = Options::default();
 	state_cf_opts.set_max_write_buffer_number(16);
 
@@ -63,6 +82,9 @@ pub fn init_db(path: &str) -> Result<Arc<DB>> {
 		ColumnFamilyDescriptor::new(CONFIDENCE_FACTOR_CF, confidence_cf_opts),
 		ColumnFamilyDescriptor::new(BLOCK_HEADER_CF, block_header_cf_opts),
 		ColumnFamilyDescriptor::new(APP_DATA_CF, app_data_cf_opts),
+		ColumnFamilyDescriptor::new(TRANSACTIONS_CF, transactions_cf_opts),
+		ColumnFamilyDescriptor::new(SUBMISSIONS_CF, submissions_cf_opts),
+		ColumnFamilyDescriptor::new(BLOBS_CF, blobs_cf_opts),
 		ColumnFamilyDescriptor::new(STATE_CF, state_cf_opts),
 	];
 
@@ -84,6 +106,444 @@ pub fn store_encoded_data_in_db<T: Encode>(
 	store_data_in_db(db, app_id, block_number, &data.encode())
 }
 
+fn transaction_hash_key(hash: &H256) -> Vec<u8> {
+	[b"hash:", hash.as_bytes()].concat()
+}
+
+fn signer_transactions_key(app_id: u32, signer: &str) -> Vec<u8> {
+	format!("signer:{app_id}:{signer}:").into_bytes()
+}
+
+fn extrinsic_indices_key(app_id: u32, block_number: u32) -> Vec<u8> {
+	format!("indices:{app_id}:{block_number}").into_bytes()
+}
+
+/// Stores app data into database under the `app_id:block_number` key,
+/// and indexes its transactions by transaction hash and signer
+pub fn store_app_data_in_db(
+	db: Arc<DB>,
+	app_id: AppId,
+	block_number: u32,
+	data: &AppData,
+) -> Result<()> {
+	let app_data_cf = db
+		.cf_handle(APP_DATA_CF)
+		.context("Failed to get cf handle")?;
+	let transactions_cf = db
+		.cf_handle(TRANSACTIONS_CF)
+		.context("Failed to get cf handle")?;
+
+	let mut batch = WriteBatch::default();
+	let key = format!("{}:{block_number}", app_id.0);
+	batch.put_cf(&app_data_cf, key.as_bytes(), data.encode());
+
+	for (index, extrinsic) in data.iter().enumerate() {
+		let index = index as u32;
+		let location = TransactionLocation {
+			app_id: app_id.0,
+			block_number,
+			index,
+		};
+		let hash: H256 = blake2_256(extrinsic).into();
+		batch.put_cf(
+			&transactions_cf,
+			transaction_hash_key(&hash),
+			location.encode(),
+		);
+
+		// Unsigned transactions are indexed only by hash
+		let Ok(decoded) = AppUncheckedExtrinsic::decode(&mut &extrinsic[..]) else {
+			continue;
+		};
+		let Some((address, _, _)) = &decoded.signature else {
+			continue;
+		};
+		let mut key = signer_transactions_key(app_id.0, &signer_address(address));
+		key.extend(block_number.to_be_bytes());
+		key.extend(index.to_be_bytes());
+		batch.put_cf(&transactions_cf, key, hash.as_bytes());
+	}
+
+	db.write(batch).context("Failed to write application data")
+}
+
+/// Stores indices of the application transactions in the block, in the application data order
+pub fn store_extrinsic_indices_in_db(
+	db: Arc<DB>,
+	app_id: AppId,
+	block_number: u32,
+	indices: &[u32],
+) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(TRANSACTIONS_CF)
+		.context("Failed to get cf handle")?;
+
+	db.put_cf(
+		&cf_handle,
+		extrinsic_indices_key(app_id.0, block_number),
+		indices.encode(),
+	)
+	.context("Failed to write extrinsic indices")
+}
+
+/// Gets indices of the application transactions in the block, if known
+pub fn get_extrinsic_indices_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	block_number: u32,
+) -> Result<Option<Vec<u32>>> {
+	// Column family is missing in the older databases opened in the read-only mode
+	let Some(cf_handle) = db.cf_handle(TRANSACTIONS_CF) else {
+		return Ok(None);
+	};
+
+	db.get_cf(&cf_handle, extrinsic_indices_key(app_id, block_number))
+		.context("Couldn't get extrinsic indices from db")?
+		.map(|value| {
+			<Vec<u32>>::decode(&mut &value[..]).context("Failed to decode extrinsic indices")
+		})
+		.transpose()
+}
+
+/// Gets location of the application transaction with given hash
+pub fn get_transaction_location_from_db(
+	db: Arc<DB>,
+	hash: H256,
+) -> Result<Option<TransactionLocation>> {
+	let cf_handle = db
+		.cf_handle(TRANSACTIONS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.get_cf(&cf_handle, transaction_hash_key(&hash))
+		.context("Couldn't get transaction location from db")?
+		.map(|value| {
+			TransactionLocation::decode(&mut &value[..])
+				.context("Failed to decode transaction location")
+		})
+		.transpose()
+}
+
+/// Stores the latest status of the submitted transaction under its ID
+pub fn store_submission_in_db(db: Arc<DB>, submission: &Submission) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(SUBMISSIONS_CF)
+		.context("Failed to get cf handle")?;
+
+	let value = serde_json::to_vec(submission).context("Failed to serialize submission")?;
+	db.put_cf(&cf_handle, submission.id.as_bytes(), value)
+		.context("Failed to write submission")
+}
+
+/// Gets the latest statuses of all submitted transactions
+pub fn get_submissions_from_db(db: Arc<DB>) -> Result<Vec<Submission>> {
+	let cf_handle = db
+		.cf_handle(SUBMISSIONS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.iterator_cf(&cf_handle, IteratorMode::Start)
+		.map(|item| {
+			let (_, value) = item.context("Couldn't iterate submissions")?;
+			serde_json::from_slice(&value).context("Failed to decode submission")
+		})
+		.collect()
+}
+
+/// Removes submissions with the given IDs
+pub fn remove_submissions_from_db(db: Arc<DB>, ids: &[Uuid]) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(SUBMISSIONS_CF)
+		.context("Failed to get cf handle")?;
+
+	let mut batch = WriteBatch::default();
+	for id in ids {
+		batch.delete_cf(&cf_handle, id.as_bytes());
+	}
+	db.write(batch).context("Failed to remove submissions")
+}
+
+/// Gets the latest status of the submitted transaction with the given ID
+pub fn get_submission_from_db(db: Arc<DB>, id: Uuid) -> Result<Option<Submission>> {
+	let cf_handle = db
+		.cf_handle(SUBMISSIONS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.get_cf(&cf_handle, id.as_bytes())
+		.context("Couldn't get submission from db")?
+		.map(|value| serde_json::from_slice(&value).context("Failed to decode submission"))
+		.transpose()
+}
+
+fn blob_chunk_key(app_id: u32, hash: &H256) -> Vec<u8> {
+	[b"chunk:", &app_id.to_be_bytes()[..], hash.as_bytes()].concat()
+}
+
+fn blob_manifest_key(app_id: u32, hash: &H256) -> Vec<u8> {
+	[&b"manifest:"[..], &app_id.to_be_bytes(), hash.as_bytes()].concat()
+}
+
+fn blob_waiting_prefix(app_id: u32, chunk_hash: &H256) -> Vec<u8> {
+	[
+		&b"waiting:"[..],
+		&app_id.to_be_bytes(),
+		chunk_hash.as_bytes(),
+	]
+	.concat()
+}
+
+fn blob_expiry_prefix(app_id: u32) -> Vec<u8> {
+	[&b"expiry:"[..], &app_id.to_be_bytes()].concat()
+}
+
+fn blob_expiry_key(app_id: u32, block_number: u32, manifest_hash: &H256) -> Vec<u8> {
+	[
+		blob_expiry_prefix(app_id),
+		block_number.to_be_bytes().to_vec(),
+		manifest_hash.as_bytes().to_vec(),
+	]
+	.concat()
+}
+
+fn blob_key(manifest_hash: &H256) -> Vec<u8> {
+	[b"blob:", manifest_hash.as_bytes()].concat()
+}
+
+/// Indexes location of the blob chunk by the hash of its data
+pub fn store_blob_chunk_in_db(
+	db: Arc<DB>,
+	hash: H256,
+	location: &TransactionLocation,
+) -> Result<()> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	db.put_cf(
+		&cf_handle,
+		blob_chunk_key(location.app_id, &hash),
+		location.encode(),
+	)
+	.context("Failed to write blob chunk location")
+}
+
+/// Gets location of the application blob chunk with given data hash
+pub fn get_blob_chunk_location_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	hash: H256,
+) -> Result<Option<TransactionLocation>> {
+	let cf_handle = db
+		.cf_handle(BLOBS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.get_cf(&cf_handle, blob_chunk_key(app_id, &hash))
+		.context("Couldn't get blob chunk location from db")?
+		.map(|value| {
+			TransactionLocation::decode(&mut &value[..])
+				.context("Failed to decode blob chunk location")
+		})
+		.transpose()
+}
+
+/// Stores blob manifest included in the given block, until the blob is reassembled or expired
+pub fn store_blob_manifest_in_db(
+	db: Arc<DB>,
+	app_id: u32,
+	block_number: u32,
+	manifest: &BlobManifest,
+) -> Result<()> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	let manifest_hash = manifest.manifest_hash();
+	let mut batch = WriteBatch::default();
+	batch.put_cf(
+		&cf_handle,
+		blob_manifest_key(app_id, &manifest_hash),
+		(block_number, manifest).encode(),
+	);
+	batch.put_cf(
+		&cf_handle,
+		blob_expiry_key(app_id, block_number, &manifest_hash),
+		[],
+	);
+	db.write(batch).context("Failed to write blob manifest")
+}
+
+/// Gets manifest of the application blob which is not reassembled yet,
+/// with the number of the block in which it is included
+pub fn get_blob_manifest_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	manifest_hash: H256,
+) -> Result<Option<(u32, BlobManifest)>> {
+	let cf_handle = db
+		.cf_handle(BLOBS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.get_cf(&cf_handle, blob_manifest_key(app_id, &manifest_hash))
+		.context("Couldn't get blob manifest from db")?
+		.map(|value| {
+			<(u32, BlobManifest)>::decode(&mut &value[..]).context("Failed to decode blob manifest")
+		})
+		.transpose()
+}
+
+/// Removes manifest of the blob which cannot be reassembled
+pub fn remove_blob_manifest_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	block_number: u32,
+	manifest_hash: H256,
+) -> Result<()> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	let mut batch = WriteBatch::default();
+	batch.delete_cf(&cf_handle, blob_manifest_key(app_id, &manifest_hash));
+	batch.delete_cf(
+		&cf_handle,
+		blob_expiry_key(app_id, block_number, &manifest_hash),
+	);
+	db.write(batch).context("Failed to remove blob manifest")
+}
+
+/// Gets manifests included before the given block, as block numbers and manifest hashes
+pub fn get_expired_blob_manifests_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	before_block_number: u32,
+) -> Result<Vec<(u32, H256)>> {
+	let cf_handle = db
+		.cf_handle(BLOBS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	let prefix = blob_expiry_prefix(app_id);
+	let mut expired = vec![];
+	for item in db.iterator_cf(&cf_handle, IteratorMode::From(&prefix, Direction::Forward)) {
+		let (key, _) = item.context("Couldn't iterate blob manifests")?;
+		let Some(suffix) = key.strip_prefix(prefix.as_slice()) else {
+			break;
+		};
+		let (block_number, manifest_hash) = suffix.split_at(4);
+		let block_number = u32::from_be_bytes(block_number.try_into()?);
+		if block_number >= before_block_number {
+			break;
+		}
+		expired.push((block_number, H256::from_slice(manifest_hash)));
+	}
+	Ok(expired)
+}
+
+/// Marks blob manifest as waiting for the chunk with the given hash
+pub fn store_blob_waiting_in_db(
+	db: Arc<DB>,
+	app_id: u32,
+	chunk_hash: H256,
+	manifest_hash: H256,
+) -> Result<()> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	let key = [
+		blob_waiting_prefix(app_id, &chunk_hash),
+		manifest_hash.as_bytes().to_vec(),
+	]
+	.concat();
+	db.put_cf(&cf_handle, key, [])
+		.context("Failed to write waiting blob manifest")
+}
+
+/// Removes and returns hashes of the blob manifests waiting for the chunk with the given hash
+pub fn take_blob_waiting_from_db(db: Arc<DB>, app_id: u32, chunk_hash: H256) -> Result<Vec<H256>> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	let prefix = blob_waiting_prefix(app_id, &chunk_hash);
+	let mut batch = WriteBatch::default();
+	let mut manifest_hashes = vec![];
+	for item in db.iterator_cf(&cf_handle, IteratorMode::From(&prefix, Direction::Forward)) {
+		let (key, _) = item.context("Couldn't iterate waiting blob manifests")?;
+		let Some(manifest_hash) = key.strip_prefix(prefix.as_slice()) else {
+			break;
+		};
+		manifest_hashes.push(H256::from_slice(manifest_hash));
+		batch.delete_cf(&cf_handle, &key);
+	}
+	db.write(batch)
+		.context("Failed to remove waiting blob manifests")?;
+	Ok(manifest_hashes)
+}
+
+/// Stores reassembled blob under its manifest hash, and removes the manifest
+pub fn store_blob_in_db(db: Arc<DB>, manifest_hash: H256, blob: &Blob) -> Result<()> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	let mut batch = WriteBatch::default();
+	batch.put_cf(&cf_handle, blob_key(&manifest_hash), blob.encode());
+	batch.delete_cf(&cf_handle, blob_manifest_key(blob.app_id, &manifest_hash));
+	batch.delete_cf(
+		&cf_handle,
+		blob_expiry_key(blob.app_id, blob.block_number, &manifest_hash),
+	);
+	db.write(batch).context("Failed to write blob")
+}
+
+/// Checks if blob with given manifest hash is reassembled
+pub fn is_blob_in_db(db: Arc<DB>, manifest_hash: H256) -> Result<bool> {
+	let cf_handle = db.cf_handle(BLOBS_CF).context("Failed to get cf handle")?;
+
+	db.get_pinned_cf(&cf_handle, blob_key(&manifest_hash))
+		.context("Failed to get blob")
+		.map(|value| value.is_some())
+}
+
+/// Gets reassembled blob with given manifest hash
+pub fn get_blob_from_db(db: Arc<DB>, manifest_hash: H256) -> Result<Option<Blob>> {
+	let cf_handle = db
+		.cf_handle(BLOBS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	db.get_cf(&cf_handle, blob_key(&manifest_hash))
+		.context("Couldn't get blob from db")?
+		.map(|value| Blob::decode(&mut &value[..]).context("Failed to decode blob"))
+		.transpose()
+}
+
+/// Gets locations of up to `limit` application transactions of the given signer,
+/// starting from the given block number, in block order
+pub fn get_signer_transactions_from_db(
+	db: Arc<DB>,
+	app_id: u32,
+	signer: &str,
+	from_block: u32,
+	limit: usize,
+) -> Result<Vec<TransactionLocation>> {
+	let cf_handle = db
+		.cf_handle(TRANSACTIONS_CF)
+		.context("Couldn't get column handle from db")?;
+
+	let prefix = signer_transactions_key(app_id, signer);
+	let mut start = prefix.clone();
+	start.extend(from_block.to_be_bytes());
+
+	let mut locations = vec![];
+	for item in db.iterator_cf(&cf_handle, IteratorMode::From(&start, Direction::Forward)) {
+		let (key, _) = item.context("Couldn't iterate signer transactions")?;
+		let Some(suffix) = key.strip_prefix(prefix.as_slice()) else {
+			break;
+		};
+		if locations.len() >= limit {
+			break;
+		}
+		let (block_number, index) = suffix.split_at(4);
+		let block_number = block_number.try_into().map(u32::from_be_bytes);
+		let index = index.try_into().map(u32::from_be_bytes);
+		let (Ok(block_number), Ok(index)) = (block_number, index) else {
+			return Err(anyhow!("Invalid signer transaction key"));
+		};
+		locations.push(TransactionLocation {
+			app_id,
+			block_number,
+			index,
+		});
+	}
+	Ok(locations)
+}
+
 /// Gets and decodes app data from database for the `app_id:block_number` key
 pub fn get_decoded_data_from_db<T: Decode>(
 	db: Arc<DB>,
@@ -153,6 +613,18 @@ pub trait Database: Clone + Send {
 	fn get_confidence(&self, block_number: u32) -> Result<Option<u32>>;
 	fn get_header(&self, block_number: u32) -> Result<Option<DaHeader>>;
 	fn get_data(&self, app_id: u32, block_number: u32) -> Result<Option<AppData>>;
+	fn get_extrinsic_indices(&self, app_id: u32, block_number: u32) -> Result<Option<Vec<u32>>>;
+	fn get_transaction_location(&self, hash: H256) -> Result<Option<TransactionLocation>>;
+	fn get_signer_transactions(
+		&self,
+		app_id: u32,
+		signer: &str,
+		from_block: u32,
+		limit: usize,
+	) -> Result<Vec<TransactionLocation>>;
+	fn get_submission(&self, id: Uuid) -> Result<Option<Submission>>;
+	fn get_blob(&self, manifest_hash: H256) -> Result<Option<Blob>>;
+	fn get_dht_columns(&self, block_number: u32) -> Result<Option<Vec<u16>>>;
 }
 
 #[derive(Clone)]
@@ -170,6 +642,36 @@ impl Database for RocksDB {
 	fn get_data(&self, app_id: u32, block_number: u32) -> Result<Option<AppData>> {
 		get_decoded_data_from_db(self.0.clone(), app_id, block_number)
 	}
+
+	fn get_extrinsic_indices(&self, app_id: u32, block_number: u32) -> Result<Option<Vec<u32>>> {
+		get_extrinsic_indices_from_db(self.0.clone(), app_id, block_number)
+	}
+
+	fn get_transaction_location(&self, hash: H256) -> Result<Option<TransactionLocation>> {
+		get_transaction_location_from_db(self.0.clone(), hash)
+	}
+
+	fn get_signer_transactions(
+		&self,
+		app_id: u32,
+		signer: &str,
+		from_block: u32,
+		limit: usize,
+	) -> Result<Vec<TransactionLocation>> {
+		get_signer_transactions_from_db(self.0.clone(), app_id, signer, from_block, limit)
+	}
+
+	fn get_submission(&self, id: Uuid) -> Result<Option<Submission>> {
+		get_submission_from_db(self.0.clone(), id)
+	}
+
+	fn get_blob(&self, manifest_hash: H256) -> Result<Option<Blob>> {
+		get_blob_from_db(self.0.clone(), manifest_hash)
+	}
+
+	fn get_dht_columns(&self, block_number: u32) -> Result<Option<Vec<u16>>> {
+		get_dht_columns_from_db(self.0.clone(), block_number)
+	}
 }
 
 /// Gets confidence factor from database for given block number
@@ -259,6 +761,73 @@ pub fn store_finality_sync_checkpoint(
 	.context("Failed to write finality sync checkpoint data")
 }
 
+/// Gets secret used for deterministic sampling from database
+pub fn get_sampling_secret_from_db(db: Arc<DB>) -> Result<Option<[u8; 32]>> {
+	let cf_handle = db
+		.cf_handle(STATE_CF)
+		.context("Couldn't get column handle from db")?;
+
+	let result = db
+		.get_cf(&cf_handle, SAMPLING_SECRET_KEY.as_bytes())
+		.context("Couldn't get sampling secret from db")?;
+
+	result.map_or(Ok(None), |secret| {
+		<[u8; 32]>::try_from(secret.as_slice())
+			.context("Invalid sampling secret length")
+			.map(Some)
+	})
+}
+
+/// Stores secret used for deterministic sampling into database
+pub fn store_sampling_secret_in_db(db: Arc<DB>, secret: &[u8; 32]) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(STATE_CF)
+		.context("Couldn't get column handle from db")?;
+	db.put_cf(&cf_handle, SAMPLING_SECRET_KEY.as_bytes(), secret)
+		.context("Failed to write sampling secret to db")
+}
+
+/// Stores columns of the archived block which were recoverable from DHT alone
+pub fn store_dht_columns_in_db(db: Arc<DB>, block_number: u32, columns: &[u16]) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(STATE_CF)
+		.context("Couldn't get column handle from db")?;
+	let key = format!("{DHT_COLUMNS_PREFIX}:{block_number}");
+	db.put_cf(&cf_handle, key.as_bytes(), columns.encode())
+		.context("Failed to write DHT columns to db")
+}
+
+/// Gets columns of the archived block which were recoverable from DHT alone
+pub fn get_dht_columns_from_db(db: Arc<DB>, block_number: u32) -> Result<Option<Vec<u16>>> {
+	let cf_handle = db
+		.cf_handle(STATE_CF)
+		.context("Couldn't get column handle from db")?;
+	let key = format!("{DHT_COLUMNS_PREFIX}:{block_number}");
+	db.get_cf(&cf_handle, key.as_bytes())
+		.context("Couldn't get DHT columns from db")?
+		.map(|columns| {
+			Vec::<u16>::decode(&mut &columns[..]).context("Failed to decode DHT columns")
+		})
+		.transpose()
+}
+
+/// Stores current timestamp into database, used to check if database is writable
+pub fn store_health_check_in_db(db: Arc<DB>) -> Result<()> {
+	let cf_handle = db
+		.cf_handle(STATE_CF)
+		.context("Couldn't get column handle from db")?;
+	let timestamp = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.context("Invalid system time")?
+		.as_secs();
+	db.put_cf(
+		&cf_handle,
+		HEALTH_CHECK_KEY.as_bytes(),
+		timestamp.to_be_bytes(),
+	)
+	.context("Failed to write health check to db")
+}
+
 /// Stores block header into database under the given block number key
 pub fn store_latest_block_in_db(db: Arc<DB>, block_number: u32) -> Result<()> {
 	let handle = db
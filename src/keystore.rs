@@ -0,0 +1,388 @@
+//! Password encrypted keystore for the Avail account and libp2p identity secret keys.
+//!
+//! Each key is stored in the `{name}.json` file in the keystore directory. Secret is encrypted
+//! with XChaCha20-Poly1305, using the key derived from the password with Argon2id.
+//! Key name and kind are authenticated as additional data, so they cannot be changed without the password.
+
+use anyhow::{anyhow, Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+	aead::{Aead, Payload},
+	KeyInit, XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::{
+	fmt, fs,
+	io::Write,
+	path::{Path, PathBuf},
+};
+use subxt::ext::sp_core::{sr25519, Pair};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+	network::p2p,
+	types::{AvailSecretKey, SecretKey},
+};
+
+/// Environment variable used to unlock the keystore
+pub const PASSWORD_ENV: &str = "AVAIL_KEYSTORE_PASSWORD";
+
+const KEY_FILE_VERSION: u8 = 1;
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20-poly1305";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyKind {
+	/// Avail account secret URI (seed, mnemonic or derivation path)
+	Avail,
+	/// Libp2p identity secret key (hex encoded ed25519 secret key or seed)
+	P2p,
+}
+
+impl fmt::Display for KeyKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			KeyKind::Avail => write!(f, "avail"),
+			KeyKind::P2p => write!(f, "p2p"),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct Kdf {
+	algorithm: String,
+	salt: String,
+	memory: u32,
+	iterations: u32,
+	parallelism: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cipher {
+	algorithm: String,
+	nonce: String,
+}
+
+/// Encrypted key, as stored in the keystore
+#[derive(Serialize, Deserialize)]
+pub struct KeyFile {
+	version: u8,
+	pub name: String,
+	pub kind: KeyKind,
+	/// SS58 address of the Avail account, or peer ID of the libp2p identity
+	pub public: String,
+	kdf: Kdf,
+	cipher: Cipher,
+	ciphertext: String,
+}
+
+impl KeyFile {
+	fn additional_data(name: &str, kind: KeyKind) -> Vec<u8> {
+		format!("{name}:{kind}").into_bytes()
+	}
+
+	fn encrypt(name: &str, kind: KeyKind, secret: &str, password: &str) -> Result<Self> {
+		let public = public(kind, secret)?;
+
+		let mut salt = [0u8; 16];
+		let mut nonce = [0u8; 24];
+		rand::thread_rng().fill_bytes(&mut salt);
+		rand::thread_rng().fill_bytes(&mut nonce);
+
+		let kdf = Kdf {
+			algorithm: KDF_ALGORITHM.to_string(),
+			salt: hex::encode(salt),
+			memory: Params::DEFAULT_M_COST,
+			iterations: Params::DEFAULT_T_COST,
+			parallelism: Params::DEFAULT_P_COST,
+		};
+
+		let payload = Payload {
+			msg: secret.as_bytes(),
+			aad: &Self::additional_data(name, kind),
+		};
+		let ciphertext = cipher(&kdf, password)?
+			.encrypt(XNonce::from_slice(&nonce), payload)
+			.map_err(|_| anyhow!("Failed to encrypt key"))?;
+
+		Ok(KeyFile {
+			version: KEY_FILE_VERSION,
+			name: name.to_string(),
+			kind,
+			public,
+			kdf,
+			cipher: Cipher {
+				algorithm: CIPHER_ALGORITHM.to_string(),
+				nonce: hex::encode(nonce),
+			},
+			ciphertext: hex::encode(ciphertext),
+		})
+	}
+
+	fn decrypt(&self, password: &str) -> Result<Zeroizing<String>> {
+		if self.version != KEY_FILE_VERSION || self.cipher.algorithm != CIPHER_ALGORITHM {
+			return Err(anyhow!("Unsupported key file format"));
+		}
+
+		let nonce = hex::decode(&self.cipher.nonce).context("Invalid nonce")?;
+		if nonce.len() != 24 {
+			return Err(anyhow!("Invalid nonce length"));
+		}
+		let ciphertext = hex::decode(&self.ciphertext).context("Invalid ciphertext")?;
+
+		let payload = Payload {
+			msg: &ciphertext,
+			aad: &Self::additional_data(&self.name, self.kind),
+		};
+		let secret = cipher(&self.kdf, password)?
+			.decrypt(XNonce::from_slice(&nonce), payload)
+			.map_err(|_| anyhow!("Failed to decrypt key {}, password is not valid", self.name))?;
+
+		String::from_utf8(secret)
+			.map(Zeroizing::new)
+			.map_err(|error| {
+				error.into_bytes().zeroize();
+				anyhow!("Decrypted key is not valid")
+			})
+	}
+}
+
+fn cipher(kdf: &Kdf, password: &str) -> Result<XChaCha20Poly1305> {
+	if kdf.algorithm != KDF_ALGORITHM {
+		return Err(anyhow!("Unsupported key derivation function"));
+	}
+
+	let salt = hex::decode(&kdf.salt).context("Invalid salt")?;
+	let params = Params::new(kdf.memory, kdf.iterations, kdf.parallelism, Some(32))
+		.map_err(|error| anyhow!("Invalid key derivation parameters: {error}"))?;
+
+	let mut key = Zeroizing::new([0u8; 32]);
+	Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+		.hash_password_into(password.as_bytes(), &salt, &mut *key)
+		.map_err(|error| anyhow!("Failed to derive key: {error}"))?;
+
+	XChaCha20Poly1305::new_from_slice(&*key).map_err(|_| anyhow!("Invalid key length"))
+}
+
+/// Returns SS58 address of the Avail account, or peer ID of the libp2p identity
+fn public(kind: KeyKind, secret: &str) -> Result<String> {
+	match kind {
+		KeyKind::Avail => AvailSecretKey::try_from(secret.to_string())
+			.map(|key| key.public().to_string())
+			.map_err(|error| anyhow!("Invalid Avail secret key: {error:?}")),
+		KeyKind::P2p => p2p::identity_keypair(Some(p2p_secret_key(secret)))
+			.map(|keypair| keypair.public().to_peer_id().to_string()),
+	}
+}
+
+/// Libp2p secret key is either hex encoded ed25519 secret key, or seed
+fn p2p_secret_key(secret: &str) -> SecretKey {
+	let is_key = secret.len() == 64 && secret.chars().all(|c| c.is_ascii_hexdigit());
+	if is_key {
+		SecretKey::Key {
+			key: secret.to_string().into(),
+		}
+	} else {
+		SecretKey::Seed {
+			seed: secret.to_string().into(),
+		}
+	}
+}
+
+fn generate_secret(kind: KeyKind) -> String {
+	match kind {
+		KeyKind::Avail => sr25519::Pair::generate_with_phrase(None).1,
+		KeyKind::P2p => {
+			let mut key = [0u8; 32];
+			rand::thread_rng().fill_bytes(&mut key);
+			hex::encode(key)
+		},
+	}
+}
+
+/// Reads keystore password from the environment
+pub fn password_from_env() -> Result<String> {
+	std::env::var(PASSWORD_ENV)
+		.with_context(|| format!("Keystore password is not set in the {PASSWORD_ENV} variable"))
+}
+
+pub struct Keystore {
+	path: PathBuf,
+}
+
+impl Keystore {
+	pub fn new(path: impl AsRef<Path>) -> Self {
+		Keystore {
+			path: path.as_ref().to_path_buf(),
+		}
+	}
+
+	fn key_path(&self, name: &str) -> Result<PathBuf> {
+		let is_valid = !name.is_empty()
+			&& name
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+		if !is_valid {
+			return Err(anyhow!(
+				"Key name can contain only alphanumeric characters, '-' and '_'"
+			));
+		}
+		Ok(self.path.join(format!("{name}.json")))
+	}
+
+	fn read(&self, name: &str) -> Result<KeyFile> {
+		let path = self.key_path(name)?;
+		let content =
+			fs::read(&path).with_context(|| format!("Key {name} is not found in the keystore"))?;
+		serde_json::from_slice(&content).with_context(|| format!("Invalid key file {path:?}"))
+	}
+
+	/// Lists keys in the keystore, without decrypting them
+	pub fn list(&self) -> Result<Vec<KeyFile>> {
+		if !self.path.exists() {
+			return Ok(vec![]);
+		}
+
+		let mut keys = vec![];
+		for entry in fs::read_dir(&self.path).context("Failed to read keystore")? {
+			let path = entry.context("Failed to read keystore")?.path();
+			if path
+				.extension()
+				.map_or(true, |extension| extension != "json")
+			{
+				continue;
+			}
+			let content = fs::read(&path).with_context(|| format!("Failed to read {path:?}"))?;
+			let key = serde_json::from_slice(&content)
+				.with_context(|| format!("Invalid key file {path:?}"))?;
+			keys.push(key);
+		}
+		keys.sort_by(|a: &KeyFile, b| a.name.cmp(&b.name));
+		Ok(keys)
+	}
+
+	/// Encrypts the secret and stores it under the given name. Existing keys are not overwritten.
+	pub fn import(
+		&self,
+		name: &str,
+		kind: KeyKind,
+		secret: &str,
+		password: &str,
+	) -> Result<KeyFile> {
+		let path = self.key_path(name)?;
+		let key = KeyFile::encrypt(name, kind, secret, password)?;
+		let content = serde_json::to_vec_pretty(&key)?;
+
+		fs::create_dir_all(&self.path).context("Failed to create keystore")?;
+		let mut options = fs::OpenOptions::new();
+		options.write(true).create_new(true);
+		#[cfg(unix)]
+		std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+		let mut file = options
+			.open(&path)
+			.with_context(|| format!("Failed to create key {name}, key may already exist"))?;
+		file.write_all(&content)
+			.with_context(|| format!("Failed to write key {name}"))?;
+
+		Ok(key)
+	}
+
+	/// Generates new random secret of the given kind, and stores it under the given name
+	pub fn generate(&self, name: &str, kind: KeyKind, password: &str) -> Result<KeyFile> {
+		self.import(name, kind, &generate_secret(kind), password)
+	}
+
+	/// Decrypts secret of the key with the given name
+	pub fn export(&self, name: &str, password: &str) -> Result<(KeyKind, Zeroizing<String>)> {
+		let key = self.read(name)?;
+		let secret = key.decrypt(password)?;
+		Ok((key.kind, secret))
+	}
+
+	fn secret(&self, name: &str, kind: KeyKind, password: &str) -> Result<Zeroizing<String>> {
+		let (key_kind, secret) = self.export(name, password)?;
+		if key_kind != kind {
+			return Err(anyhow!(
+				"Key {name} is {key_kind} key, {kind} key is expected"
+			));
+		}
+		Ok(secret)
+	}
+
+	pub fn avail_secret_key(&self, name: &str, password: &str) -> Result<AvailSecretKey> {
+		let secret = self.secret(name, KeyKind::Avail, password)?;
+		AvailSecretKey::try_from(secret.to_string())
+			.map_err(|error| anyhow!("Invalid key {name}: {error:?}"))
+	}
+
+	pub fn p2p_secret_key(&self, name: &str, password: &str) -> Result<SecretKey> {
+		self.secret(name, KeyKind::P2p, password)
+			.map(|secret| p2p_secret_key(&secret))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{KeyKind, Keystore};
+	use crate::types::SecretKey;
+	use std::fs;
+	use subxt::ext::sp_core::Pair;
+	use uuid::Uuid;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+	fn keystore() -> (Keystore, std::path::PathBuf) {
+		let path = std::env::temp_dir().join(format!("keystore-{}", Uuid::new_v4()));
+		(Keystore::new(&path), path)
+	}
+
+	#[test]
+	fn import_and_export() {
+		let (keystore, path) = keystore();
+
+		let key = keystore
+			.import("alice", KeyKind::Avail, "//Alice", "password")
+			.unwrap();
+		assert_eq!(key.public, ALICE);
+
+		let (kind, secret) = keystore.export("alice", "password").unwrap();
+		assert_eq!(kind, KeyKind::Avail);
+		assert_eq!(*secret, "//Alice");
+
+		let secret_key = keystore.avail_secret_key("alice", "password").unwrap();
+		assert_eq!(secret_key.public().to_string(), ALICE);
+
+		assert!(keystore.export("alice", "wrong").is_err());
+		assert!(keystore.p2p_secret_key("alice", "password").is_err());
+		assert!(keystore
+			.import("alice", KeyKind::Avail, "//Bob", "password")
+			.is_err());
+
+		fs::remove_dir_all(path).unwrap();
+	}
+
+	#[test]
+	fn generate_and_list() {
+		let (keystore, path) = keystore();
+		assert!(keystore.list().unwrap().is_empty());
+
+		keystore.generate("p2p", KeyKind::P2p, "password").unwrap();
+		keystore
+			.generate("account", KeyKind::Avail, "password")
+			.unwrap();
+		assert!(keystore
+			.generate("../key", KeyKind::P2p, "password")
+			.is_err());
+
+		let keys = keystore.list().unwrap();
+		let names = keys.iter().map(|key| key.name.as_str()).collect::<Vec<_>>();
+		assert_eq!(names, vec!["account", "p2p"]);
+
+		let secret_key = keystore.p2p_secret_key("p2p", "password").unwrap();
+		assert!(matches!(secret_key, SecretKey::Key { .. }));
+
+		fs::remove_dir_all(path).unwrap();
+	}
+}
@@ -1,13 +1,17 @@
 pub mod api;
 pub mod app_client;
+pub mod blobs;
+pub mod cell_cache;
 pub mod consts;
 #[cfg(feature = "crawl")]
 pub mod crawl_client;
 pub mod data;
+pub mod keystore;
 pub mod light_client;
 pub mod light_client_commons;
 pub mod network;
 pub mod proof;
+pub mod sampling;
 pub mod sync_client;
 pub mod sync_finality;
 pub mod telemetry;
@@ -5,7 +5,7 @@
 //! # Flow
 //!
 //! * Connect to the Avail node WebSocket stream and start listening to finalized headers
-//! * Generate random cells for random data sampling (8 cells currently)
+//! * Choose cells for random data sampling using configured sampling strategy
 //! * Retrieve cell proofs from a) DHT and/or b) via RPC call from the node, in that order
 //! * Verify proof using the received cells
 //! * Calculate block confidence and store it in RocksDB
@@ -22,7 +22,6 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use avail_subxt::{primitives::Header, utils::H256};
 use codec::Encode;
-use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
 use futures::future::join_all;
 use kate_recovery::{
 	commitments, data,
@@ -37,9 +36,10 @@ use std::{
 	time::Instant,
 };
 use tokio::sync::{broadcast, mpsc::Sender};
-use tracing::{error, info};
+use tracing::{error, info, info_span, Instrument};
 
 use crate::{
+	cell_cache::CellCache,
 	data::{
 		store_block_header_in_db, store_blocks_list_in_db, store_confidence_achieved_blocks_in_db,
 		store_confidence_in_db, store_latest_block_in_db,
@@ -48,7 +48,8 @@ use crate::{
 		p2p::Client as P2pClient,
 		rpc::{self, Client as RpcClient, Event},
 	},
-	proof,
+	proof::{Priority, Verifier},
+	sampling::SamplingStrategy,
 	telemetry::{MetricCounter, MetricValue, Metrics},
 	types::{self, BlockVerified, LightClientConfig, OptionBlockRange, State},
 	utils::{calculate_confidence, extract_kate},
@@ -63,6 +64,8 @@ pub trait LightClient {
 		block_number: u32,
 	) -> (Vec<Cell>, Vec<Position>);
 	async fn insert_cells_into_dht(&self, block: u32, cells: Vec<Cell>) -> f32;
+	/// Keeps cells locally, so they can be served without fetching them again
+	fn cache_cells(&self, block: u32, cells: &[Cell]);
 	async fn insert_rows_into_dht(&self, block: u32, rows: Vec<(RowIndex, Vec<u8>)>) -> f32;
 	async fn get_kate_proof(&self, hash: H256, positions: &[Position]) -> Result<Vec<Cell>>;
 	async fn shrink_kademlia_map(&self) -> Result<()>;
@@ -80,13 +83,20 @@ struct LightClientImpl {
 	db: Arc<DB>,
 	p2p_client: P2pClient,
 	rpc_client: RpcClient,
+	cell_cache: CellCache,
 }
 
-pub fn new(db: Arc<DB>, p2p_client: P2pClient, rpc_client: RpcClient) -> impl LightClient {
+pub fn new(
+	db: Arc<DB>,
+	p2p_client: P2pClient,
+	rpc_client: RpcClient,
+	cell_cache: CellCache,
+) -> impl LightClient {
 	LightClientImpl {
 		db,
 		p2p_client,
 		rpc_client,
+		cell_cache,
 	}
 }
 
@@ -95,6 +105,9 @@ impl LightClient for LightClientImpl {
 	async fn insert_cells_into_dht(&self, block: u32, cells: Vec<Cell>) -> f32 {
 		self.p2p_client.insert_cells_into_dht(block, cells).await
 	}
+	fn cache_cells(&self, block: u32, cells: &[Cell]) {
+		self.cell_cache.insert(block, cells);
+	}
 	async fn shrink_kademlia_map(&self) -> Result<()> {
 		self.p2p_client.shrink_kademlia_map().await
 	}
@@ -141,19 +154,19 @@ impl LightClient for LightClientImpl {
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn process_block(
 	light_client: &impl LightClient,
 	metrics: &Arc<impl Metrics>,
 	cfg: &LightClientConfig,
-	pp: Arc<PublicParameters>,
+	verifier: &Verifier,
+	sampling: &dyn SamplingStrategy,
 	header: &Header,
 	received_at: Instant,
 	state: Arc<Mutex<State>>,
 ) -> Result<Option<f64>> {
-	metrics.count(MetricCounter::SessionBlock).await;
-	metrics
-		.record(MetricValue::TotalBlockNumber(header.number))
-		.await?;
+	metrics.count(MetricCounter::SessionBlock);
+	metrics.record(MetricValue::TotalBlockNumber(header.number));
 
 	let block_number = header.number;
 	let header_hash: H256 = Encode::using_encoded(header, blake2_256).into();
@@ -182,16 +195,17 @@ pub async fn process_block(
 	let commitments = commitments::from_slice(&commitment)?;
 
 	let cell_count = rpc::cell_count_for_confidence(cfg.confidence);
-	let positions = rpc::generate_random_cells(dimensions, cell_count);
+	let positions = sampling.positions(header_hash, dimensions, cell_count);
 	info!(
 		block_number,
 		"cells_requested" = positions.len(),
-		"Random cells generated: {}",
+		"Cells for sampling generated: {}",
 		positions.len()
 	);
 
 	let (cells_fetched, unfetched) = light_client
 		.fetch_cells_from_dht(&positions, block_number)
+		.instrument(info_span!("dht_fetch"))
 		.await;
 	info!(
 		block_number,
@@ -206,21 +220,18 @@ pub async fn process_block(
 		Err(err) => panic!("err : {}", err),
 	}
 	let _ = light_client.store_blocks_list_in_db(block_number);
-	metrics
-		.record(MetricValue::DHTFetched(cells_fetched.len() as f64))
-		.await?;
+	metrics.record(MetricValue::DHTFetched(cells_fetched.len() as f64));
 
-	metrics
-		.record(MetricValue::DHTFetchedPercentage(
-			cells_fetched.len() as f64 / positions.len() as f64,
-		))
-		.await?;
+	metrics.record(MetricValue::DHTFetchedPercentage(
+		cells_fetched.len() as f64 / positions.len() as f64,
+	));
 
 	let mut rpc_fetched = if cfg.disable_rpc {
 		vec![]
 	} else {
 		light_client
 			.get_kate_proof(header_hash, &unfetched)
+			.instrument(info_span!("rpc_fetch"))
 			.await
 			.context("Failed to fetch cells from node RPC")?
 	};
@@ -231,9 +242,7 @@ pub async fn process_block(
 		"Number of cells fetched from RPC: {}",
 		rpc_fetched.len()
 	);
-	metrics
-		.record(MetricValue::NodeRPCFetched(rpc_fetched.len() as f64))
-		.await?;
+	metrics.record(MetricValue::NodeRPCFetched(rpc_fetched.len() as f64));
 
 	let mut cells = vec![];
 	cells.extend(cells_fetched);
@@ -250,8 +259,16 @@ pub async fn process_block(
 
 	let mut confidence = None;
 	if !cfg.disable_proof_verification {
-		let (verified, unverified) =
-			proof::verify(block_number, dimensions, &cells, &commitments, pp)?;
+		let (verified, unverified) = verifier
+			.verify(
+				Priority::Live,
+				block_number,
+				dimensions,
+				&cells,
+				&commitments,
+			)
+			.instrument(info_span!("verification"))
+			.await?;
 		let count = verified.len().saturating_sub(unverified.len());
 		info!(
 			block_number,
@@ -259,6 +276,13 @@ pub async fn process_block(
 			"Completed {count} verification rounds",
 		);
 
+		let verified_cells = cells
+			.iter()
+			.filter(|cell| verified.contains(&cell.position))
+			.cloned()
+			.collect::<Vec<_>>();
+		light_client.cache_cells(block_number, &verified_cells);
+
 		// write confidence factor into on-disk database
 		light_client
 			.store_confidence_in_db(verified.len() as u32, block_number)
@@ -273,7 +297,7 @@ pub async fn process_block(
 			"Confidence factor: {}",
 			conf
 		);
-		metrics.record(MetricValue::BlockConfidence(conf)).await?;
+		metrics.record(MetricValue::BlockConfidence(conf));
 		confidence = Some(conf);
 	}
 
@@ -329,6 +353,8 @@ pub async fn process_block(
 							.any(move |rpc_cell| rpc_cell.position.eq(&cell.position))
 					})
 					.collect::<Vec<_>>();
+				// Partition cells are fetched from the node RPC and inserted into DHT without verification
+				light_client.cache_cells(block_number, &partition_fetched_filtered);
 				rpc_fetched.extend(partition_fetched_filtered.clone());
 			}
 		}
@@ -344,6 +370,7 @@ pub async fn process_block(
 
 		let dht_insert_rows_success_rate = light_client
 			.insert_rows_into_dht(block_number, rpc_fetched_data_rows)
+			.instrument(info_span!("dht_insert_rows"))
 			.await;
 		let success_rate: f64 = dht_insert_rows_success_rate.into();
 		let time_elapsed = begin.elapsed();
@@ -353,9 +380,7 @@ pub async fn process_block(
 			"DHT PUT rows operation success rate: {dht_insert_rows_success_rate}"
 		);
 
-		metrics
-			.record(MetricValue::DHTPutRowsSuccess(success_rate))
-			.await?;
+		metrics.record(MetricValue::DHTPutRowsSuccess(success_rate));
 
 		info!(
 			block_number,
@@ -363,9 +388,7 @@ pub async fn process_block(
 			"{rows_len} rows inserted into DHT"
 		);
 
-		metrics
-			.record(MetricValue::DHTPutRowsDuration(time_elapsed.as_secs_f64()))
-			.await?;
+		metrics.record(MetricValue::DHTPutRowsDuration(time_elapsed.as_secs_f64()));
 	}
 
 	let partition_time_elapsed = begin.elapsed();
@@ -376,16 +399,15 @@ pub async fn process_block(
 		"partition_cells_fetched" = rpc_fetched_len,
 		"Partition cells received",
 	);
-	metrics
-		.record(MetricValue::RPCCallDuration(
-			partition_time_elapsed.as_secs_f64(),
-		))
-		.await?;
+	metrics.record(MetricValue::RPCCallDuration(
+		partition_time_elapsed.as_secs_f64(),
+	));
 
 	begin = Instant::now();
 
 	let dht_insert_success_rate = light_client
 		.insert_cells_into_dht(block_number, rpc_fetched)
+		.instrument(info_span!("dht_insert"))
 		.await;
 
 	info!(
@@ -393,9 +415,7 @@ pub async fn process_block(
 		"DHT PUT operation success rate: {}", dht_insert_success_rate
 	);
 
-	metrics
-		.record(MetricValue::DHTPutSuccess(dht_insert_success_rate as f64))
-		.await?;
+	metrics.record(MetricValue::DHTPutSuccess(dht_insert_success_rate as f64));
 
 	let dht_put_time_elapsed = begin.elapsed();
 	info!(
@@ -404,11 +424,9 @@ pub async fn process_block(
 		"{rpc_fetched_len} cells inserted into DHT",
 	);
 
-	metrics
-		.record(MetricValue::DHTPutDuration(
-			dht_put_time_elapsed.as_secs_f64(),
-		))
-		.await?;
+	metrics.record(MetricValue::DHTPutDuration(
+		dht_put_time_elapsed.as_secs_f64(),
+	));
 
 	light_client
 		.shrink_kademlia_map()
@@ -418,16 +436,14 @@ pub async fn process_block(
 	// dump what we have on the current p2p network
 	if let Ok((multiaddr, ip)) = light_client.get_multiaddress_and_ip().await {
 		// set Multiaddress
-		metrics.set_multiaddress(multiaddr).await;
-		metrics.set_ip(ip).await;
+		metrics.set_multiaddress(multiaddr);
+		metrics.set_ip(ip);
 	}
 	if let Ok(counted_peers) = light_client.count_dht_entries().await {
-		metrics
-			.record(MetricValue::KadRoutingPeerNum(counted_peers))
-			.await?
+		metrics.record(MetricValue::KadRoutingPeerNum(counted_peers));
 	}
 
-	metrics.record(MetricValue::HealthCheck()).await?;
+	metrics.record(MetricValue::HealthCheck());
 
 	Ok(confidence)
 }
@@ -445,13 +461,15 @@ pub struct Channels {
 /// * `light_client` - Light client implementation
 /// * `cfg` - Light client configuration
 /// * `block_tx` - Channel used to send header of verified block
-/// * `pp` - Public parameters (i.e. SRS) needed for proof verification
+/// * `verifier` - Shared proof verifier
+/// * `sampling` - Strategy used to choose cells for sampling
 /// * `registry` - Prometheus metrics registry
 /// * `state` - Processed blocks state
 pub async fn run(
 	light_client: impl LightClient,
 	cfg: LightClientConfig,
-	pp: Arc<PublicParameters>,
+	verifier: Verifier,
+	sampling: Arc<dyn SamplingStrategy>,
 	metrics: Arc<impl Metrics>,
 	state: Arc<Mutex<State>>,
 	mut channels: Channels,
@@ -459,12 +477,13 @@ pub async fn run(
 	info!("Starting light client...");
 
 	loop {
-		let (header, received_at) = match channels.rpc_event_receiver.recv().await {
+		let (header, received_at, span) = match channels.rpc_event_receiver.recv().await {
 			Ok(event) => match event {
 				Event::HeaderUpdate {
 					header,
 					received_at,
-				} => (header, received_at),
+					span,
+				} => (header, received_at, span),
 			},
 			Err(error) => {
 				error!("Cannot receive message: {error}");
@@ -473,12 +492,7 @@ pub async fn run(
 		};
 
 		if let Some(seconds) = cfg.block_processing_delay.sleep_duration(received_at) {
-			if let Err(error) = metrics
-				.record(MetricValue::BlockProcessingDelay(seconds.as_secs_f64()))
-				.await
-			{
-				error!("Cannot record crawl block delay: {}", error);
-			}
+			metrics.record(MetricValue::BlockProcessingDelay(seconds.as_secs_f64()));
 			info!("Sleeping for {seconds:?} seconds");
 			tokio::time::sleep(seconds).await;
 		}
@@ -487,11 +501,13 @@ pub async fn run(
 			&light_client,
 			&metrics,
 			&cfg,
-			pp.clone(),
+			&verifier,
+			sampling.as_ref(),
 			&header,
 			received_at,
 			state.clone(),
 		)
+		.instrument(span.clone())
 		.await;
 		let confidence = match process_block_result {
 			Ok(confidence) => confidence,
@@ -504,7 +520,8 @@ pub async fn run(
 			},
 		};
 
-		let Ok(client_msg) = types::BlockVerified::try_from((header, confidence)) else {
+		let Ok(client_msg) = span.in_scope(|| types::BlockVerified::try_from((header, confidence)))
+		else {
 			error!("Cannot create message from header");
 			continue;
 		};
@@ -523,7 +540,9 @@ pub async fn run(
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{network::rpc::cell_count_for_confidence, telemetry, types::RuntimeConfig};
+	use crate::{
+		network::rpc::cell_count_for_confidence, sampling::Uniform, telemetry, types::RuntimeConfig,
+	};
 	use avail_subxt::{
 		api::runtime_types::avail_core::{
 			data_lookup::compact::CompactDataLookup,
@@ -669,6 +688,7 @@ mod tests {
 		mock_client
 			.expect_insert_cells_into_dht()
 			.returning(|_, _| Box::pin(async move { 1f32 }));
+		mock_client.expect_cache_cells().returning(|_, _| ());
 		mock_client
 			.expect_shrink_kademlia_map()
 			.returning(|| Box::pin(async move { Ok(()) }));
@@ -681,14 +701,15 @@ mod tests {
 
 		let mut mock_metrics = telemetry::MockMetrics::new();
 		mock_metrics.expect_count().returning(|_| ());
-		mock_metrics.expect_record().returning(|_| Ok(()));
+		mock_metrics.expect_record().returning(|_| ());
 		mock_metrics.expect_set_multiaddress().returning(|_| ());
 		mock_metrics.expect_set_ip().returning(|_| ());
 		process_block(
 			&mock_client,
 			&Arc::new(mock_metrics),
 			&cfg,
-			pp,
+			&test_verifier(pp),
+			&Uniform,
 			&header,
 			recv,
 			state,
@@ -804,6 +825,7 @@ mod tests {
 		mock_client
 			.expect_insert_cells_into_dht()
 			.returning(|_, _| Box::pin(async move { 1f32 }));
+		mock_client.expect_cache_cells().returning(|_, _| ());
 		mock_client
 			.expect_shrink_kademlia_map()
 			.returning(|| Box::pin(async move { Ok(()) }));
@@ -816,14 +838,15 @@ mod tests {
 
 		let mut mock_metrics = telemetry::MockMetrics::new();
 		mock_metrics.expect_count().returning(|_| ());
-		mock_metrics.expect_record().returning(|_| Ok(()));
+		mock_metrics.expect_record().returning(|_| ());
 		mock_metrics.expect_set_multiaddress().returning(|_| ());
 		mock_metrics.expect_set_ip().returning(|_| ());
 		process_block(
 			&mock_client,
 			&Arc::new(mock_metrics),
 			&cfg,
-			pp,
+			&test_verifier(pp),
+			&Uniform,
 			&header,
 			recv,
 			state,
@@ -3,12 +3,13 @@ use avail_core::AppId;
 use clap::{command, Parser};
 use tracing::error;
 
-use crate::api::v2::types::{PublishMessage, Topic};
+use crate::api::v2::types::{PublishMessage, Submission, Topic};
+use crate::cell_cache::CellCache;
 use crate::consts::{
-	APP_DATA_CF, BLOCKS_LIST_CF, BLOCKS_LIST_LENGTH_CF, BLOCK_HEADER_CF,
+	APP_DATA_CF, BLOBS_CF, BLOCKS_LIST_CF, BLOCKS_LIST_LENGTH_CF, BLOCK_HEADER_CF,
 	CONFIDENCE_ACHIEVED_BLOCKS_CF, CONFIDENCE_ACHIEVED_MESSAGE_CF, CONFIDENCE_FACTOR_CF,
 	DATA_VERIFIED_MESSAGE_CF, EXPECTED_NETWORK_VERSION, HEADER_VERIFIED_MESSAGE_CF,
-	LATEST_BLOCK_CF, STATE_CF,
+	LATEST_BLOCK_CF, STATE_CF, SUBMISSIONS_CF, TRANSACTIONS_CF,
 };
 use crate::data::{
 	self, store_confidence_achieved_message_in_db, store_data_verified_message_in_db,
@@ -16,10 +17,9 @@ use crate::data::{
 };
 use crate::network::p2p;
 use crate::types::{self, Mode, RuntimeConfig, State};
-use crate::{api, network::rpc, telemetry};
+use crate::{api, network::rpc, proof, sampling, telemetry};
 
 use crate::{app_client, light_client, sync_client, sync_finality};
-use kate_recovery::com::AppData;
 use libp2p::{multiaddr::Protocol, Multiaddr};
 use rocksdb::{ColumnFamilyDescriptor, Options, DB};
 use std::fmt::Display;
@@ -32,6 +32,7 @@ use tokio::sync::{broadcast, mpsc::Sender};
 use tracing::{info, metadata::ParseLevelError, trace, warn, Level};
 use tracing_subscriber::{
 	fmt::format::{self, DefaultFields, Format, Full, Json},
+	layer::SubscriberExt,
 	FmtSubscriber,
 };
 pub type FfiCallback = extern "C" fn(data: *const u8);
@@ -67,6 +68,15 @@ pub fn init_db(path: &str, read_only: bool) -> Result<Arc<DB>> {
 	let mut app_data_cf_opts = Options::default();
 	app_data_cf_opts.set_max_write_buffer_number(16);
 
+	let mut transactions_cf_opts = Options::default();
+	transactions_cf_opts.set_max_write_buffer_number(16);
+
+	let mut submissions_cf_opts = Options::default();
+	submissions_cf_opts.set_max_write_buffer_number(16);
+
+	let mut blobs_cf_opts = Options::default();
+	blobs_cf_opts.set_max_write_buffer_number(16);
+
 	let mut state_cf_opts = Options::default();
 	state_cf_opts.set_max_write_buffer_number(16);
 
@@ -92,23 +102,26 @@ pub fn init_db(path: &str, read_only: bool) -> Result<Arc<DB>> {
 	data_verified_message_cf_opts.set_max_write_buffer_number(16);
 
 	let cf_opts = vec![
-		ColumnFamilyDescriptor::new(CONFIDENCE_FACTOR_CF, confidence_cf_opts),
-		ColumnFamilyDescriptor::new(BLOCK_HEADER_CF, block_header_cf_opts),
-		ColumnFamilyDescriptor::new(APP_DATA_CF, app_data_cf_opts),
-		ColumnFamilyDescriptor::new(STATE_CF, state_cf_opts),
-		ColumnFamilyDescriptor::new(LATEST_BLOCK_CF, latest_block_cf_opts),
-		ColumnFamilyDescriptor::new(
+		(CONFIDENCE_FACTOR_CF, confidence_cf_opts),
+		(BLOCK_HEADER_CF, block_header_cf_opts),
+		(APP_DATA_CF, app_data_cf_opts),
+		(TRANSACTIONS_CF, transactions_cf_opts),
+		(SUBMISSIONS_CF, submissions_cf_opts),
+		(BLOBS_CF, blobs_cf_opts),
+		(STATE_CF, state_cf_opts),
+		(LATEST_BLOCK_CF, latest_block_cf_opts),
+		(
 			CONFIDENCE_ACHIEVED_BLOCKS_CF,
 			confidence_achieved_blocks_cf_opts,
 		),
-		ColumnFamilyDescriptor::new(BLOCKS_LIST_CF, blocks_list_cf_opts),
-		ColumnFamilyDescriptor::new(BLOCKS_LIST_LENGTH_CF, blocks_list_length_cf_opts),
-		ColumnFamilyDescriptor::new(
+		(BLOCKS_LIST_CF, blocks_list_cf_opts),
+		(BLOCKS_LIST_LENGTH_CF, blocks_list_length_cf_opts),
+		(
 			CONFIDENCE_ACHIEVED_MESSAGE_CF,
 			confidence_achieved_message_cf_opts,
 		),
-		ColumnFamilyDescriptor::new(HEADER_VERIFIED_MESSAGE_CF, header_verified_message_cf_opts),
-		ColumnFamilyDescriptor::new(DATA_VERIFIED_MESSAGE_CF, data_verified_message_cf_opts),
+		(HEADER_VERIFIED_MESSAGE_CF, header_verified_message_cf_opts),
+		(DATA_VERIFIED_MESSAGE_CF, data_verified_message_cf_opts),
 	];
 
 	let mut db_opts = Options::default();
@@ -116,8 +129,18 @@ pub fn init_db(path: &str, read_only: bool) -> Result<Arc<DB>> {
 	db_opts.create_missing_column_families(true);
 	let db;
 	if read_only {
+		// Databases created by older versions are missing the newer column families,
+		// which cannot be created in the read-only mode
+		let existing = DB::list_cf(&db_opts, path)?;
+		let cf_opts = cf_opts
+			.into_iter()
+			.filter(|(name, _)| existing.iter().any(|existing| existing.as_str() == *name))
+			.map(|(name, opts)| ColumnFamilyDescriptor::new(name, opts));
 		db = DB::open_cf_descriptors_read_only(&db_opts, path, cf_opts, false)?;
 	} else {
+		let cf_opts = cf_opts
+			.into_iter()
+			.map(|(name, opts)| ColumnFamilyDescriptor::new(name, opts));
 		db = DB::open_cf_descriptors(&db_opts, path, cf_opts)?;
 	}
 	Ok(Arc::new(db))
@@ -147,7 +170,7 @@ fn parse_log_level(log_level: &str, default: Level) -> (Level, Option<ParseLevel
 
 pub async fn run(
 	error_sender: Sender<anyhow::Error>,
-	cfg: RuntimeConfig,
+	mut cfg: RuntimeConfig,
 	server_needed: bool,
 	await_run: bool,
 	set_parser: bool,
@@ -156,18 +179,27 @@ pub async fn run(
 ) -> Result<(Arc<Mutex<State>>, Arc<DB>)> {
 	if set_parser {
 		let (log_level, parse_error) = parse_log_level(&cfg.log_level, Level::INFO);
+		let tracer = cfg
+			.ot_tracing
+			.then(|| telemetry::traces::tracer(cfg.ot_collector_endpoint.clone(), CLIENT_ROLE))
+			.transpose()
+			.context("Unable to initialize OpenTelemetry tracing")?;
+		let traces_layer = tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer));
 		if cfg.log_format_json {
-			tracing::subscriber::set_global_default(json_subscriber(log_level))
+			tracing::subscriber::set_global_default(json_subscriber(log_level).with(traces_layer))
 				.expect("global json subscriber is set")
 		} else {
-			tracing::subscriber::set_global_default(default_subscriber(log_level))
-				.expect("global default subscriber is set")
+			tracing::subscriber::set_global_default(
+				default_subscriber(log_level).with(traces_layer),
+			)
+			.expect("global default subscriber is set")
 		}
 	}
 
 	let version = clap::crate_version!();
 	info!("Running Avail light client version: {version}");
 	info!("Using config: {cfg:?}");
+	cfg.unlock_keys().context("Failed to unlock keystore")?;
 	if cfg.bootstraps.is_empty() {
 		Err(anyhow!("Bootstrap node list must not be empty. Either use a '--network' flag or add a list of bootstrap nodes in the configuration file"))?
 	}
@@ -184,12 +216,8 @@ pub async fn run(
 	let (id_keys, peer_id) = p2p::keypair((&cfg).into())?;
 
 	let ot_metrics = Arc::new(
-		telemetry::otlp::initialize(
-			cfg.ot_collector_endpoint.clone(),
-			peer_id,
-			CLIENT_ROLE.into(),
-		)
-		.context("Unable to initialize OpenTelemetry service")?,
+		telemetry::initialize(&(&cfg).into(), peer_id, CLIENT_ROLE.into())
+			.context("Unable to initialize metrics")?,
 	);
 
 	// raise new P2P Network Client and Event Loop
@@ -234,6 +262,7 @@ pub async fn run(
 	let public_params_hash = hex::encode(sp_core::blake2_128(&raw_pp));
 	let public_params_len = hex::encode(raw_pp).len();
 	trace!("Public params ({public_params_len}): hash: {public_params_hash}");
+	let verifier = proof::Verifier::new(pp.clone(), &(&cfg).into(), ot_metrics.clone());
 	let state = Arc::new(Mutex::new(State::default()));
 	let (rpc_client, rpc_events, rpc_event_loop) =
 		rpc::init(db.clone(), state.clone(), &cfg.full_node_ws);
@@ -269,13 +298,17 @@ pub async fn run(
 	state.lock().unwrap().latest = block_header.number;
 	let sync_range = cfg.sync_range(block_header.number);
 
+	let sampling = sampling::new(cfg.sampling_strategy, db.clone())
+		.context("Cannot initialize sampling strategy")?;
+
 	let ws_clients = api::v2::types::WsClients::default();
+	let cell_cache = CellCache::new(cfg.cell_cache_blocks);
 	// let (message_tx, message_rx) = broadcast::channel::<(Header, Instant)>(128);
 	let (block_tx, data_rx) = if let Mode::AppClient(app_id) = Mode::from(cfg.app_id) {
 		// communication channels being established for talking to
 		// libp2p backed application client
 		let (block_tx, block_rx) = broadcast::channel::<types::BlockVerified>(1 << 7);
-		let (data_tx, data_rx) = broadcast::channel::<(u32, AppData)>(1 << 7);
+		let (data_tx, data_rx) = broadcast::channel::<types::DataVerified>(1 << 7);
 		tokio::task::spawn(app_client::run(
 			(&cfg).into(),
 			db.clone(),
@@ -284,6 +317,8 @@ pub async fn run(
 			AppId(app_id),
 			block_rx,
 			pp.clone(),
+			verifier.clone(),
+			cell_cache.clone(),
 			state.clone(),
 			sync_range.clone(),
 			data_tx,
@@ -307,7 +342,7 @@ pub async fn run(
 				sender.subscribe(),
 			));
 		}
-		let (_, data_reciever_for_db_dump) = broadcast::channel::<(u32, AppData)>(1 << 7);
+		let (_, data_reciever_for_db_dump) = broadcast::channel::<types::DataVerified>(1 << 7);
 		tokio::task::spawn(store_publish_messages(
 			db.clone(),
 			api::v2::types::Topic::DataVerified,
@@ -316,6 +351,16 @@ pub async fn run(
 	}
 
 	if server_needed {
+		// Published messages, consumed by server-sent events and webhooks
+		let (events, _) = broadcast::channel::<PublishMessage>(1 << 7);
+		api::v2::webhooks::run((&cfg).into(), &events);
+		// Status updates of the transactions submitted through the API
+		let (submissions, _) = broadcast::channel::<Submission>(1 << 7);
+		if let Err(error) = api::v2::transactions::recheck_submissions(db.clone()) {
+			error!("Cannot recheck pending submissions: {error:#}");
+		}
+		tokio::task::spawn(api::v2::transactions::prune_submissions(db.clone()));
+
 		// Spawn tokio task which runs one http server for handling RPC
 		let server = api::server::Server {
 			db: db.clone(),
@@ -325,7 +370,13 @@ pub async fn run(
 			network_version: EXPECTED_NETWORK_VERSION.to_string(),
 			node,
 			node_client: rpc_client.clone(),
+			p2p_client: p2p_client.clone(),
 			ws_clients: ws_clients.clone(),
+			events: events.clone(),
+			submissions: submissions.clone(),
+			cell_cache: cell_cache.clone(),
+			verifier: verifier.clone(),
+			pp: pp.clone(),
 		};
 
 		tokio::task::spawn(server.run());
@@ -334,6 +385,7 @@ pub async fn run(
 			api::v2::types::Topic::HeaderVerified,
 			publish_rpc_event_receiver_2,
 			ws_clients.clone(),
+			events.clone(),
 		));
 
 		if let Some(sender) = block_tx.as_ref() {
@@ -341,14 +393,23 @@ pub async fn run(
 				api::v2::types::Topic::ConfidenceAchieved,
 				sender.subscribe(),
 				ws_clients.clone(),
+				events.clone(),
 			));
 		}
 
+		tokio::task::spawn(api::v2::publish(
+			api::v2::types::Topic::TransactionStatus,
+			submissions.subscribe(),
+			ws_clients.clone(),
+			events.clone(),
+		));
+
 		if let Some(data_rx) = data_rx {
 			tokio::task::spawn(api::v2::publish(
 				api::v2::types::Topic::DataVerified,
 				data_rx,
 				ws_clients,
+				events,
 			));
 		}
 	} else {
@@ -387,6 +448,8 @@ pub async fn run(
 			cfg.crawl.crawl_block_delay,
 			ot_metrics.clone(),
 			cfg.crawl.crawl_block_mode,
+			sampling.clone(),
+			cfg.crawl.crawl_block_sample_size,
 		));
 	}
 
@@ -398,7 +461,8 @@ pub async fn run(
 			sync_client,
 			(&cfg).into(),
 			sync_range,
-			pp.clone(),
+			verifier.clone(),
+			sampling.clone(),
 			block_tx.clone(),
 			state.clone(),
 		));
@@ -419,7 +483,12 @@ pub async fn run(
 		s.finality_synced = true;
 	}
 
-	let light_client = light_client::new(db.clone(), p2p_client.clone(), rpc_client.clone());
+	let light_client = light_client::new(
+		db.clone(),
+		p2p_client.clone(),
+		rpc_client.clone(),
+		cell_cache,
+	);
 
 	let lc_channels = light_client::Channels {
 		block_sender: block_tx,
@@ -431,7 +500,8 @@ pub async fn run(
 		let err = tokio::task::spawn(light_client::run(
 			light_client,
 			(&cfg).into(),
-			pp,
+			verifier,
+			sampling,
 			ot_metrics,
 			state.clone(),
 			lc_channels,
@@ -444,7 +514,8 @@ pub async fn run(
 		tokio::task::spawn(light_client::run(
 			light_client,
 			(&cfg).into(),
-			pp,
+			verifier,
+			sampling,
 			ot_metrics,
 			state.clone(),
 			lc_channels,
@@ -494,6 +565,8 @@ pub async fn store_publish_messages<T: Clone + TryInto<PublishMessage>>(
 					let _ = store_data_verified_message_in_db(_db.clone(), message);
 				}
 			},
+			// Submissions are stored by the submitter
+			PublishMessage::TransactionStatus(_) => (),
 		}
 	}
 }
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use futures::future::Either;
 use kad_mem_store::{MemoryStore, MemoryStoreConfig};
 use libp2p::{
@@ -182,24 +182,34 @@ pub fn init(
 // Keypair function creates identity Keypair for a local node.
 // From such generated keypair it derives multihash identifier of the local peer.
 pub fn keypair(cfg: LibP2PConfig) -> Result<(libp2p::identity::Keypair, String)> {
-	let keypair = match cfg.secret_key {
+	let keypair = identity_keypair(cfg.secret_key)?;
+	let peer_id = PeerId::from(keypair.public()).to_string();
+	Ok((keypair, peer_id))
+}
+
+/// Creates identity keypair from the configured secret key
+pub fn identity_keypair(secret_key: Option<SecretKey>) -> Result<identity::Keypair> {
+	let keypair = match secret_key {
 		// If seed is provided, generate secret key from seed
 		Some(SecretKey::Seed { seed }) => {
-			let seed_digest = multihash::Sha3_256::digest(seed.as_bytes());
+			let seed_digest = multihash::Sha3_256::digest(seed.expose().as_bytes());
 			identity::Keypair::ed25519_from_bytes(seed_digest)
 				.context("error generating secret key from seed")?
 		},
 		// Import secret key if provided
 		Some(SecretKey::Key { key }) => {
 			let mut decoded_key = [0u8; 32];
-			hex::decode_to_slice(key.into_bytes(), &mut decoded_key)
+			hex::decode_to_slice(key.expose(), &mut decoded_key)
 				.context("error decoding secret key from config")?;
 			identity::Keypair::ed25519_from_bytes(decoded_key)
 				.context("error importing secret key")?
 		},
+		// Keystore keys are replaced with the decrypted secret keys once keystore is unlocked
+		Some(SecretKey::Keystore { keystore }) => {
+			return Err(anyhow!("Keystore key {keystore} is not unlocked"))
+		},
 		// If neither seed nor secret key provided, generate secret key from random seed
 		None => identity::Keypair::generate_ed25519(),
 	};
-	let peer_id = PeerId::from(keypair.public()).to_string();
-	Ok((keypair, peer_id))
+	Ok(keypair)
 }
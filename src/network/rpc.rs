@@ -1,13 +1,12 @@
 use anyhow::anyhow;
 use avail_subxt::{primitives::Header, utils::H256};
 use codec::Decode;
-use kate_recovery::matrix::{Dimensions, Position};
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng};
 use rocksdb::DB;
-use serde::{de, Deserialize};
+use schemars::JsonSchema;
+use serde::{de, Deserialize, Serialize};
 use sp_core::bytes::from_hex;
 use std::{
-	collections::HashSet,
 	fmt::Display,
 	sync::{Arc, Mutex},
 };
@@ -54,6 +53,67 @@ pub struct FinalityProof {
 	pub unknown_headers: Vec<Header>,
 }
 
+/// Merkle proof of data inclusion into the block data root
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataProof {
+	/// Root hash of generated merkle tree
+	#[schemars(with = "String")]
+	pub root: H256,
+	/// Proof items (does not contain the leaf hash, nor the root obviously)
+	#[schemars(with = "Vec<String>")]
+	pub proof: Vec<H256>,
+	/// Number of leaves in the original tree
+	pub number_of_leaves: u32,
+	/// Index of the leaf the proof is for (0-based)
+	pub leaf_index: u32,
+	/// Leaf content
+	#[schemars(with = "String")]
+	pub leaf: H256,
+}
+
+/// Weight of the extrinsic
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, JsonSchema)]
+pub struct Weight {
+	#[serde(alias = "refTime")]
+	pub ref_time: u64,
+	#[serde(alias = "proofSize")]
+	pub proof_size: u64,
+}
+
+/// Dispatch info and partial fee of the extrinsic, returned by the `payment_queryInfo` RPC
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentInfo {
+	pub weight: Weight,
+	pub class: String,
+	/// Fee without the tip
+	#[serde(deserialize_with = "balance")]
+	pub partial_fee: u128,
+}
+
+/// Balance is serialized as decimal string, or as number or hex string by the older nodes
+fn balance<'de, D>(deserializer: D) -> std::result::Result<u128, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Balance {
+		Number(u64),
+		String(String),
+	}
+
+	match Balance::deserialize(deserializer)? {
+		Balance::Number(balance) => Ok(balance.into()),
+		Balance::String(balance) => match balance.strip_prefix("0x") {
+			Some(hex) => u128::from_str_radix(hex, 16),
+			None => balance.parse(),
+		}
+		.map_err(de::Error::custom),
+	}
+}
+
 #[derive(Debug, Decode, Clone)]
 pub struct WrappedProof(pub FinalityProof);
 
@@ -181,26 +241,6 @@ pub fn init(
 	)
 }
 
-/// Generates random cell positions for sampling
-pub fn generate_random_cells(dimensions: Dimensions, cell_count: u32) -> Vec<Position> {
-	let max_cells = dimensions.extended_size();
-	let count = if max_cells < cell_count {
-		debug!("Max cells count {max_cells} is lesser than cell_count {cell_count}");
-		max_cells
-	} else {
-		cell_count
-	};
-	let mut rng = thread_rng();
-	let mut indices = HashSet::new();
-	while (indices.len() as u16) < count as u16 {
-		let col = rng.gen_range(0..dimensions.cols().into());
-		let row = rng.gen_range(0..dimensions.extended_rows());
-		indices.insert(Position { row, col });
-	}
-
-	indices.into_iter().collect::<Vec<_>>()
-}
-
 /* @note: fn to take the number of cells needs to get equal to or greater than
 the percentage of confidence mentioned in config file */
 
@@ -238,3 +278,41 @@ pub async fn wait_for_finalized_header(
 		Err(_) => Err(anyhow!("Timeout on waiting for first finalized header")),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{PaymentInfo, Weight};
+	use test_case::test_case;
+
+	#[test_case(r#"124000000000"#, 124_000_000_000 ; "Number")]
+	#[test_case(r#""124000000000000000""#, 124_000_000_000_000_000 ; "Decimal string")]
+	#[test_case(r#""0x1b88957d0260000""#, 124_000_000_000_000_000 ; "Hex string")]
+	#[test_case(r#""340282366920938463463374607431768211455""#, u128::MAX ; "Maximum balance")]
+	fn payment_info_partial_fee(partial_fee: &str, expected: u128) {
+		let json = format!(
+			r#"{{"weight":{{"refTime":220000000,"proofSize":0}},"class":"normal","partialFee":{partial_fee}}}"#
+		);
+		let payment_info: PaymentInfo = serde_json::from_str(&json).unwrap();
+		assert_eq!(
+			payment_info,
+			PaymentInfo {
+				weight: Weight {
+					ref_time: 220_000_000,
+					proof_size: 0,
+				},
+				class: "normal".to_string(),
+				partial_fee: expected,
+			}
+		);
+	}
+
+	#[test_case(r#""0xzz""# ; "Invalid hex string")]
+	#[test_case(r#""1.5""# ; "Invalid decimal string")]
+	#[test_case(r#"-1"# ; "Negative number")]
+	fn payment_info_invalid_partial_fee(partial_fee: &str) {
+		let json = format!(
+			r#"{{"weight":{{"refTime":0,"proofSize":0}},"class":"normal","partialFee":{partial_fee}}}"#
+		);
+		assert!(serde_json::from_str::<PaymentInfo>(&json).is_err());
+	}
+}
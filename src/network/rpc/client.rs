@@ -9,6 +9,7 @@ use avail_subxt::{
 use kate_recovery::{data::Cell, matrix::Position};
 use sp_core::ed25519::{self, Public};
 use subxt::{
+	ext::sp_runtime::transaction_validity::TransactionValidity,
 	storage::StorageKey,
 	tx::{PairSigner, Payload, TxProgress},
 	utils::AccountId32,
@@ -16,7 +17,7 @@ use subxt::{
 };
 use tokio::sync::{mpsc, oneshot};
 
-use super::{Node, WrappedProof};
+use super::{DataProof, Node, PaymentInfo, WrappedProof};
 use crate::types::RuntimeVersion;
 
 #[derive(Clone)]
@@ -57,6 +58,21 @@ impl Client {
 			.context("RPC Command Sender not to be dropped.")?
 	}
 
+	/// Gets hashes of the block extrinsics, in the block order
+	pub async fn get_block_extrinsic_hashes(&self, block_hash: H256) -> Result<Vec<H256>> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::GetBlockExtrinsicHashes {
+				block_hash,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
 	pub async fn get_validator_set_by_hash(&self, block_hash: H256) -> Result<Vec<Public>> {
 		let (response_sender, response_receiver) = oneshot::channel();
 		self.command_sender
@@ -131,6 +147,25 @@ impl Client {
 			.context("RPC Command Sender not to be dropped.")?
 	}
 
+	pub async fn request_data_proof(
+		&self,
+		block_hash: H256,
+		transaction_index: u32,
+	) -> Result<DataProof> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::RequestDataProof {
+				transaction_index,
+				block_hash,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
 	pub async fn get_system_version(&self) -> Result<String> {
 		let (response_sender, response_receiver) = oneshot::channel();
 		self.command_sender
@@ -214,6 +249,7 @@ impl Client {
 		extrinsic: Payload<SubmitData>,
 		pair_signer: PairSigner<AvailConfig, Pair>,
 		params: AvailExtrinsicParams,
+		nonce: u32,
 	) -> Result<TxProgress<AvailConfig, OnlineClient<AvailConfig>>> {
 		let (response_sender, response_receiver) = oneshot::channel();
 		self.command_sender
@@ -221,6 +257,74 @@ impl Client {
 				extrinsic,
 				pair_signer: Box::new(pair_signer),
 				params,
+				nonce,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
+	/// Creates signed extrinsic, without submitting it
+	pub async fn create_signed(
+		&self,
+		extrinsic: Payload<SubmitData>,
+		pair_signer: PairSigner<AvailConfig, Pair>,
+		params: AvailExtrinsicParams,
+		nonce: u32,
+	) -> Result<Vec<u8>> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::CreateSigned {
+				extrinsic,
+				pair_signer: Box::new(pair_signer),
+				params,
+				nonce,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
+	pub async fn query_payment_info(&self, tx_bytes: Vec<u8>) -> Result<PaymentInfo> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::QueryPaymentInfo {
+				tx_bytes,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
+	/// Validates transaction against the state of the best block, including its signature and nonce
+	pub async fn validate_transaction(&self, tx_bytes: Vec<u8>) -> Result<TransactionValidity> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::ValidateTransaction {
+				tx_bytes,
+				response_sender,
+			})
+			.await
+			.context("RPC Command Receiver not be dropped")?;
+		response_receiver
+			.await
+			.context("RPC Command Sender not to be dropped.")?
+	}
+
+	pub async fn get_account_nonce(&self, account_id: AccountId32) -> Result<u32> {
+		let (response_sender, response_receiver) = oneshot::channel();
+		self.command_sender
+			.send(Command::GetAccountNonce {
+				account_id,
 				response_sender,
 			})
 			.await
@@ -324,6 +428,10 @@ pub enum Command {
 		block_hash: H256,
 		response_sender: oneshot::Sender<Result<Header>>,
 	},
+	GetBlockExtrinsicHashes {
+		block_hash: H256,
+		response_sender: oneshot::Sender<Result<Vec<H256>>>,
+	},
 	GetValidatorSetByHash {
 		block_hash: H256,
 		response_sender: oneshot::Sender<Result<Vec<Public>>>,
@@ -356,6 +464,11 @@ pub enum Command {
 		block_hash: H256,
 		response_sender: oneshot::Sender<Result<Vec<Cell>>>,
 	},
+	RequestDataProof {
+		transaction_index: u32,
+		block_hash: H256,
+		response_sender: oneshot::Sender<Result<DataProof>>,
+	},
 	GetSystemVersion {
 		response_sender: oneshot::Sender<Result<String>>,
 	},
@@ -382,9 +495,29 @@ pub enum Command {
 		extrinsic: Payload<SubmitData>,
 		pair_signer: Box<PairSigner<AvailConfig, Pair>>,
 		params: AvailExtrinsicParams,
+		nonce: u32,
 		response_sender:
 			oneshot::Sender<Result<TxProgress<AvailConfig, OnlineClient<AvailConfig>>>>,
 	},
+	GetAccountNonce {
+		account_id: AccountId32,
+		response_sender: oneshot::Sender<Result<u32>>,
+	},
+	CreateSigned {
+		extrinsic: Payload<SubmitData>,
+		pair_signer: Box<PairSigner<AvailConfig, Pair>>,
+		params: AvailExtrinsicParams,
+		nonce: u32,
+		response_sender: oneshot::Sender<Result<Vec<u8>>>,
+	},
+	QueryPaymentInfo {
+		tx_bytes: Vec<u8>,
+		response_sender: oneshot::Sender<Result<PaymentInfo>>,
+	},
+	ValidateTransaction {
+		tx_bytes: Vec<u8>,
+		response_sender: oneshot::Sender<Result<TransactionValidity>>,
+	},
 	GetPagedStorageKeys {
 		key: Vec<u8>,
 		count: u32,
@@ -8,20 +8,21 @@ use avail_subxt::{
 	utils::H256,
 	AvailConfig,
 };
-use codec::Encode;
+use codec::{Decode, Encode};
 use futures::Stream;
 use kate_recovery::{data::Cell, matrix::Position};
 use rocksdb::DB;
 use sp_core::{
 	blake2_256,
 	ed25519::{self, Public},
-	Pair,
+	Bytes, Pair,
 };
 use std::{
 	sync::{Arc, Mutex},
 	time::Instant,
 };
 use subxt::{
+	ext::sp_runtime::transaction_validity::{TransactionSource, TransactionValidity},
 	rpc::{types::BlockNumber, RpcParams},
 	rpc_params,
 	storage::StorageKey,
@@ -31,9 +32,12 @@ use subxt::{
 };
 use tokio::sync::{broadcast::Sender, mpsc};
 use tokio_stream::StreamExt;
-use tracing::{info, instrument, trace, warn};
+use tracing::{info, info_span, instrument, trace, warn, Span};
 
-use super::{client::Command, ExpectedVersion, Nodes, WrappedProof, CELL_WITH_PROOF_SIZE};
+use super::{
+	client::Command, DataProof, ExpectedVersion, Nodes, PaymentInfo, WrappedProof,
+	CELL_WITH_PROOF_SIZE,
+};
 use crate::{
 	data::store_finality_sync_checkpoint,
 	types::{
@@ -48,6 +52,8 @@ pub enum Event {
 	HeaderUpdate {
 		header: Header,
 		received_at: Instant,
+		/// Root span of the block processing, propagated to the block consumers
+		span: Span,
 	},
 }
 
@@ -211,23 +217,33 @@ impl EventLoop {
 		// try to get latest Finalized Block Data and set values
 		self.gather_block_data().await?;
 
-		loop {
+		self.state.lock().unwrap().rpc_connected = true;
+		let result = loop {
 			tokio::select! {
-				subscription = subscriptions_stream.next() => self.handle_subscription_stream(subscription.expect("RPC Subscription stream should be infinite")).await,
+				subscription = subscriptions_stream.next() => match subscription {
+					Some(subscription) => self.handle_subscription_stream(subscription).await,
+					None => break Err(anyhow!("RPC Subscription stream ended")),
+				},
 				command = self.command_receiver.recv() => match command {
 					Some(c) => self.handle_command(c).await,
 					// Command channel closed, thus shutting down the RPC Event Loop
-					None => return Err(anyhow!("RPC Event Loop shutting down")),
+					None => break Err(anyhow!("RPC Event Loop shutting down")),
 				},
 			}
-		}
+		};
+		self.state.lock().unwrap().rpc_connected = false;
+		result
 	}
 
 	async fn handle_subscription_stream(&mut self, subscription: Subscription) {
 		match subscription {
 			Subscription::Header(header) => {
 				let received_at = Instant::now();
-				self.state.lock().unwrap().latest = header.clone().number;
+				{
+					let mut state = self.state.lock().unwrap();
+					state.latest = header.number;
+					state.latest_header_received_at = Some(received_at);
+				}
 				info!("Header no.: {}", header.number);
 				// push new Unverified Header
 				self.block_data
@@ -372,6 +388,7 @@ impl EventLoop {
 						// send as output event
 						self.event_sender
 							.send(Event::HeaderUpdate {
+								span: info_span!("block", block_number = header.number),
 								header,
 								received_at,
 							})
@@ -391,6 +408,7 @@ impl EventLoop {
 					.set(header.number);
 				self.event_sender
 					.send(Event::HeaderUpdate {
+						span: info_span!("block", block_number = header.number),
 						header,
 						received_at,
 					})
@@ -427,6 +445,13 @@ impl EventLoop {
 				let res = self.get_header_by_hash(block_hash).await;
 				_ = response_sender.send(res);
 			},
+			Command::GetBlockExtrinsicHashes {
+				block_hash,
+				response_sender,
+			} => {
+				let res = self.get_block_extrinsic_hashes(block_hash).await;
+				_ = response_sender.send(res);
+			},
 			Command::GetValidatorSetByHash {
 				block_hash,
 				response_sender,
@@ -479,6 +504,14 @@ impl EventLoop {
 				let res = self.request_kate_proof(&positions, block_hash).await;
 				_ = response_sender.send(res)
 			},
+			Command::RequestDataProof {
+				transaction_index,
+				block_hash,
+				response_sender,
+			} => {
+				let res = self.request_data_proof(transaction_index, block_hash).await;
+				_ = response_sender.send(res)
+			},
 			Command::GetConnectedNode { response_sender } => {
 				let node = self
 					.nodes
@@ -511,13 +544,45 @@ impl EventLoop {
 				extrinsic,
 				pair_signer,
 				params,
+				nonce,
 				response_sender,
 			} => {
 				let res = self
-					.submit_signed_and_watch(extrinsic, pair_signer.as_ref(), params)
+					.submit_signed_and_watch(extrinsic, pair_signer.as_ref(), params, nonce)
 					.await;
 				_ = response_sender.send(res);
 			},
+			Command::GetAccountNonce {
+				account_id,
+				response_sender,
+			} => {
+				let res = self.get_account_nonce(&account_id).await;
+				_ = response_sender.send(res);
+			},
+			Command::CreateSigned {
+				extrinsic,
+				pair_signer,
+				params,
+				nonce,
+				response_sender,
+			} => {
+				let res = self.create_signed(extrinsic, pair_signer.as_ref(), params, nonce);
+				_ = response_sender.send(res);
+			},
+			Command::QueryPaymentInfo {
+				tx_bytes,
+				response_sender,
+			} => {
+				let res = self.query_payment_info(tx_bytes).await;
+				_ = response_sender.send(res);
+			},
+			Command::ValidateTransaction {
+				tx_bytes,
+				response_sender,
+			} => {
+				let res = self.validate_transaction(tx_bytes).await;
+				_ = response_sender.send(res);
+			},
 			Command::GetPagedStorageKeys {
 				key,
 				count,
@@ -584,6 +649,22 @@ impl EventLoop {
 			.ok_or_else(|| anyhow!("Block Header with hash: {block_hash:?} not found"))
 	}
 
+	async fn get_block_extrinsic_hashes(&self, block_hash: H256) -> Result<Vec<H256>> {
+		let block = self
+			.unpack_client()?
+			.rpc()
+			.block(Some(block_hash))
+			.await?
+			.ok_or_else(|| anyhow!("Block with hash: {block_hash:?} not found"))?;
+
+		Ok(block
+			.block
+			.extrinsics
+			.iter()
+			.map(|extrinsic| blake2_256(&extrinsic.0).into())
+			.collect())
+	}
+
 	async fn get_validator_set_by_hash(&self, block_hash: H256) -> Result<Vec<Public>> {
 		let valset = self
 			.unpack_client()?
@@ -702,14 +783,78 @@ impl EventLoop {
 		extrinsic: Payload<SubmitData>,
 		pair_signer: &PairSigner<AvailConfig, avail::Pair>,
 		params: AvailExtrinsicParams,
+		nonce: u32,
 	) -> Result<TxProgress<AvailConfig, OnlineClient<AvailConfig>>> {
 		self.unpack_client()?
 			.tx()
-			.sign_and_submit_then_watch(&extrinsic, pair_signer, params)
+			.create_signed_with_nonce(&extrinsic, pair_signer, nonce, params)
+			.map_err(|e| anyhow!(e))?
+			.submit_and_watch()
 			.await
 			.map_err(|e| anyhow!(e))
 	}
 
+	fn create_signed(
+		&self,
+		extrinsic: Payload<SubmitData>,
+		pair_signer: &PairSigner<AvailConfig, avail::Pair>,
+		params: AvailExtrinsicParams,
+		nonce: u32,
+	) -> Result<Vec<u8>> {
+		self.unpack_client()?
+			.tx()
+			.create_signed_with_nonce(&extrinsic, pair_signer, nonce, params)
+			.map(|extrinsic| extrinsic.encoded().to_vec())
+			.map_err(|e| anyhow!(e))
+	}
+
+	async fn query_payment_info(&self, tx_bytes: Vec<u8>) -> Result<PaymentInfo> {
+		let mut params = RpcParams::new();
+		params.push(Bytes(tx_bytes))?;
+
+		self.unpack_client()?
+			.rpc()
+			.request("payment_queryInfo", params)
+			.await
+			.map_err(|e| anyhow!("Failed to query Payment Info. Error: {e}"))
+	}
+
+	async fn validate_transaction(&self, tx_bytes: Vec<u8>) -> Result<TransactionValidity> {
+		let client = self.unpack_client()?;
+		let block_hash = client
+			.rpc()
+			.block_hash(None)
+			.await?
+			.context("Best block hash is not available")?;
+
+		// Arguments of the `TaggedTransactionQueue_validate_transaction` runtime API (version 3)
+		let args = [
+			TransactionSource::External.encode(),
+			tx_bytes,
+			block_hash.encode(),
+		]
+		.concat();
+		let mut params = RpcParams::new();
+		params.push("TaggedTransactionQueue_validate_transaction")?;
+		params.push(Bytes(args))?;
+		params.push(block_hash)?;
+
+		let result: Bytes = client
+			.rpc()
+			.request("state_call", params)
+			.await
+			.map_err(|e| anyhow!("Failed to validate transaction. Error: {e}"))?;
+		decode_transaction_validity(&result)
+	}
+
+	async fn get_account_nonce(&self, account_id: &AccountId32) -> Result<u32> {
+		self.unpack_client()?
+			.rpc()
+			.system_account_next_index(account_id)
+			.await
+			.context("Failed to get account nonce")
+	}
+
 	async fn submit_from_bytes_and_watch(
 		&self,
 		tx_bytes: Vec<u8>,
@@ -753,6 +898,22 @@ impl EventLoop {
 			.map_err(|e| anyhow!(e))
 	}
 
+	async fn request_data_proof(
+		&self,
+		transaction_index: u32,
+		block_hash: H256,
+	) -> Result<DataProof> {
+		let mut params = RpcParams::new();
+		params.push(transaction_index)?;
+		params.push(block_hash)?;
+
+		self.unpack_client()?
+			.rpc()
+			.request("kate_queryDataProof", params)
+			.await
+			.map_err(|e| anyhow!("Failed to query Data Proof. Error: {e}"))
+	}
+
 	async fn request_finality_proof(&self, block_number: u32) -> Result<WrappedProof> {
 		let mut params = RpcParams::new();
 		params.push(block_number)?;
@@ -777,9 +938,55 @@ fn is_signed_by_supermajority(num_signatures: usize, validator_set_size: usize)
 	num_signatures >= supermajority
 }
 
+/// Decodes result of the `TaggedTransactionQueue_validate_transaction` runtime API call
+fn decode_transaction_validity(result: &[u8]) -> Result<TransactionValidity> {
+	TransactionValidity::decode(&mut &result[..]).context("Failed to decode transaction validity")
+}
+
 #[cfg(test)]
 mod tests {
+	use super::decode_transaction_validity;
+	use hex_literal::hex;
+	use subxt::ext::sp_runtime::transaction_validity::{
+		InvalidTransaction, TransactionValidityError, UnknownTransaction, ValidTransaction,
+	};
 	use test_case::test_case;
+
+	#[test]
+	fn decode_valid_transaction() {
+		// Ok, priority 1, no requires, provides [0x0102], longevity 64, propagate
+		let result = hex!("00 0100000000000000 00 04080102 4000000000000000 01");
+		let expected = ValidTransaction {
+			priority: 1,
+			requires: vec![],
+			provides: vec![vec![1, 2]],
+			longevity: 64,
+			propagate: true,
+		};
+		assert_eq!(decode_transaction_validity(&result).unwrap(), Ok(expected));
+	}
+
+	#[test_case(&hex!("010003"), TransactionValidityError::Invalid(InvalidTransaction::Stale), "Transaction is outdated" ; "Stale")]
+	#[test_case(&hex!("010002"), TransactionValidityError::Invalid(InvalidTransaction::Future), "Transaction will be valid in the future" ; "Future")]
+	#[test_case(&hex!("010004"), TransactionValidityError::Invalid(InvalidTransaction::BadProof), "Transaction has a bad signature" ; "Bad proof")]
+	#[test_case(&hex!("010100"), TransactionValidityError::Unknown(UnknownTransaction::CannotLookup), "Could not lookup information required to validate the transaction" ; "Cannot lookup")]
+	fn decode_invalid_transaction(
+		result: &[u8],
+		expected: TransactionValidityError,
+		message: &str,
+	) {
+		let error = decode_transaction_validity(result).unwrap().unwrap_err();
+		assert_eq!(error, expected);
+		assert_eq!(<&str>::from(error), message);
+	}
+
+	#[test_case(&[] ; "Empty")]
+	#[test_case(&hex!("02") ; "Invalid variant")]
+	#[test_case(&hex!("0100") ; "Truncated")]
+	fn decode_invalid_validity(result: &[u8]) {
+		assert!(decode_transaction_validity(result).is_err());
+	}
+
 	#[test_case(1, 1 => true)]
 	#[test_case(1, 2 => false)]
 	#[test_case(2, 2 => true)]
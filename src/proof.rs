@@ -1,5 +1,8 @@
-//! Parallelized proof verification
+//! Parallelized proof verification, and verification of data inclusion proofs
 
+use anyhow::{anyhow, Context, Result};
+use avail_subxt::{primitives::Header, utils::H256};
+use codec::Encode;
 use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
 use itertools::{Either, Itertools};
 use kate_recovery::{
@@ -7,49 +10,628 @@ use kate_recovery::{
 	matrix::{Dimensions, Position},
 	proof,
 };
-use std::sync::{mpsc::channel, Arc};
-use tracing::error;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sp_core::{blake2_256, keccak_256};
+use std::{
+	sync::{mpsc::channel, Arc},
+	time::Instant,
+};
+use threadpool::ThreadPool;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, error};
+
+use crate::{
+	network::rpc::{self, DataProof},
+	telemetry::{MetricValue, Metrics},
+	types::VerifierConfig,
+	utils::extract_kate,
+};
 
-/// Verifies proofs for given block, cells and commitments
-pub fn verify(
+/// Verification priority. Queued live blocks are verified before blocks being synced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+	Live,
+	Sync,
+}
+
+type VerificationResult = Result<(Vec<Position>, Vec<Position>), proof::Error>;
+
+struct Job {
 	block_num: u32,
 	dimensions: Dimensions,
-	cells: &[Cell],
-	commitments: &[[u8; 48]],
+	cells: Vec<Cell>,
+	commitments: Vec<[u8; 48]>,
+	queued_at: Instant,
+	response_sender: oneshot::Sender<VerificationResult>,
+}
+
+/// Long-lived proof verification service, shared by the light, sync and app clients.
+///
+/// Verification requests are queued into bounded per-priority queues.
+/// Queued requests (from several blocks) are batched into a single verification job,
+/// which is executed on the shared worker pool.
+#[derive(Clone)]
+pub struct Verifier {
+	live_sender: mpsc::Sender<Job>,
+	sync_sender: mpsc::Sender<Job>,
+	metrics: Arc<dyn Metrics + Send + Sync>,
+}
+
+impl Verifier {
+	/// Creates verifier and spawns its dispatcher task on the current runtime
+	pub fn new(
+		public_parameters: Arc<PublicParameters>,
+		cfg: &VerifierConfig,
+		metrics: Arc<impl Metrics + Send + Sync + 'static>,
+	) -> Self {
+		let (live_sender, live_receiver) = mpsc::channel(cfg.queue_size);
+		let (sync_sender, sync_receiver) = mpsc::channel(cfg.queue_size);
+		let dispatcher = Dispatcher {
+			public_parameters,
+			pool: ThreadPool::new(cfg.threads),
+			live_receiver,
+			sync_receiver,
+			batch_size: cfg.batch_size,
+		};
+		tokio::spawn(dispatcher.run(metrics.clone()));
+		Verifier {
+			live_sender,
+			sync_sender,
+			metrics,
+		}
+	}
+
+	/// Verifies proofs for given block, cells and commitments.
+	/// Returns verified and unverified positions.
+	pub async fn verify(
+		&self,
+		priority: Priority,
+		block_num: u32,
+		dimensions: Dimensions,
+		cells: &[Cell],
+		commitments: &[[u8; 48]],
+	) -> Result<(Vec<Position>, Vec<Position>)> {
+		if cells.is_empty() {
+			return Ok((vec![], vec![]));
+		}
+
+		let (response_sender, response_receiver) = oneshot::channel();
+		let job = Job {
+			block_num,
+			dimensions,
+			cells: cells.to_vec(),
+			commitments: commitments.to_vec(),
+			queued_at: Instant::now(),
+			response_sender,
+		};
+
+		let sender = match priority {
+			Priority::Live => &self.live_sender,
+			Priority::Sync => &self.sync_sender,
+		};
+
+		self.metrics
+			.record(MetricValue::ProofVerificationQueueDepthChange(1));
+		if sender.send(job).await.is_err() {
+			self.metrics
+				.record(MetricValue::ProofVerificationQueueDepthChange(-1));
+			return Err(anyhow!("Proof verifier is not running"));
+		}
+
+		response_receiver
+			.await
+			.context("Proof verifier dropped verification job")?
+			.context("Failed to verify proofs")
+	}
+}
+
+struct Dispatcher {
+	public_parameters: Arc<PublicParameters>,
+	pool: ThreadPool,
+	live_receiver: mpsc::Receiver<Job>,
+	sync_receiver: mpsc::Receiver<Job>,
+	batch_size: usize,
+}
+
+impl Dispatcher {
+	fn try_recv(&mut self) -> Option<Job> {
+		self.live_receiver
+			.try_recv()
+			.or_else(|_| self.sync_receiver.try_recv())
+			.ok()
+	}
+
+	/// Waits for the next job, and batches it with already queued jobs, live blocks first.
+	/// Returns `None` once all verifier handles are dropped.
+	async fn recv_batch(&mut self) -> Option<Vec<Job>> {
+		let job = tokio::select! {
+			biased;
+			Some(job) = self.live_receiver.recv() => job,
+			Some(job) = self.sync_receiver.recv() => job,
+			else => return None,
+		};
+
+		let mut cells_count = job.cells.len();
+		let mut jobs = vec![job];
+		while cells_count < self.batch_size {
+			let Some(job) = self.try_recv() else {
+				break;
+			};
+			cells_count += job.cells.len();
+			jobs.push(job);
+		}
+		Some(jobs)
+	}
+
+	async fn run(mut self, metrics: Arc<impl Metrics>) {
+		while let Some(jobs) = self.recv_batch().await {
+			let cells_count = jobs.iter().map(|job| job.cells.len()).sum::<usize>();
+			debug!("Verifying {cells_count} cells from {} jobs", jobs.len());
+			metrics.record(MetricValue::ProofVerificationQueueDepthChange(
+				-(jobs.len() as i64),
+			));
+
+			let pool = self.pool.clone();
+			let public_parameters = self.public_parameters.clone();
+			let latencies = match tokio::task::spawn_blocking(move || {
+				verify_batch(&pool, public_parameters, jobs)
+			})
+			.await
+			{
+				Ok(latencies) => latencies,
+				Err(error) => {
+					error!("Proof verification job failed: {error}");
+					continue;
+				},
+			};
+
+			for latency in latencies {
+				metrics.record(MetricValue::ProofVerificationLatency(latency));
+			}
+		}
+	}
+}
+
+/// Creates verifier with default configuration and no-op metrics, used in tests
+#[cfg(test)]
+pub(crate) fn test_verifier(public_parameters: Arc<PublicParameters>) -> Verifier {
+	let mut metrics = crate::telemetry::MockMetrics::new();
+	metrics.expect_record().returning(|_| ());
+	let cfg = VerifierConfig::from(&crate::types::RuntimeConfig::default());
+	Verifier::new(public_parameters, &cfg, Arc::new(metrics))
+}
+
+/// Verifies cells of all jobs on the worker pool, and sends results to the job owners.
+/// Returns latencies (in seconds) of the verified jobs.
+fn verify_batch(
+	pool: &ThreadPool,
 	public_parameters: Arc<PublicParameters>,
-) -> Result<(Vec<Position>, Vec<Position>), proof::Error> {
-	let cpus = num_cpus::get();
-	let pool = threadpool::ThreadPool::new(cpus);
-	let (tx, rx) = channel::<(Position, Result<bool, proof::Error>)>();
+	jobs: Vec<Job>,
+) -> Vec<f64> {
+	let (tx, rx) = channel::<(usize, Position, Result<bool, proof::Error>)>();
 
-	for cell in cells {
-		let commitment = commitments[cell.position.row as usize];
+	for (index, job) in jobs.iter().enumerate() {
+		for cell in &job.cells {
+			// Cell with out of range row cannot be verified, so it is reported as failed
+			let Some(&commitment) = job.commitments.get(cell.position.row as usize) else {
+				if let Err(error) = tx.send((index, cell.position, Ok(false))) {
+					error!(
+						block_num = job.block_num,
+						"Failed to send proof verified message: {error}"
+					);
+				}
+				continue;
+			};
 
-		let tx = tx.clone();
-		let cell = cell.clone();
-		let public_parameters = public_parameters.clone();
+			let tx = tx.clone();
+			let cell = cell.clone();
+			let dimensions = job.dimensions;
+			let block_num = job.block_num;
+			let public_parameters = public_parameters.clone();
 
-		pool.execute(move || {
-			let result = proof::verify(&public_parameters, dimensions, &commitment, &cell);
-			if let Err(error) = tx.clone().send((cell.position, result)) {
+			pool.execute(move || {
+				let result = proof::verify(&public_parameters, dimensions, &commitment, &cell);
+				if let Err(error) = tx.send((index, cell.position, result)) {
+					error!(block_num, "Failed to send proof verified message: {error}");
+				}
+			});
+		}
+	}
+	// Channel is closed once all workers are done
+	drop(tx);
+
+	let mut results = jobs.iter().map(|_| vec![]).collect::<Vec<_>>();
+	for (index, position, result) in rx.iter() {
+		results[index].push((position, result));
+	}
+
+	jobs.into_iter()
+		.zip(results)
+		.map(|(job, results)| {
+			let result = results
+				.into_iter()
+				.map(|(position, result)| result.map(|is_verified| (position, is_verified)))
+				.collect::<Result<Vec<(Position, bool)>, _>>()
+				.map(|results| {
+					results
+						.into_iter()
+						.partition_map(|(position, is_verified)| match is_verified {
+							true => Either::Left(position),
+							false => Either::Right(position),
+						})
+				});
+
+			let latency = job.queued_at.elapsed().as_secs_f64();
+			if job.response_sender.send(result).is_err() {
 				error!(
-					"{} {} {error}",
-					block_num, "Failed to send proof verified message"
+					block_number = job.block_num,
+					"Failed to send proof verification result"
 				);
 			}
-		});
+			latency
+		})
+		.collect()
+}
+
+/// Verifies merkle proof of data inclusion against given data root.
+///
+/// Proof is verified against binary merkle tree with Keccak-256 hashing,
+/// where the last node of the odd-sized level is promoted to the next level.
+pub fn verify_data_proof(data_root: H256, data_proof: &DataProof) -> bool {
+	if data_proof.root != data_root || data_proof.leaf_index >= data_proof.number_of_leaves {
+		return false;
+	}
+
+	let mut computed = keccak_256(data_proof.leaf.as_bytes());
+	let mut position = data_proof.leaf_index;
+	let mut width = data_proof.number_of_leaves;
+	let mut proof = data_proof.proof.iter();
+
+	while width > 1 {
+		// Last node of the odd-sized level is promoted without hashing
+		if !(position % 2 == 0 && position + 1 == width) {
+			let Some(item) = proof.next() else {
+				return false;
+			};
+			let mut combined = [0u8; 64];
+			let (left, right) = match position % 2 {
+				0 => (computed.as_slice(), item.as_bytes()),
+				_ => (item.as_bytes(), computed.as_slice()),
+			};
+			combined[..32].copy_from_slice(left);
+			combined[32..].copy_from_slice(right);
+			computed = keccak_256(&combined);
+		}
+		position /= 2;
+		width = (width + 1) / 2;
+	}
+
+	proof.next().is_none() && H256(computed) == data_root
+}
+
+/// Checks that the data proof is for the requested transaction, and valid against the data root
+fn check_data_proof(data_root: H256, transaction_index: u32, data_proof: &DataProof) -> Result<()> {
+	if data_proof.leaf_index != transaction_index {
+		return Err(anyhow!(
+			"Data proof is for transaction {}, requested transaction {transaction_index}",
+			data_proof.leaf_index
+		));
 	}
 
-	let (verified, unverified): (Vec<_>, Vec<_>) = rx
-		.iter()
-		.take(cells.len())
-		.map(|(position, result)| result.map(|is_verified| (position, is_verified)))
-		.collect::<Result<Vec<(Position, bool)>, _>>()?
-		.into_iter()
-		.partition_map(|(position, is_verified)| match is_verified {
-			true => Either::Left(position),
-			false => Either::Right(position),
+	if !verify_data_proof(data_root, data_proof) {
+		return Err(anyhow!(
+			"Data proof for transaction {transaction_index} is not valid against data root {data_root:?}"
+		));
+	}
+
+	Ok(())
+}
+
+/// Self-contained proof of data inclusion into the block
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct DataInclusionProof {
+	pub block_number: u32,
+	#[schemars(with = "String")]
+	pub block_hash: H256,
+	#[schemars(with = "String")]
+	pub data_root: H256,
+	pub transaction_index: u32,
+	pub data_proof: DataProof,
+}
+
+/// Fetches data inclusion proof for the transaction with given index from the node,
+/// and verifies it against the data root of the given (finality verified) header.
+pub async fn fetch_data_inclusion_proof(
+	rpc_client: &rpc::Client,
+	header: &Header,
+	transaction_index: u32,
+) -> Result<DataInclusionProof> {
+	let block_hash: H256 = Encode::using_encoded(header, blake2_256).into();
+	let (_, _, data_root, _) = extract_kate(&header.extension);
+
+	let data_proof = rpc_client
+		.request_data_proof(block_hash, transaction_index)
+		.await
+		.context("Failed to fetch data proof")?;
+
+	check_data_proof(data_root, transaction_index, &data_proof)?;
+
+	Ok(DataInclusionProof {
+		block_number: header.number,
+		block_hash,
+		data_root,
+		transaction_index,
+		data_proof,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		check_data_proof, verify_batch, verify_data_proof, Dispatcher, Job, Priority, Verifier,
+	};
+	use crate::{
+		network::rpc::DataProof,
+		telemetry::{MetricValue, MockMetrics},
+		types::{RuntimeConfig, VerifierConfig},
+	};
+	use avail_subxt::utils::H256;
+	use kate_recovery::{
+		data::Cell,
+		matrix::{Dimensions, Position},
+		testnet,
+	};
+	use sp_core::keccak_256;
+	use std::{
+		sync::{
+			atomic::{AtomicI64, Ordering},
+			Arc,
+		},
+		time::Instant,
+	};
+	use test_case::test_case;
+	use threadpool::ThreadPool;
+	use tokio::sync::{mpsc, oneshot};
+
+	fn job(block_num: u32, rows: &[u32]) -> (Job, oneshot::Receiver<super::VerificationResult>) {
+		let (response_sender, response_receiver) = oneshot::channel();
+		let cells = rows
+			.iter()
+			.map(|&row| Cell {
+				position: Position { row, col: 0 },
+				content: [0u8; 80],
+			})
+			.collect();
+		let job = Job {
+			block_num,
+			dimensions: Dimensions::new(1, 4).unwrap(),
+			cells,
+			commitments: vec![],
+			queued_at: Instant::now(),
+			response_sender,
+		};
+		(job, response_receiver)
+	}
+
+	fn dispatcher(batch_size: usize) -> (Dispatcher, mpsc::Sender<Job>, mpsc::Sender<Job>) {
+		let (live_sender, live_receiver) = mpsc::channel(10);
+		let (sync_sender, sync_receiver) = mpsc::channel(10);
+		let dispatcher = Dispatcher {
+			public_parameters: Arc::new(testnet::public_params(1024)),
+			pool: ThreadPool::new(1),
+			live_receiver,
+			sync_receiver,
+			batch_size,
+		};
+		(dispatcher, live_sender, sync_sender)
+	}
+
+	fn block_numbers(jobs: &[Job]) -> Vec<u32> {
+		jobs.iter().map(|job| job.block_num).collect()
+	}
+
+	#[tokio::test]
+	async fn recv_batch_live_first() {
+		let (mut dispatcher, live_sender, sync_sender) = dispatcher(2);
+		let mut receivers = vec![];
+		for (sender, block_num) in [(&sync_sender, 1), (&live_sender, 2), (&live_sender, 3)] {
+			let (job, receiver) = job(block_num, &[0]);
+			sender.send(job).await.unwrap();
+			receivers.push(receiver);
+		}
+
+		let batch = dispatcher.recv_batch().await.unwrap();
+		assert_eq!(block_numbers(&batch), vec![2, 3]);
+		let batch = dispatcher.recv_batch().await.unwrap();
+		assert_eq!(block_numbers(&batch), vec![1]);
+
+		drop((live_sender, sync_sender));
+		assert!(dispatcher.recv_batch().await.is_none());
+	}
+
+	#[tokio::test]
+	async fn recv_batch_limited_by_cells() {
+		let (mut dispatcher, live_sender, sync_sender) = dispatcher(3);
+		let mut receivers = vec![];
+		for (sender, block_num, rows) in [
+			(&live_sender, 1, vec![0, 0]),
+			(&sync_sender, 2, vec![0]),
+			(&sync_sender, 3, vec![0]),
+		] {
+			let (job, receiver) = job(block_num, &rows);
+			sender.send(job).await.unwrap();
+			receivers.push(receiver);
+		}
+
+		// Batch is closed once it reaches the batch size
+		let batch = dispatcher.recv_batch().await.unwrap();
+		assert_eq!(block_numbers(&batch), vec![1, 2]);
+		let batch = dispatcher.recv_batch().await.unwrap();
+		assert_eq!(block_numbers(&batch), vec![3]);
+	}
+
+	#[test]
+	fn verify_batch_out_of_range_row() {
+		let (job, mut receiver) = job(1, &[4]);
+		let pool = ThreadPool::new(1);
+		let latencies = verify_batch(&pool, Arc::new(testnet::public_params(1024)), vec![job]);
+		assert_eq!(latencies.len(), 1);
+
+		let (verified, unverified) = receiver.try_recv().unwrap().unwrap();
+		assert!(verified.is_empty());
+		assert_eq!(unverified, vec![Position { row: 4, col: 0 }]);
+	}
+
+	#[tokio::test]
+	async fn verify_tracks_queue_depth() {
+		let queue_depth = Arc::new(AtomicI64::new(0));
+		let mut metrics = MockMetrics::new();
+		let depth = queue_depth.clone();
+		metrics.expect_record().returning(move |value| {
+			if let MetricValue::ProofVerificationQueueDepthChange(delta) = value {
+				depth.fetch_add(delta, Ordering::SeqCst);
+			}
 		});
+		let cfg = VerifierConfig::from(&RuntimeConfig::default());
+		let verifier = Verifier::new(
+			Arc::new(testnet::public_params(1024)),
+			&cfg,
+			Arc::new(metrics),
+		);
+
+		// Cell with out of range row is reported as unverified without computing proofs
+		let cells = job(1, &[4]).0.cells;
+		let dimensions = Dimensions::new(1, 4).unwrap();
+		let (_, unverified) = verifier
+			.verify(Priority::Live, 1, dimensions, &cells, &[])
+			.await
+			.unwrap();
+		assert_eq!(unverified.len(), 1);
+		assert_eq!(queue_depth.load(Ordering::SeqCst), 0);
+	}
+
+	fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+		keccak_256(&[left.as_slice(), right.as_slice()].concat())
+	}
+
+	// Builds merkle tree levels, promoting the last node of odd-sized levels
+	fn levels(leaves: &[H256]) -> Vec<Vec<[u8; 32]>> {
+		let mut levels = vec![leaves
+			.iter()
+			.map(|leaf| keccak_256(leaf.as_bytes()))
+			.collect::<Vec<_>>()];
+		while levels.last().unwrap().len() > 1 {
+			let next = levels
+				.last()
+				.unwrap()
+				.chunks(2)
+				.map(|pair| match pair {
+					[left, right] => hash_pair(left, right),
+					[single] => *single,
+					_ => unreachable!(),
+				})
+				.collect();
+			levels.push(next);
+		}
+		levels
+	}
+
+	fn data_proof(leaves: &[H256], leaf_index: usize) -> DataProof {
+		let levels = levels(leaves);
+		let mut position = leaf_index;
+		let mut proof = vec![];
+		for level in &levels[..levels.len() - 1] {
+			let sibling = if position % 2 == 0 {
+				position + 1
+			} else {
+				position - 1
+			};
+			if let Some(node) = level.get(sibling) {
+				proof.push(H256(*node));
+			}
+			position /= 2;
+		}
+		DataProof {
+			root: H256(levels.last().unwrap()[0]),
+			proof,
+			number_of_leaves: leaves.len() as u32,
+			leaf_index: leaf_index as u32,
+			leaf: leaves[leaf_index],
+		}
+	}
+
+	#[test_case(1, 0 ; "single leaf")]
+	#[test_case(2, 1 ; "two leaves")]
+	#[test_case(5, 0 ; "odd leaves, first leaf")]
+	#[test_case(5, 4 ; "odd leaves, promoted leaf")]
+	#[test_case(7, 5 ; "odd leaves, middle leaf")]
+	fn verify_data_proof_valid(leaves_count: u8, leaf_index: usize) {
+		let leaves = (0..leaves_count)
+			.map(|i| H256(keccak_256(&[i])))
+			.collect::<Vec<_>>();
+		let proof = data_proof(&leaves, leaf_index);
+		assert!(verify_data_proof(proof.root, &proof));
+	}
+
+	// Known-answer vectors computed independently of the tree above, using a standalone
+	// Keccak-256 binary merkle tree (last node of the odd-sized level promoted), over five
+	// leaves `keccak_256("data-{i}")`. Proofs are in the `kate_queryDataProof` response format.
+	#[test_case(r#"{
+		"root": "0xf0ab382521eef131d539b736e997a2641d1944f005d04a706c054dc89143ef57",
+		"proof": [
+			"0x5fc7a54154052de050eab4fdcc4eead7297a2806c3091ddf04bc559797df528a",
+			"0x8fae7a781d826259fc3012dfcb7a02a3d1d4f5b2959d36ebc4bf759ac28d30a0",
+			"0x155f6ebb5089c7f06eb535d3ea8bf091a134e98baec8b7d7afb056775ba2b3e0"
+		],
+		"numberOfLeaves": 5,
+		"leafIndex": 2,
+		"leaf": "0xe74817abdd7d52861950c863f79716f8644faf9b0902b210bc1d40ca77fc4be9"
+	}"#, 2 ; "middle leaf")]
+	#[test_case(r#"{
+		"root": "0xf0ab382521eef131d539b736e997a2641d1944f005d04a706c054dc89143ef57",
+		"proof": ["0x150637d2ec6738bdfa881e9ba626846f36853bb384b56337004d0a14e08a7df3"],
+		"numberOfLeaves": 5,
+		"leafIndex": 4,
+		"leaf": "0x7ffd138de02bbe45dcde52c62a295fc56db5110787c34cae7b86fc43ab3911dd"
+	}"#, 4 ; "promoted leaf")]
+	fn verify_data_proof_known_answer(json: &str, leaf_index: u8) {
+		let proof: DataProof = serde_json::from_str(json).unwrap();
+		let leaf = keccak_256(format!("data-{leaf_index}").as_bytes());
+		assert_eq!(proof.leaf, H256(leaf));
+		assert!(verify_data_proof(proof.root, &proof));
+
+		let mut invalid_proof = proof.clone();
+		invalid_proof.proof[0] = H256::zero();
+		assert!(!verify_data_proof(proof.root, &invalid_proof));
+	}
 
-	Ok((verified, unverified))
+	#[test]
+	fn verify_data_proof_invalid() {
+		let leaves = (0..4u8).map(|i| H256(keccak_256(&[i]))).collect::<Vec<_>>();
+		let proof = data_proof(&leaves, 2);
+		assert!(!verify_data_proof(H256::zero(), &proof));
+
+		let mut invalid_leaf = proof.clone();
+		invalid_leaf.leaf = leaves[1];
+		assert!(!verify_data_proof(proof.root, &invalid_leaf));
+
+		let mut invalid_index = proof.clone();
+		invalid_index.leaf_index = 3;
+		assert!(!verify_data_proof(proof.root, &invalid_index));
+
+		let mut extra_item = proof.clone();
+		extra_item.proof.push(H256::zero());
+		assert!(!verify_data_proof(proof.root, &extra_item));
+	}
+
+	#[test_case(2, 2, true => true ; "requested transaction")]
+	#[test_case(1, 2, true => false ; "other transaction")]
+	#[test_case(2, 2, false => false ; "other data root")]
+	fn check_data_proof_index(leaf_index: usize, transaction_index: u32, same_root: bool) -> bool {
+		let leaves = (0..4u8).map(|i| H256(keccak_256(&[i]))).collect::<Vec<_>>();
+		let proof = data_proof(&leaves, leaf_index);
+		let data_root = if same_root { proof.root } else { H256::zero() };
+		check_data_proof(data_root, transaction_index, &proof).is_ok()
+	}
 }
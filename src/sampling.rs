@@ -0,0 +1,236 @@
+//! Cell sampling strategies.
+//!
+//! Sampling strategy chooses positions of the cells which are fetched and verified in order to calculate block confidence.
+//! Besides uniformly random sampling, cells can be sampled stratified across rows and columns of the extended matrix,
+//! or deterministically, from the local secret and the block hash.
+//! Deterministically sampled positions are unpredictable to the network, but can be reproduced and audited by the client.
+
+use anyhow::{Context, Result};
+use avail_subxt::utils::H256;
+use kate_recovery::matrix::{Dimensions, Position};
+use rand::{seq::SliceRandom, thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+use sp_core::blake2_256;
+use std::{collections::HashSet, ops::Range, sync::Arc};
+use tracing::{debug, info};
+
+use crate::data::{get_sampling_secret_from_db, store_sampling_secret_in_db};
+
+/// Chooses cell positions for data availability sampling
+pub trait SamplingStrategy: Send + Sync {
+	/// Returns distinct positions of the extended matrix to sample for the given block.
+	/// Number of positions is limited by the extended matrix size.
+	fn positions(&self, block_hash: H256, dimensions: Dimensions, cell_count: u32)
+		-> Vec<Position>;
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SamplingMode {
+	#[default]
+	Uniform,
+	Stratified,
+	Deterministic,
+}
+
+/// Samples positions uniformly at random
+pub struct Uniform;
+
+impl SamplingStrategy for Uniform {
+	fn positions(&self, _: H256, dimensions: Dimensions, cell_count: u32) -> Vec<Position> {
+		let count = cells_to_sample(dimensions, cell_count);
+		uniform_positions(&mut thread_rng(), dimensions, count)
+	}
+}
+
+/// Samples positions at random, spread across distinct row and column strata of the extended matrix
+pub struct Stratified;
+
+impl SamplingStrategy for Stratified {
+	fn positions(&self, _: H256, dimensions: Dimensions, cell_count: u32) -> Vec<Position> {
+		let count = cells_to_sample(dimensions, cell_count);
+		stratified_positions(&mut thread_rng(), dimensions, count)
+	}
+}
+
+/// Samples positions uniformly, using random generator seeded from the local secret and the block hash
+pub struct KeyedDeterministic {
+	secret: [u8; 32],
+}
+
+impl KeyedDeterministic {
+	pub fn new(secret: [u8; 32]) -> Self {
+		KeyedDeterministic { secret }
+	}
+}
+
+impl SamplingStrategy for KeyedDeterministic {
+	fn positions(
+		&self,
+		block_hash: H256,
+		dimensions: Dimensions,
+		cell_count: u32,
+	) -> Vec<Position> {
+		let count = cells_to_sample(dimensions, cell_count);
+		let seed = blake2_256(&[self.secret.as_slice(), block_hash.as_bytes()].concat());
+		uniform_positions(&mut ChaChaRng::from_seed(seed), dimensions, count)
+	}
+}
+
+/// Creates sampling strategy for the given mode.
+/// In case of deterministic mode, secret is loaded from the database, or generated and stored if missing.
+pub fn new(mode: SamplingMode, db: Arc<DB>) -> Result<Arc<dyn SamplingStrategy>> {
+	info!("Using {mode:?} sampling strategy");
+	Ok(match mode {
+		SamplingMode::Uniform => Arc::new(Uniform),
+		SamplingMode::Stratified => Arc::new(Stratified),
+		SamplingMode::Deterministic => Arc::new(KeyedDeterministic::new(sampling_secret(db)?)),
+	})
+}
+
+fn sampling_secret(db: Arc<DB>) -> Result<[u8; 32]> {
+	if let Some(secret) = get_sampling_secret_from_db(db.clone())? {
+		return Ok(secret);
+	}
+	let secret: [u8; 32] = thread_rng().gen();
+	store_sampling_secret_in_db(db, &secret).context("Failed to store sampling secret")?;
+	Ok(secret)
+}
+
+fn cells_to_sample(dimensions: Dimensions, cell_count: u32) -> usize {
+	let max_cells = dimensions.extended_size();
+	if max_cells < cell_count {
+		debug!("Max cells count {max_cells} is lesser than cell_count {cell_count}");
+		return max_cells as usize;
+	}
+	cell_count as usize
+}
+
+fn random_position<R: Rng + ?Sized>(rng: &mut R, dimensions: Dimensions) -> Position {
+	let col = rng.gen_range(0..dimensions.cols().into());
+	let row = rng.gen_range(0..dimensions.extended_rows());
+	Position { row, col }
+}
+
+fn uniform_positions<R: Rng + ?Sized>(
+	rng: &mut R,
+	dimensions: Dimensions,
+	count: usize,
+) -> Vec<Position> {
+	let mut positions = Vec::with_capacity(count);
+	let mut sampled = HashSet::new();
+	while positions.len() < count {
+		let position = random_position(rng, dimensions);
+		if sampled.insert(position) {
+			positions.push(position);
+		}
+	}
+	positions
+}
+
+fn stratified_positions<R: Rng + ?Sized>(
+	rng: &mut R,
+	dimensions: Dimensions,
+	count: usize,
+) -> Vec<Position> {
+	if count == 0 {
+		return vec![];
+	}
+
+	// Splits range of given size into `count` strata, each stratum has at least one index
+	let stratum = |index: usize, size: u32| -> Range<u32> {
+		let size = size as u64;
+		let start = (index as u64 * size / count as u64) as u32;
+		let end = ((index as u64 + 1) * size / count as u64) as u32;
+		start..end.max(start + 1)
+	};
+
+	let rows = dimensions.extended_rows();
+	let cols = dimensions.cols().get().into();
+
+	let mut column_strata = (0..count).collect::<Vec<_>>();
+	column_strata.shuffle(rng);
+
+	let mut positions = Vec::with_capacity(count);
+	let mut sampled = HashSet::new();
+	for (row_stratum, column_stratum) in (0..count).zip(column_strata) {
+		let row = rng.gen_range(stratum(row_stratum, rows));
+		let col = rng.gen_range(stratum(column_stratum, cols)) as u16;
+		let position = Position { row, col };
+		if sampled.insert(position) {
+			positions.push(position);
+		}
+	}
+
+	// Strata overlap if cell count exceeds number of rows or columns, so duplicates are replaced
+	while positions.len() < count {
+		let position = random_position(rng, dimensions);
+		if sampled.insert(position) {
+			positions.push(position);
+		}
+	}
+	positions
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{KeyedDeterministic, SamplingStrategy, Stratified, Uniform};
+	use avail_subxt::utils::H256;
+	use kate_recovery::matrix::Dimensions;
+	use std::collections::HashSet;
+	use test_case::test_case;
+
+	fn assert_distinct_in_bounds(
+		strategy: &dyn SamplingStrategy,
+		dimensions: Dimensions,
+		count: u32,
+	) {
+		let positions = strategy.positions(H256::repeat_byte(1), dimensions, count);
+		let expected = count.min(dimensions.extended_size()) as usize;
+		assert_eq!(positions.len(), expected);
+		assert_eq!(positions.iter().collect::<HashSet<_>>().len(), expected);
+		assert!(positions
+			.iter()
+			.all(|position| position.row < dimensions.extended_rows()
+				&& position.col < dimensions.cols().get()));
+	}
+
+	#[test_case(1, 4, 8 ; "Sample entire matrix")]
+	#[test_case(1, 4, 20 ; "Cell count exceeds matrix size")]
+	#[test_case(16, 64, 10 ; "Regular block")]
+	#[test_case(2, 256, 10 ; "Cell count exceeds number of rows")]
+	fn sampled_positions_are_distinct(rows: u16, cols: u16, count: u32) {
+		let dimensions = Dimensions::new(rows, cols).unwrap();
+		assert_distinct_in_bounds(&Uniform, dimensions, count);
+		assert_distinct_in_bounds(&Stratified, dimensions, count);
+		assert_distinct_in_bounds(&KeyedDeterministic::new([1; 32]), dimensions, count);
+	}
+
+	#[test]
+	fn stratified_positions_spread_across_rows_and_columns() {
+		let dimensions = Dimensions::new(16, 64).unwrap();
+		let positions = Stratified.positions(H256::zero(), dimensions, 8);
+		let rows = positions.iter().map(|p| p.row / 4).collect::<HashSet<_>>();
+		let cols = positions.iter().map(|p| p.col / 8).collect::<HashSet<_>>();
+		assert_eq!(rows.len(), 8);
+		assert_eq!(cols.len(), 8);
+	}
+
+	#[test]
+	fn deterministic_positions_are_reproducible() {
+		let dimensions = Dimensions::new(16, 64).unwrap();
+		let strategy = KeyedDeterministic::new([1; 32]);
+		let block_hash = H256::repeat_byte(2);
+
+		let positions = strategy.positions(block_hash, dimensions, 10);
+		assert_eq!(positions, strategy.positions(block_hash, dimensions, 10));
+
+		let other_block = strategy.positions(H256::repeat_byte(3), dimensions, 10);
+		assert_ne!(positions, other_block);
+
+		let other_secret = KeyedDeterministic::new([2; 32]).positions(block_hash, dimensions, 10);
+		assert_ne!(positions, other_secret);
+	}
+}
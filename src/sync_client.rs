@@ -5,7 +5,7 @@
 //! # Flow
 //!
 //! * For each block, fetches block header from RPC and stores it into database
-//! * Generate random cells for random data sampling
+//! * Choose cells for random data sampling using configured sampling strategy
 //! * Retrieve cell proofs from a) DHT and/or b) via RPC call from the node, in that order
 //! * Verify proof using the received cells
 //! * Calculate block confidence and store it in RocksDB
@@ -24,14 +24,14 @@ use crate::{
 		p2p::Client as P2pClient,
 		rpc::{self, Client as RpcClient},
 	},
-	proof,
+	proof::{Priority, Verifier},
+	sampling::SamplingStrategy,
 	types::{BlockVerified, OptionBlockRange, State, SyncClientConfig},
 	utils::{calculate_confidence, extract_app_lookup, extract_kate},
 };
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use avail_subxt::{primitives::Header as DaHeader, utils::H256};
-use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
 use kate_recovery::{commitments, matrix::Dimensions};
 use kate_recovery::{data::Cell, matrix::Position};
 use mockall::automock;
@@ -42,7 +42,7 @@ use std::{
 	time::Instant,
 };
 use tokio::sync::broadcast;
-use tracing::{error, info, warn};
+use tracing::{error, info, info_span, warn, Instrument};
 
 #[async_trait]
 #[automock]
@@ -128,7 +128,8 @@ async fn process_block(
 	sync_client: &impl SyncClient,
 	block_number: u32,
 	cfg: &SyncClientConfig,
-	pp: Arc<PublicParameters>,
+	verifier: &Verifier,
+	sampling: &dyn SamplingStrategy,
 	block_verified_sender: Option<broadcast::Sender<BlockVerified>>,
 ) -> Result<()> {
 	if sync_client
@@ -176,10 +177,11 @@ async fn process_block(
 
 	// now this is in `u64`
 	let cell_count = rpc::cell_count_for_confidence(cfg.confidence);
-	let positions = rpc::generate_random_cells(dimensions, cell_count);
+	let positions = sampling.positions(header_hash, dimensions, cell_count);
 
 	let (dht_fetched, unfetched) = sync_client
 		.fetch_cells_from_dht(&positions, block_number)
+		.instrument(info_span!("dht_fetch"))
 		.await;
 
 	info!(
@@ -191,7 +193,10 @@ async fn process_block(
 	let rpc_fetched = if cfg.disable_rpc {
 		vec![]
 	} else {
-		sync_client.get_kate_proof(header_hash, &unfetched).await?
+		sync_client
+			.get_kate_proof(header_hash, &unfetched)
+			.instrument(info_span!("rpc_fetch"))
+			.await?
 	};
 
 	info!(
@@ -213,7 +218,16 @@ async fn process_block(
 	let cells_len = cells.len();
 	info!(block_number, "Fetched {cells_len} cells for verification");
 
-	let (verified, _) = proof::verify(block_number, dimensions, &cells, &commitments, pp)?;
+	let (verified, _) = verifier
+		.verify(
+			Priority::Sync,
+			block_number,
+			dimensions,
+			&cells,
+			&commitments,
+		)
+		.instrument(info_span!("verification"))
+		.await?;
 
 	info!(
 		block_number,
@@ -228,6 +242,7 @@ async fn process_block(
 
 	let inserted_cells = sync_client
 		.insert_cells_into_dht(block_number, rpc_fetched)
+		.instrument(info_span!("dht_insert"))
 		.await;
 	info!(block_number, "Cells inserted into DHT: {inserted_cells}");
 
@@ -251,13 +266,15 @@ async fn process_block(
 /// * `cfg` - Sync client configuration
 /// * `start_block` - Sync start block
 /// * `end_block` - Sync end block
-/// * `pp` - Public parameters (i.e. SRS) needed for proof verification
+/// * `verifier` - Shared proof verifier
+/// * `sampling` - Strategy used to choose cells for sampling
 /// * `block_verified_sender` - Optional channel to send verified blocks
 pub async fn run(
 	sync_client: impl SyncClient,
 	cfg: SyncClientConfig,
 	sync_range: Range<u32>,
-	pp: Arc<PublicParameters>,
+	verifier: Verifier,
+	sampling: Arc<dyn SamplingStrategy>,
 	block_verified_sender: Option<broadcast::Sender<BlockVerified>>,
 	state: Arc<Mutex<State>>,
 ) {
@@ -281,9 +298,16 @@ pub async fn run(
 
 		// TODO: Should we handle unprocessed blocks differently?
 		let block_verified_sender = block_verified_sender.clone();
-		let pp = pp.clone();
-		if let Err(error) =
-			process_block(&sync_client, block_number, &cfg, pp, block_verified_sender).await
+		if let Err(error) = process_block(
+			&sync_client,
+			block_number,
+			&cfg,
+			&verifier,
+			sampling.as_ref(),
+			block_verified_sender,
+		)
+		.instrument(info_span!("block", block_number))
+		.await
 		{
 			error!(block_number, "Cannot process block: {error:#}");
 		} else {
@@ -301,7 +325,11 @@ pub async fn run(
 mod tests {
 
 	use super::*;
-	use crate::types::{self, RuntimeConfig};
+	use crate::{
+		proof::test_verifier,
+		sampling::Uniform,
+		types::{self, RuntimeConfig},
+	};
 	use avail_subxt::{
 		api::runtime_types::avail_core::{
 			data_lookup::compact::CompactDataLookup,
@@ -442,9 +470,16 @@ mod tests {
 			.expect_insert_cells_into_dht()
 			.withf(move |x, _| *x == 42)
 			.returning(move |_, _| Box::pin(async move { 1f32 }));
-		process_block(&mock_client, 42, &cfg, pp, Some(block_tx))
-			.await
-			.unwrap();
+		process_block(
+			&mock_client,
+			42,
+			&cfg,
+			&test_verifier(pp),
+			&Uniform,
+			Some(block_tx),
+		)
+		.await
+		.unwrap();
 	}
 
 	#[tokio::test]
@@ -577,9 +612,16 @@ mod tests {
 			.expect_insert_cells_into_dht()
 			.withf(move |x, _| *x == 42)
 			.returning(move |_, _| Box::pin(async move { 1f32 }));
-		process_block(&mock_client, 42, &cfg, pp, Some(block_tx))
-			.await
-			.unwrap();
+		process_block(
+			&mock_client,
+			42,
+			&cfg,
+			&test_verifier(pp),
+			&Uniform,
+			Some(block_tx),
+		)
+		.await
+		.unwrap();
 	}
 	#[tokio::test]
 	pub async fn test_header_in_dbstore() {
@@ -593,8 +635,15 @@ mod tests {
 			.returning(|_| Ok(true));
 		mock_client.expect_get_header_by_block_number().never();
 		mock_client.block_header_in_db(42).unwrap();
-		process_block(&mock_client, 42, &cfg, pp, Some(block_tx))
-			.await
-			.unwrap();
+		process_block(
+			&mock_client,
+			42,
+			&cfg,
+			&test_verifier(pp),
+			&Uniform,
+			Some(block_tx),
+		)
+		.await
+		.unwrap();
 	}
 }
@@ -1,8 +1,12 @@
-use anyhow::Result;
-use async_trait::async_trait;
+use anyhow::{Context, Result};
 use mockall::automock;
+use serde::{Deserialize, Serialize};
+
+use crate::types::MetricsConfig;
 
 pub mod otlp;
+pub mod prometheus;
+pub mod traces;
 
 pub enum MetricCounter {
 	SessionBlock,
@@ -22,6 +26,9 @@ pub enum MetricValue {
 	KadRoutingPeerNum(usize),
 	HealthCheck(),
 	BlockProcessingDelay(f64),
+	/// Change of the proof verification queue depth, recorded into an up/down counter
+	ProofVerificationQueueDepthChange(i64),
+	ProofVerificationLatency(f64),
 	#[cfg(feature = "crawl")]
 	CrawlCellsSuccessRate(f64),
 	#[cfg(feature = "crawl")]
@@ -30,11 +37,81 @@ pub enum MetricValue {
 	CrawlBlockDelay(f64),
 }
 
+/// Records metrics into pre-registered instruments.
+/// Recording is synchronous and cheap, so it can be used on the hot path.
 #[automock]
-#[async_trait]
 pub trait Metrics {
-	async fn count(&self, counter: MetricCounter);
-	async fn record(&self, value: MetricValue) -> Result<()>;
-	async fn set_multiaddress(&self, multiaddr: String);
-	async fn set_ip(&self, ip: String);
+	fn count(&self, counter: MetricCounter);
+	fn record(&self, value: MetricValue);
+	fn set_multiaddress(&self, multiaddr: String);
+	fn set_ip(&self, ip: String);
+}
+
+/// Metrics backend used to export collected metrics
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricsBackend {
+	/// Pushes metrics to the OpenTelemetry Collector
+	#[default]
+	Otlp,
+	/// Serves metrics on the `/metrics` endpoint, to be scraped by Prometheus
+	Prometheus,
+	/// Discards metrics, used where exporting is not possible (e.g. FFI and mobile builds)
+	Noop,
+}
+
+/// Metrics implementation for the configured backend
+pub enum Backend {
+	Otlp(otlp::Metrics),
+	Prometheus(prometheus::Metrics),
+	Noop,
+}
+
+impl Metrics for Backend {
+	fn count(&self, counter: MetricCounter) {
+		match self {
+			Backend::Otlp(metrics) => metrics.count(counter),
+			Backend::Prometheus(metrics) => metrics.count(counter),
+			Backend::Noop => (),
+		}
+	}
+
+	fn record(&self, value: MetricValue) {
+		match self {
+			Backend::Otlp(metrics) => metrics.record(value),
+			Backend::Prometheus(metrics) => metrics.record(value),
+			Backend::Noop => (),
+		}
+	}
+
+	fn set_multiaddress(&self, multiaddr: String) {
+		match self {
+			Backend::Otlp(metrics) => metrics.set_multiaddress(multiaddr),
+			Backend::Prometheus(metrics) => metrics.set_multiaddress(multiaddr),
+			Backend::Noop => (),
+		}
+	}
+
+	fn set_ip(&self, ip: String) {
+		match self {
+			Backend::Otlp(metrics) => metrics.set_ip(ip),
+			Backend::Prometheus(metrics) => metrics.set_ip(ip),
+			Backend::Noop => (),
+		}
+	}
+}
+
+/// Initializes metrics for the configured backend
+pub fn initialize(cfg: &MetricsConfig, peer_id: String, role: String) -> Result<Backend> {
+	Ok(match cfg.backend {
+		MetricsBackend::Otlp => Backend::Otlp(
+			otlp::initialize(cfg.ot_collector_endpoint.clone(), peer_id, role)
+				.context("Unable to initialize OpenTelemetry service")?,
+		),
+		MetricsBackend::Prometheus => Backend::Prometheus(
+			prometheus::initialize(&cfg.prometheus_host, cfg.prometheus_port, peer_id, role)
+				.context("Unable to initialize Prometheus exporter")?,
+		),
+		MetricsBackend::Noop => Backend::Noop,
+	})
 }
@@ -1,140 +1,299 @@
-use anyhow::{Error, Ok, Result};
-use async_trait::async_trait;
+use anyhow::{Error, Result};
 use opentelemetry_api::{
 	global,
-	metrics::{Counter, Meter},
+	metrics::{Counter, Histogram, Meter, ObservableGauge, Unit, UpDownCounter},
 	KeyValue,
 };
 use opentelemetry_otlp::{ExportConfig, Protocol, WithExportConfig};
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc, RwLock,
+	},
+	time::Duration,
+};
 
-#[derive(Debug)]
-pub struct Metrics {
-	meter: Meter,
-	session_block_counter: Counter<u64>,
+/// Last recorded gauge value, observed on every export.
+/// Gauge is not observed until the first value is recorded.
+#[derive(Default)]
+struct GaugeValue {
+	value: AtomicU64,
+	is_set: AtomicBool,
+}
+
+impl GaugeValue {
+	fn new() -> Self {
+		Self::default()
+	}
+
+	fn set_u64(&self, value: u64) {
+		self.value.store(value, Ordering::Relaxed);
+		self.is_set.store(true, Ordering::Release);
+	}
+
+	fn set_f64(&self, value: f64) {
+		self.set_u64(value.to_bits());
+	}
+
+	fn get_u64(&self) -> Option<u64> {
+		self.is_set
+			.load(Ordering::Acquire)
+			.then(|| self.value.load(Ordering::Relaxed))
+	}
+
+	fn get_f64(&self) -> Option<f64> {
+		self.get_u64().map(f64::from_bits)
+	}
+}
+
+struct Attributes {
 	peer_id: String,
-	multiaddress: RwLock<String>,
-	ip: RwLock<String>,
 	role: String,
+	multiaddress: String,
+	ip: String,
+	key_values: Arc<[KeyValue]>,
 }
 
-impl Metrics {
-	async fn attributes(&self) -> [KeyValue; 5] {
-		[
+impl Attributes {
+	fn new(peer_id: String, role: String) -> Self {
+		let mut attributes = Attributes {
+			peer_id,
+			role,
+			// Default value is empty until first processed block triggers an update
+			multiaddress: "".to_string(),
+			ip: "".to_string(),
+			key_values: Arc::new([]),
+		};
+		attributes.update();
+		attributes
+	}
+
+	fn update(&mut self) {
+		self.key_values = Arc::new([
 			KeyValue::new("version", clap::crate_version!()),
 			KeyValue::new("role", self.role.clone()),
 			KeyValue::new("peerID", self.peer_id.clone()),
-			KeyValue::new("multiaddress", self.multiaddress.read().await.clone()),
-			KeyValue::new("ip", self.ip.read().await.clone()),
-		]
+			KeyValue::new("multiaddress", self.multiaddress.clone()),
+			KeyValue::new("ip", self.ip.clone()),
+		]);
 	}
+}
 
-	async fn record_u64(&self, name: &'static str, value: u64) -> Result<()> {
-		let instrument = self.meter.u64_observable_gauge(name).try_init()?;
-		let attributes = self.attributes().await;
-		self.meter
-			.register_callback(&[instrument.as_any()], move |observer| {
-				observer.observe_u64(&instrument, value, &attributes)
-			})?;
-		Ok(())
+/// OpenTelemetry metrics.
+///
+/// All instruments are registered once on initialization.
+/// Counters, up/down counters and histograms are recorded synchronously,
+/// while gauge values are stored and observed by a single callback on every export.
+pub struct Metrics {
+	attributes: Arc<RwLock<Attributes>>,
+	session_block_counter: Counter<u64>,
+	total_block_number: Arc<GaugeValue>,
+	dht_fetched: Arc<GaugeValue>,
+	dht_fetched_percentage: Arc<GaugeValue>,
+	node_rpc_fetched: Arc<GaugeValue>,
+	block_confidence: Arc<GaugeValue>,
+	rpc_call_duration: Histogram<f64>,
+	dht_put_duration: Histogram<f64>,
+	dht_put_success: Arc<GaugeValue>,
+	dht_put_rows_duration: Histogram<f64>,
+	dht_put_rows_success: Arc<GaugeValue>,
+	kad_routing_table_peer_num: Arc<GaugeValue>,
+	up: Arc<GaugeValue>,
+	block_processing_delay: Histogram<f64>,
+	proof_verification_queue_depth: UpDownCounter<i64>,
+	proof_verification_latency: Histogram<f64>,
+	#[cfg(feature = "crawl")]
+	crawl_cells_success_rate: Arc<GaugeValue>,
+	#[cfg(feature = "crawl")]
+	crawl_rows_success_rate: Arc<GaugeValue>,
+	#[cfg(feature = "crawl")]
+	crawl_block_delay: Histogram<f64>,
+}
+
+impl Metrics {
+	fn new(meter: &Meter, peer_id: String, role: String) -> Result<Self> {
+		let attributes = Arc::new(RwLock::new(Attributes::new(peer_id, role)));
+
+		let mut u64_gauges: Vec<(ObservableGauge<u64>, Arc<GaugeValue>)> = vec![];
+		let mut u64_gauge = |name: &'static str| {
+			let value = Arc::new(GaugeValue::new());
+			u64_gauges.push((meter.u64_observable_gauge(name).init(), value.clone()));
+			value
+		};
+		let total_block_number = u64_gauge("total_block_number");
+		let kad_routing_table_peer_num = u64_gauge("kad_routing_table_peer_num");
+		let up = u64_gauge("up");
+
+		let mut f64_gauges: Vec<(ObservableGauge<f64>, Arc<GaugeValue>)> = vec![];
+		let mut f64_gauge = |name: &'static str| {
+			let value = Arc::new(GaugeValue::new());
+			f64_gauges.push((meter.f64_observable_gauge(name).init(), value.clone()));
+			value
+		};
+		let dht_fetched = f64_gauge("dht_fetched");
+		let dht_fetched_percentage = f64_gauge("dht_fetched_percentage");
+		let node_rpc_fetched = f64_gauge("node_rpc_fetched");
+		let block_confidence = f64_gauge("block_confidence");
+		let dht_put_success = f64_gauge("dht_put_success");
+		let dht_put_rows_success = f64_gauge("dht_put_rows_success");
+		#[cfg(feature = "crawl")]
+		let crawl_cells_success_rate = f64_gauge("crawl_cells_success_rate");
+		#[cfg(feature = "crawl")]
+		let crawl_rows_success_rate = f64_gauge("crawl_rows_success_rate");
+
+		let instruments = u64_gauges
+			.iter()
+			.map(|(gauge, _)| gauge.as_any())
+			.chain(f64_gauges.iter().map(|(gauge, _)| gauge.as_any()))
+			.collect::<Vec<_>>();
+
+		let callback_attributes = attributes.clone();
+		// Single callback observes last recorded values of all gauges
+		meter.register_callback(&instruments, move |observer| {
+			let Ok(attributes) = callback_attributes.read() else {
+				return;
+			};
+			for (gauge, value) in &u64_gauges {
+				if let Some(value) = value.get_u64() {
+					observer.observe_u64(gauge, value, &attributes.key_values);
+				}
+			}
+			for (gauge, value) in &f64_gauges {
+				if let Some(value) = value.get_f64() {
+					observer.observe_f64(gauge, value, &attributes.key_values);
+				}
+			}
+		})?;
+
+		let seconds_histogram =
+			|name: &'static str| meter.f64_histogram(name).with_unit(Unit::new("s")).init();
+
+		Ok(Metrics {
+			attributes,
+			session_block_counter: meter.u64_counter("session_block_counter").init(),
+			total_block_number,
+			dht_fetched,
+			dht_fetched_percentage,
+			node_rpc_fetched,
+			block_confidence,
+			rpc_call_duration: seconds_histogram("rpc_call_duration"),
+			dht_put_duration: seconds_histogram("dht_put_duration"),
+			dht_put_success,
+			dht_put_rows_duration: seconds_histogram("dht_put_rows_duration"),
+			dht_put_rows_success,
+			kad_routing_table_peer_num,
+			up,
+			block_processing_delay: seconds_histogram("block_processing_delay"),
+			proof_verification_queue_depth: meter
+				.i64_up_down_counter("proof_verification_queue_depth")
+				.init(),
+			proof_verification_latency: seconds_histogram("proof_verification_latency"),
+			#[cfg(feature = "crawl")]
+			crawl_cells_success_rate,
+			#[cfg(feature = "crawl")]
+			crawl_rows_success_rate,
+			#[cfg(feature = "crawl")]
+			crawl_block_delay: seconds_histogram("crawl_block_delay"),
+		})
 	}
 
-	async fn record_f64(&self, name: &'static str, value: f64) -> Result<()> {
-		let instrument = self.meter.f64_observable_gauge(name).try_init()?;
-		let attributes = self.attributes().await;
-		self.meter
-			.register_callback(&[instrument.as_any()], move |observer| {
-				observer.observe_f64(&instrument, value, &attributes)
-			})?;
-		Ok(())
+	fn key_values(&self) -> Arc<[KeyValue]> {
+		self.attributes
+			.read()
+			.map(|attributes| attributes.key_values.clone())
+			.unwrap_or_else(|_| Arc::new([]))
 	}
 
-	async fn set_multiaddress(&self, multiaddr: String) {
-		let mut m = self.multiaddress.write().await;
-		*m = multiaddr;
+	fn observe(&self, histogram: &Histogram<f64>, value: f64) {
+		histogram.record(value, &self.key_values());
 	}
 
-	async fn set_ip(&self, ip: String) {
-		let mut i = self.ip.write().await;
-		*i = ip;
+	fn update_attributes(&self, update: impl FnOnce(&mut Attributes)) {
+		if let Ok(mut attributes) = self.attributes.write() {
+			update(&mut attributes);
+			attributes.update();
+		}
 	}
 }
 
-#[async_trait]
 impl super::Metrics for Metrics {
-	async fn count(&self, counter: super::MetricCounter) {
+	fn count(&self, counter: super::MetricCounter) {
 		match counter {
 			super::MetricCounter::SessionBlock => {
-				self.session_block_counter.add(1, &self.attributes().await);
+				self.session_block_counter.add(1, &self.key_values());
 			},
 		}
 	}
 
-	async fn record(&self, value: super::MetricValue) -> Result<()> {
+	fn record(&self, value: super::MetricValue) {
 		match value {
 			super::MetricValue::TotalBlockNumber(number) => {
-				self.record_u64("total_block_number", number.into()).await?;
+				self.total_block_number.set_u64(number.into());
 			},
 			super::MetricValue::DHTFetched(number) => {
-				self.record_f64("dht_fetched", number).await?;
+				self.dht_fetched.set_f64(number);
 			},
 			super::MetricValue::DHTFetchedPercentage(number) => {
-				self.record_f64("dht_fetched_percentage", number).await?;
+				self.dht_fetched_percentage.set_f64(number);
 			},
 			super::MetricValue::NodeRPCFetched(number) => {
-				self.record_f64("node_rpc_fetched", number).await?;
+				self.node_rpc_fetched.set_f64(number);
 			},
 			super::MetricValue::BlockConfidence(number) => {
-				self.record_f64("block_confidence", number).await?;
+				self.block_confidence.set_f64(number);
 			},
 			super::MetricValue::RPCCallDuration(number) => {
-				self.record_f64("rpc_call_duration", number).await?;
+				self.observe(&self.rpc_call_duration, number);
 			},
 			super::MetricValue::DHTPutDuration(number) => {
-				self.record_f64("dht_put_duration", number).await?;
+				self.observe(&self.dht_put_duration, number);
 			},
 			super::MetricValue::DHTPutSuccess(number) => {
-				self.record_f64("dht_put_success", number).await?;
+				self.dht_put_success.set_f64(number);
 			},
 			super::MetricValue::DHTPutRowsDuration(number) => {
-				self.record_f64("dht_put_rows_duration", number).await?;
+				self.observe(&self.dht_put_rows_duration, number);
 			},
 			super::MetricValue::DHTPutRowsSuccess(number) => {
-				self.record_f64("dht_put_rows_success", number).await?;
+				self.dht_put_rows_success.set_f64(number);
 			},
 			super::MetricValue::KadRoutingPeerNum(number) => {
-				self.record_u64("kad_routing_table_peer_num", number as u64)
-					.await?;
+				self.kad_routing_table_peer_num.set_u64(number as u64);
 			},
 			super::MetricValue::HealthCheck() => {
-				self.record_u64("up", 1).await?;
+				self.up.set_u64(1);
 			},
 			super::MetricValue::BlockProcessingDelay(number) => {
-				self.record_f64("block_processing_delay", number).await?;
+				self.observe(&self.block_processing_delay, number);
+			},
+			super::MetricValue::ProofVerificationQueueDepthChange(delta) => {
+				self.proof_verification_queue_depth
+					.add(delta, &self.key_values());
+			},
+			super::MetricValue::ProofVerificationLatency(number) => {
+				self.observe(&self.proof_verification_latency, number);
 			},
 			#[cfg(feature = "crawl")]
 			super::MetricValue::CrawlCellsSuccessRate(number) => {
-				self.record_f64("crawl_cells_success_rate", number).await?;
+				self.crawl_cells_success_rate.set_f64(number);
 			},
 			#[cfg(feature = "crawl")]
 			super::MetricValue::CrawlRowsSuccessRate(number) => {
-				self.record_f64("crawl_rows_success_rate", number).await?;
+				self.crawl_rows_success_rate.set_f64(number);
 			},
 			#[cfg(feature = "crawl")]
 			super::MetricValue::CrawlBlockDelay(number) => {
-				self.record_f64("crawl_block_delay", number).await?;
+				self.observe(&self.crawl_block_delay, number);
 			},
 		};
-		Ok(())
 	}
 
-	async fn set_multiaddress(&self, multiaddr: String) {
-		self.set_multiaddress(multiaddr).await;
+	fn set_multiaddress(&self, multiaddr: String) {
+		self.update_attributes(|attributes| attributes.multiaddress = multiaddr);
 	}
 
-	async fn set_ip(&self, ip: String) {
-		self.set_ip(ip).await;
+	fn set_ip(&self, ip: String) {
+		self.update_attributes(|attributes| attributes.ip = ip);
 	}
 }
 
@@ -157,15 +316,27 @@ pub fn initialize(endpoint: String, peer_id: String, role: String) -> Result<Met
 
 	global::set_meter_provider(provider);
 	let meter = global::meter("avail_light_client");
-	// Initialize counters - they need to persist unlike Gauges that are recreated on every record
-	// TODO - move to a separate func once there's more than 1 counter
-	let session_block_counter = meter.u64_counter("session_block_counter").init();
-	Ok(Metrics {
-		meter,
-		session_block_counter,
-		peer_id,
-		multiaddress: RwLock::new("".to_string()), // Default value is empty until first processed block triggers an update
-		ip: RwLock::new("".to_string()),
-		role,
-	})
+	Metrics::new(&meter, peer_id, role)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::GaugeValue;
+
+	#[test]
+	fn gauge_value_is_unset_until_recorded() {
+		let gauge = GaugeValue::new();
+		assert_eq!(gauge.get_u64(), None);
+		assert_eq!(gauge.get_f64(), None);
+
+		gauge.set_f64(0.5);
+		assert_eq!(gauge.get_f64(), Some(0.5));
+
+		gauge.set_u64(42);
+		assert_eq!(gauge.get_u64(), Some(42));
+
+		// Maximum value is a valid recording, not a marker of an unset gauge
+		gauge.set_u64(u64::MAX);
+		assert_eq!(gauge.get_u64(), Some(u64::MAX));
+	}
 }
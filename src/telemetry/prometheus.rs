@@ -0,0 +1,371 @@
+//! Prometheus metrics exporter.
+//!
+//! Metrics are collected into a registry, which is encoded in the text exposition format
+//! and served on the `/metrics` endpoint of a dedicated HTTP server.
+
+use anyhow::{Context, Result};
+use prometheus_client::{
+	encoding::text::encode,
+	metrics::{
+		counter::Counter,
+		family::Family,
+		gauge::Gauge,
+		histogram::{exponential_buckets, Histogram},
+	},
+	registry::Registry,
+};
+use std::{
+	borrow::Cow,
+	net::SocketAddr,
+	str::FromStr,
+	sync::{atomic::AtomicU64, Arc, Mutex},
+};
+use tracing::{error, info};
+use warp::{http::StatusCode, Filter, Reply};
+
+type GaugeF64 = Gauge<f64, AtomicU64>;
+type InfoLabels = Vec<(&'static str, String)>;
+
+/// Duration histogram buckets, from 5ms to ~40s
+fn duration_histogram() -> Histogram {
+	Histogram::new(exponential_buckets(0.005, 2.0, 14))
+}
+
+pub struct Metrics {
+	session_block_counter: Counter,
+	total_block_number: Gauge,
+	dht_fetched: GaugeF64,
+	dht_fetched_percentage: GaugeF64,
+	node_rpc_fetched: GaugeF64,
+	block_confidence: GaugeF64,
+	rpc_call_duration: Histogram,
+	dht_put_duration: Histogram,
+	dht_put_success: GaugeF64,
+	dht_put_rows_duration: Histogram,
+	dht_put_rows_success: GaugeF64,
+	kad_routing_table_peer_num: Gauge,
+	up: Gauge,
+	block_processing_delay: Histogram,
+	proof_verification_queue_depth: Gauge,
+	proof_verification_latency: Histogram,
+	#[cfg(feature = "crawl")]
+	crawl_cells_success_rate: GaugeF64,
+	#[cfg(feature = "crawl")]
+	crawl_rows_success_rate: GaugeF64,
+	#[cfg(feature = "crawl")]
+	crawl_block_delay: Histogram,
+	node_info: Family<InfoLabels, Gauge>,
+	node_info_labels: Mutex<InfoLabels>,
+}
+
+impl Metrics {
+	fn new(registry: &mut Registry) -> Self {
+		let metrics = Metrics {
+			session_block_counter: Default::default(),
+			total_block_number: Default::default(),
+			dht_fetched: Default::default(),
+			dht_fetched_percentage: Default::default(),
+			node_rpc_fetched: Default::default(),
+			block_confidence: Default::default(),
+			rpc_call_duration: duration_histogram(),
+			dht_put_duration: duration_histogram(),
+			dht_put_success: Default::default(),
+			dht_put_rows_duration: duration_histogram(),
+			dht_put_rows_success: Default::default(),
+			kad_routing_table_peer_num: Default::default(),
+			up: Default::default(),
+			block_processing_delay: duration_histogram(),
+			proof_verification_queue_depth: Default::default(),
+			proof_verification_latency: duration_histogram(),
+			#[cfg(feature = "crawl")]
+			crawl_cells_success_rate: Default::default(),
+			#[cfg(feature = "crawl")]
+			crawl_rows_success_rate: Default::default(),
+			#[cfg(feature = "crawl")]
+			crawl_block_delay: duration_histogram(),
+			node_info: Default::default(),
+			// Default values are empty until first processed block triggers an update
+			node_info_labels: Mutex::new(vec![
+				("multiaddress", "".to_string()),
+				("ip", "".to_string()),
+			]),
+		};
+
+		registry.register(
+			"session_block",
+			"Number of blocks processed in the current session",
+			metrics.session_block_counter.clone(),
+		);
+		registry.register(
+			"total_block_number",
+			"Number of the latest processed block",
+			metrics.total_block_number.clone(),
+		);
+		registry.register(
+			"dht_fetched",
+			"Number of cells fetched from the DHT",
+			metrics.dht_fetched.clone(),
+		);
+		registry.register(
+			"dht_fetched_percentage",
+			"Percentage of cells fetched from the DHT",
+			metrics.dht_fetched_percentage.clone(),
+		);
+		registry.register(
+			"node_rpc_fetched",
+			"Number of cells fetched from the RPC",
+			metrics.node_rpc_fetched.clone(),
+		);
+		registry.register(
+			"block_confidence",
+			"Confidence of the latest processed block",
+			metrics.block_confidence.clone(),
+		);
+		registry.register(
+			"rpc_call_duration_seconds",
+			"Duration of the RPC calls fetching cells",
+			metrics.rpc_call_duration.clone(),
+		);
+		registry.register(
+			"dht_put_duration_seconds",
+			"Duration of the DHT cells insertion",
+			metrics.dht_put_duration.clone(),
+		);
+		registry.register(
+			"dht_put_success",
+			"Success rate of the DHT cells insertion",
+			metrics.dht_put_success.clone(),
+		);
+		registry.register(
+			"dht_put_rows_duration_seconds",
+			"Duration of the DHT rows insertion",
+			metrics.dht_put_rows_duration.clone(),
+		);
+		registry.register(
+			"dht_put_rows_success",
+			"Success rate of the DHT rows insertion",
+			metrics.dht_put_rows_success.clone(),
+		);
+		registry.register(
+			"kad_routing_table_peer_num",
+			"Number of peers in the Kademlia routing table",
+			metrics.kad_routing_table_peer_num.clone(),
+		);
+		registry.register("up", "Light client health check", metrics.up.clone());
+		registry.register(
+			"block_processing_delay_seconds",
+			"Delay between receiving and processing of the block",
+			metrics.block_processing_delay.clone(),
+		);
+		registry.register(
+			"proof_verification_queue_depth",
+			"Number of proof verification jobs waiting in the queue",
+			metrics.proof_verification_queue_depth.clone(),
+		);
+		registry.register(
+			"proof_verification_latency_seconds",
+			"Latency of the proof verification jobs, including queueing",
+			metrics.proof_verification_latency.clone(),
+		);
+		#[cfg(feature = "crawl")]
+		{
+			registry.register(
+				"crawl_cells_success_rate",
+				"Success rate of the crawled cells",
+				metrics.crawl_cells_success_rate.clone(),
+			);
+			registry.register(
+				"crawl_rows_success_rate",
+				"Success rate of the crawled rows",
+				metrics.crawl_rows_success_rate.clone(),
+			);
+			registry.register(
+				"crawl_block_delay_seconds",
+				"Delay between receiving and crawling of the block",
+				metrics.crawl_block_delay.clone(),
+			);
+		}
+		registry.register(
+			"node_info",
+			"Multiaddress and IP address of the light client",
+			metrics.node_info.clone(),
+		);
+		metrics
+	}
+
+	fn set_node_info_label(&self, key: &'static str, value: String) {
+		let Ok(mut labels) = self.node_info_labels.lock() else {
+			return;
+		};
+		for (_, label_value) in labels.iter_mut().filter(|(label, _)| *label == key) {
+			*label_value = value.clone();
+		}
+		self.node_info.clear();
+		self.node_info.get_or_create(&labels).set(1);
+	}
+}
+
+impl super::Metrics for Metrics {
+	fn count(&self, counter: super::MetricCounter) {
+		match counter {
+			super::MetricCounter::SessionBlock => {
+				self.session_block_counter.inc();
+			},
+		}
+	}
+
+	fn record(&self, value: super::MetricValue) {
+		match value {
+			super::MetricValue::TotalBlockNumber(number) => {
+				self.total_block_number.set(number.into());
+			},
+			super::MetricValue::DHTFetched(number) => {
+				self.dht_fetched.set(number);
+			},
+			super::MetricValue::DHTFetchedPercentage(number) => {
+				self.dht_fetched_percentage.set(number);
+			},
+			super::MetricValue::NodeRPCFetched(number) => {
+				self.node_rpc_fetched.set(number);
+			},
+			super::MetricValue::BlockConfidence(number) => {
+				self.block_confidence.set(number);
+			},
+			super::MetricValue::RPCCallDuration(number) => {
+				self.rpc_call_duration.observe(number);
+			},
+			super::MetricValue::DHTPutDuration(number) => {
+				self.dht_put_duration.observe(number);
+			},
+			super::MetricValue::DHTPutSuccess(number) => {
+				self.dht_put_success.set(number);
+			},
+			super::MetricValue::DHTPutRowsDuration(number) => {
+				self.dht_put_rows_duration.observe(number);
+			},
+			super::MetricValue::DHTPutRowsSuccess(number) => {
+				self.dht_put_rows_success.set(number);
+			},
+			super::MetricValue::KadRoutingPeerNum(number) => {
+				self.kad_routing_table_peer_num.set(number as i64);
+			},
+			super::MetricValue::HealthCheck() => {
+				self.up.set(1);
+			},
+			super::MetricValue::BlockProcessingDelay(number) => {
+				self.block_processing_delay.observe(number);
+			},
+			super::MetricValue::ProofVerificationQueueDepthChange(delta) => {
+				self.proof_verification_queue_depth.inc_by(delta);
+			},
+			super::MetricValue::ProofVerificationLatency(number) => {
+				self.proof_verification_latency.observe(number);
+			},
+			#[cfg(feature = "crawl")]
+			super::MetricValue::CrawlCellsSuccessRate(number) => {
+				self.crawl_cells_success_rate.set(number);
+			},
+			#[cfg(feature = "crawl")]
+			super::MetricValue::CrawlRowsSuccessRate(number) => {
+				self.crawl_rows_success_rate.set(number);
+			},
+			#[cfg(feature = "crawl")]
+			super::MetricValue::CrawlBlockDelay(number) => {
+				self.crawl_block_delay.observe(number);
+			},
+		};
+	}
+
+	fn set_multiaddress(&self, multiaddr: String) {
+		self.set_node_info_label("multiaddress", multiaddr);
+	}
+
+	fn set_ip(&self, ip: String) {
+		self.set_node_info_label("ip", ip);
+	}
+}
+
+fn metrics_route(
+	registry: Arc<Registry>,
+) -> impl Filter<Extract = impl Reply, Error = warp::Rejection> + Clone {
+	warp::path!("metrics").and(warp::get()).map(move || {
+		let mut body = String::new();
+		if let Err(error) = encode(&mut body, &registry) {
+			error!("Cannot encode metrics: {error}");
+			return warp::reply::with_status(String::new(), StatusCode::INTERNAL_SERVER_ERROR)
+				.into_response();
+		}
+		warp::reply::with_header(
+			body,
+			"content-type",
+			"application/openmetrics-text; version=1.0.0; charset=utf-8",
+		)
+		.into_response()
+	})
+}
+
+/// Initializes Prometheus metrics and spawns HTTP server serving them on the `/metrics` endpoint.
+/// Fails if the exporter address cannot be bound.
+pub fn initialize(host: &str, port: u16, peer_id: String, role: String) -> Result<Metrics> {
+	let addr = SocketAddr::from_str(&format!("{host}:{port}"))
+		.context("Unable to parse Prometheus exporter address")?;
+
+	let labels = [
+		("version", clap::crate_version!().to_string()),
+		("role", role),
+		("peerID", peer_id),
+	]
+	.into_iter()
+	.map(|(key, value)| (Cow::Borrowed(key), Cow::Owned(value)));
+
+	let mut registry = Registry::with_prefix_and_labels("avail_light_client", labels);
+	let metrics = Metrics::new(&mut registry);
+
+	let (addr, server) = warp::serve(metrics_route(Arc::new(registry)))
+		.try_bind_ephemeral(addr)
+		.context("Unable to bind Prometheus exporter address")?;
+
+	info!("Prometheus metrics exporter running on http://{addr}/metrics");
+	tokio::spawn(server);
+	Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{initialize, metrics_route, Metrics};
+	use crate::telemetry::{MetricCounter, MetricValue, Metrics as _};
+	use prometheus_client::registry::Registry;
+	use std::sync::Arc;
+
+	#[tokio::test]
+	async fn metrics_route_exposes_recorded_values() {
+		let mut registry = Registry::default();
+		let metrics = Metrics::new(&mut registry);
+		metrics.count(MetricCounter::SessionBlock);
+		metrics.record(MetricValue::TotalBlockNumber(42));
+		metrics.record(MetricValue::RPCCallDuration(0.1));
+		metrics.set_ip("127.0.0.1".to_string());
+
+		let route = metrics_route(Arc::new(registry));
+		let response = warp::test::request()
+			.method("GET")
+			.path("/metrics")
+			.reply(&route)
+			.await;
+
+		assert_eq!(response.status(), 200);
+		let body = String::from_utf8(response.body().to_vec()).unwrap();
+		assert!(body.contains("session_block_total 1"));
+		assert!(body.contains("total_block_number 42"));
+		assert!(body.contains("rpc_call_duration_seconds_count 1"));
+		assert!(body.contains("node_info{multiaddress=\"\",ip=\"127.0.0.1\"} 1"));
+	}
+
+	#[tokio::test]
+	async fn initialize_fails_if_port_is_in_use() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let port = listener.local_addr().unwrap().port();
+		let result = initialize("127.0.0.1", port, "peer".to_string(), "role".to_string());
+		assert!(result.is_err());
+	}
+}
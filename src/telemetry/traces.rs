@@ -0,0 +1,32 @@
+//! Export of block processing traces to the OpenTelemetry Collector.
+//!
+//! Each processed block has a root `block` span, created when the finalized header is received,
+//! with child spans for DHT fetch, RPC fetch, proof verification, DHT insert and app data reconstruction.
+
+use anyhow::{Context, Result};
+use opentelemetry_api::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+	trace::{self, Tracer},
+	Resource,
+};
+use std::time::Duration;
+
+/// Installs OTLP span exporter and returns tracer used by the tracing layer
+pub fn tracer(endpoint: String, role: &str) -> Result<Tracer> {
+	opentelemetry_otlp::new_pipeline()
+		.tracing()
+		.with_exporter(
+			opentelemetry_otlp::new_exporter()
+				.tonic()
+				.with_endpoint(endpoint)
+				.with_timeout(Duration::from_secs(10)),
+		)
+		.with_trace_config(trace::config().with_resource(Resource::new([
+			KeyValue::new("service.name", "avail-light-client"),
+			KeyValue::new("service.version", clap::crate_version!()),
+			KeyValue::new("role", role.to_string()),
+		])))
+		.install_batch(opentelemetry_sdk::runtime::Tokio)
+		.context("Cannot install OpenTelemetry tracer")
+}
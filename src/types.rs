@@ -1,27 +1,44 @@
 //! Shared light client structs and enums.
 
-use crate::utils::{extract_app_lookup, extract_kate};
+use crate::{
+	api::{auth::ApiKey, v2::types::Topic},
+	keystore::{self, KeyKind, Keystore},
+	sampling::SamplingMode,
+	telemetry::MetricsBackend,
+	utils::{extract_app_lookup, extract_kate},
+};
 use anyhow::anyhow;
 use anyhow::{Context, Result};
 use avail_core::DataLookup;
 use avail_subxt::{primitives::Header as DaHeader, utils::H256};
 use codec::{Decode, Encode};
 use kate_recovery::{
+	com::AppData,
 	commitments,
 	matrix::{Dimensions, Partition},
 };
 use libp2p::{Multiaddr, PeerId};
+use schemars::JsonSchema;
 use serde::{de::Error, Deserialize, Serialize};
 use sp_core::{blake2_256, bytes, ed25519};
+use std::collections::BTreeMap;
 use std::ops::Range;
 use std::str::FromStr;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{fmt, fs};
-use subxt::ext::sp_core::{sr25519::Pair, Pair as _};
+use subxt::ext::sp_core::{
+	sr25519::{Pair, Public},
+	Pair as _,
+};
 use subxt::ext::sp_runtime::app_crypto::SecretStringError;
+use tracing::Span;
+use zeroize::{Zeroize, Zeroizing};
+
+const REDACTED: &str = "<redacted>";
 
 const CELL_SIZE: usize = 32;
 const PROOF_SIZE: usize = 48;
@@ -48,6 +65,17 @@ pub struct BlockVerified {
 	pub lookup: DataLookup,
 	pub commitments: Vec<[u8; 48]>,
 	pub confidence: Option<f64>,
+	/// Span of the block processing, set to the current span on creation
+	pub span: Span,
+}
+
+/// App client to the API channel message struct
+#[derive(Clone, Debug)]
+pub struct DataVerified {
+	pub block_number: u32,
+	pub data: AppData,
+	/// Indices of the application transactions in the block, if known
+	pub extrinsic_indices: Option<Vec<u32>>,
 }
 
 impl TryFrom<(DaHeader, Option<f64>)> for BlockVerified {
@@ -67,6 +95,7 @@ impl TryFrom<(DaHeader, Option<f64>)> for BlockVerified {
 			lookup,
 			commitments: commitments::from_slice(&commitment)?,
 			confidence,
+			span: Span::current(),
 		})
 	}
 }
@@ -75,7 +104,8 @@ impl TryFrom<(DaHeader, Option<f64>)> for BlockVerified {
 ///
 /// * `LightClient` - light client is running
 /// * `AppClient` - app client is running alongside the light client
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, JsonSchema)]
+#[schemars(rename = "ModeV1")]
 pub enum Mode {
 	LightClient,
 	AppClient(u32),
@@ -166,11 +196,113 @@ impl From<&MultiaddrConfig> for (PeerId, Multiaddr) {
 	}
 }
 
+/// Secret value, which is redacted when printed or serialized, and zeroized on drop.
+/// Value is not copied on clone, clones share it until the last one is dropped.
+pub struct Secret<T: Zeroize>(Arc<Zeroizing<T>>);
+
+impl<T: Zeroize> Secret<T> {
+	pub fn new(value: T) -> Self {
+		Secret(Arc::new(Zeroizing::new(value)))
+	}
+
+	pub fn expose(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T: Zeroize> Clone for Secret<T> {
+	fn clone(&self) -> Self {
+		Secret(self.0.clone())
+	}
+}
+
+impl<T: Zeroize + PartialEq> PartialEq for Secret<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.expose() == other.expose()
+	}
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+	fn from(value: T) -> Self {
+		Secret::new(value)
+	}
+}
+
+impl<'de, T: Zeroize + Deserialize<'de>> Deserialize<'de> for Secret<T> {
+	fn deserialize<D: serde::Deserializer<'de>>(
+		deserializer: D,
+	) -> std::result::Result<Self, D::Error> {
+		T::deserialize(deserializer).map(Secret::new)
+	}
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{REDACTED}")
+	}
+}
+
+impl<T: Zeroize> Serialize for Secret<T> {
+	fn serialize<S: serde::Serializer>(
+		&self,
+		serializer: S,
+	) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(REDACTED)
+	}
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum SecretKey {
-	Seed { seed: String },
-	Key { key: String },
+	Seed {
+		seed: Secret<String>,
+	},
+	Key {
+		key: Secret<String>,
+	},
+	/// Name of the libp2p identity key stored in the keystore
+	Keystore {
+		keystore: String,
+	},
+}
+
+/// HTTP server TLS configuration, certificates are reloaded on file change
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+	/// Path to the PEM encoded certificate chain
+	pub cert_path: String,
+	/// Path to the PEM encoded private key
+	pub key_path: String,
+	/// Path to the PEM encoded CA certificates. If set, clients are required to authenticate with a certificate signed by the CA (mTLS)
+	pub client_ca_path: Option<String>,
+}
+
+/// Webhook receiving published messages as HTTP POST requests
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookConfig {
+	/// Webhook URL
+	pub url: String,
+	/// Topics delivered to the webhook, all topics are delivered if empty
+	#[serde(default)]
+	pub topics: Vec<Topic>,
+	/// Secret used to sign the requests with HMAC-SHA256. If not set, requests are not signed
+	#[serde(default)]
+	pub secret: Option<Secret<String>>,
+}
+
+/// Named Avail account used to sign the submitted data transactions
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignerConfig {
+	/// Signer name, used to choose the signer on submit
+	pub name: String,
+	/// Avail account secret key
+	#[serde(default)]
+	pub secret_key: Option<AvailSecretKey>,
+	/// Name of the Avail account key stored in the keystore, used instead of `secret_key`
+	#[serde(default)]
+	pub key: Option<String>,
+	/// Applications the signer is allowed to submit data for
+	pub app_ids: Vec<u32>,
 }
 
 /// Representation of a configuration used by this project.
@@ -181,9 +313,30 @@ pub struct RuntimeConfig {
 	pub http_server_host: String,
 	/// Light client HTTP server port (default: 7000).
 	pub http_server_port: u16,
+	/// Maximum number of expected block times since the last received header, before client is reported as not ready (default: 3).
+	pub health_max_header_delay: u32,
+	/// Minimum number of peers in the DHT routing table, before client is reported as not ready (default: 1).
+	pub health_min_dht_peers: usize,
+	/// Maximum number of blocks app client can lag behind verified headers, before client is reported as not ready (default: 10).
+	pub health_max_app_lag: u32,
+	/// API keys used to authenticate API requests, with `read` and/or `submit` scopes. If empty, authentication is disabled (default: empty).
+	pub api_keys: Vec<ApiKey>,
+	/// Maximum number of API requests per minute per API key, unless overridden by the key (default: None).
+	pub api_key_rate_limit: Option<u32>,
+	/// Maximum number of API requests per minute per client IP address (default: None).
+	pub api_ip_rate_limit: Option<u32>,
+	/// Origins allowed to access the API from the browser, "*" allows any origin (default: ["*"]).
+	pub api_cors_allowed_origins: Vec<String>,
+	/// Maximum number of webhook delivery retries, before the message is written to the dead-letter log (default: 5).
+	pub webhook_max_retries: u32,
+	/// Delay in seconds before the first webhook delivery retry, doubled on each next retry (default: 1).
+	pub webhook_retry_delay: u64,
+	/// File to which undelivered webhook messages are appended as JSON lines. If not set, undelivered messages are only logged (default: None).
+	pub webhook_dead_letter_path: Option<String>,
 	/// Secret key for libp2p keypair. Can be either set to `seed` or to `key`.
 	/// If set to seed, keypair will be generated from that seed.
 	/// If set to key, a valid ed25519 private key must be provided, else the client will fail
+	/// If set to keystore, key with the given name is loaded from the keystore.
 	/// If `secret_key` is not set, random seed will be used.
 	pub secret_key: Option<SecretKey>,
 	/// P2P service port (default: 37000).
@@ -216,14 +369,26 @@ pub struct RuntimeConfig {
 	pub app_id: Option<u32>,
 	/// Confidence threshold, used to calculate how many cells need to be sampled to achieve desired confidence (default: 92.0).
 	pub confidence: f64,
+	/// Strategy used to choose cells for sampling. Available strategies are "uniform", "stratified" and "deterministic".
+	/// Deterministic strategy derives positions from the local secret (stored in database) and the block hash (default: "uniform").
+	pub sampling_strategy: SamplingMode,
 	/// File system path where RocksDB used by light client, stores its data.
 	pub avail_path: String,
+	/// Directory with the encrypted keys, managed with the `keys` command (default: "keystore").
+	/// Keystore is unlocked with the password set in the `AVAIL_KEYSTORE_PASSWORD` environment variable.
+	pub keystore_path: String,
 	/// Log level, default is `INFO`. See `<https://docs.rs/log/0.4.14/log/enum.LevelFilter.html>` for possible log level values. (default: `INFO`).
 	pub log_level: String,
 	/// If set to true, logs are displayed in JSON format, which is used for structured logging. Otherwise, plain text format is used (default: false).
 	pub log_format_json: bool,
-	/// OpenTelemetry Collector endpoint (default: `http://otelcollector.avail.tools:4317`)
+	/// Metrics backend. Available backends are "otlp", "prometheus" and "noop" (default: "otlp").
+	pub metrics_backend: MetricsBackend,
+	/// OpenTelemetry Collector endpoint, used by "otlp" backend (default: `http://otelcollector.avail.tools:4317`)
 	pub ot_collector_endpoint: String,
+	/// Enables export of block processing traces to the OpenTelemetry Collector (default: false).
+	pub ot_tracing: bool,
+	/// Prometheus exporter port, used by "prometheus" backend. Exporter is listening on the HTTP server host (default: 9520).
+	pub prometheus_port: u16,
 	/// Disables fetching of cells from RPC, set to true if client expects cells to be available in DHT (default: false).
 	pub disable_rpc: bool,
 	/// Disables proof verification in general, if set to true, otherwise proof verification is performed. (default: false).
@@ -245,8 +410,17 @@ pub struct RuntimeConfig {
 	pub sync_finality_enable: bool,
 	/// Maximum number of cells per request for proof queries (default: 30).
 	pub max_cells_per_rpc: Option<usize>,
-	/// Threshold for the number of cells fetched via DHT for the app client (default: 5000)
+	/// Number of latest blocks for which fetched cells are cached, and served over the API (default: 16).
+	pub cell_cache_blocks: usize,
+	/// Threshold for the number of cells fetched via DHT for the app client, also used to limit cells fetched for the block reconstruction in archival mode (default: 5000)
 	pub threshold: usize,
+	/// Maximum number of queued proof verification requests per priority, before requesting clients are blocked (default: 1024).
+	pub proof_verification_queue_size: usize,
+	/// Maximum number of cells, from one or more blocks, verified in a single batch (default: 4096).
+	pub proof_verification_batch_size: usize,
+	/// Enables archival mode of the app client. If enabled, the complete data matrix is reconstructed for every block,
+	/// and data of every application is decoded and stored, not only data of the configured app_id (default: false).
+	pub archival_mode: bool,
 	/// Kademlia configuration - WARNING: Changing the default values might cause the peer to suffer poor performance!
 	/// Default Kademlia config values have been copied from rust-libp2p Kademila defaults
 	///
@@ -286,16 +460,40 @@ pub struct RuntimeConfig {
 	/// The maximum number of provider records for which the local node is the provider. (default: 1024).
 	pub max_kad_provided_keys: u64,
 	/// Avail account secret key. (default: None)
-	#[serde(skip_serializing)]
 	pub avail_secret_key: Option<AvailSecretKey>,
+	/// Name of the Avail account key stored in the keystore, used instead of `avail_secret_key` (default: None).
+	pub avail_key: Option<String>,
+	/// Additional named Avail accounts used to sign submitted data, each allowed for a set of applications (default: empty).
+	/// Account configured with `avail_secret_key` is named `default`, and allowed for the configured `app_id`.
+	pub signers: Vec<SignerConfig>,
+	/// Maximum size of the data submitted in a single data transaction, in bytes (default: 524288).
+	/// Larger data is submitted as a blob, split into multiple data transactions, followed by the blob manifest.
+	pub blob_chunk_size: usize,
+	/// Light client HTTP server TLS configuration. If set, API is served over HTTPS, and websockets over WSS (default: None).
+	pub http_server_tls: Option<TlsConfig>,
+	/// Webhooks receiving messages published on the v2 API topics (default: empty).
+	pub webhooks: Vec<WebhookConfig>,
 	#[cfg(feature = "crawl")]
 	#[serde(flatten)]
 	pub crawl: crate::crawl_client::CrawlConfig,
 }
 
+/// Avail account key, which is redacted when printed or serialized.
+/// Key pair is not copied on clone, and its secret key is zeroized on drop.
 #[derive(Deserialize, Clone)]
 #[serde(try_from = "String")]
-pub struct AvailSecretKey(pub Pair);
+pub struct AvailSecretKey(Arc<Pair>);
+
+impl AvailSecretKey {
+	pub fn public(&self) -> Public {
+		self.0.public()
+	}
+
+	/// Copy of the key pair, used by the transaction signer
+	pub fn pair(&self) -> Pair {
+		self.0.as_ref().clone()
+	}
+}
 
 impl fmt::Debug for AvailSecretKey {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -305,13 +503,23 @@ impl fmt::Debug for AvailSecretKey {
 	}
 }
 
+impl Serialize for AvailSecretKey {
+	fn serialize<S: serde::Serializer>(
+		&self,
+		serializer: S,
+	) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(REDACTED)
+	}
+}
+
 impl TryFrom<String> for AvailSecretKey {
 	type Error = SecretStringError;
 
-	fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
-		Pair::from_string_with_seed(&value, None)
-			.map(|(pair, _)| pair)
-			.map(AvailSecretKey)
+	fn try_from(mut value: String) -> std::result::Result<Self, Self::Error> {
+		let key = Pair::from_string_with_seed(&value, None)
+			.map(|(pair, _)| AvailSecretKey(Arc::new(pair)));
+		value.zeroize();
+		key
 	}
 }
 
@@ -476,11 +684,106 @@ impl From<&RuntimeConfig> for SyncClientConfig {
 	}
 }
 
+/// Proof verifier configuration (see [RuntimeConfig] for details)
+pub struct VerifierConfig {
+	pub queue_size: usize,
+	pub batch_size: usize,
+	pub threads: usize,
+}
+
+impl From<&RuntimeConfig> for VerifierConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		VerifierConfig {
+			queue_size: val.proof_verification_queue_size,
+			batch_size: val.proof_verification_batch_size,
+			threads: num_cpus::get(),
+		}
+	}
+}
+
+/// Metrics configuration (see [RuntimeConfig] for details)
+pub struct MetricsConfig {
+	pub backend: MetricsBackend,
+	pub ot_collector_endpoint: String,
+	pub prometheus_host: String,
+	pub prometheus_port: u16,
+}
+
+impl From<&RuntimeConfig> for MetricsConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		MetricsConfig {
+			backend: val.metrics_backend,
+			ot_collector_endpoint: val.ot_collector_endpoint.clone(),
+			prometheus_host: val.http_server_host.clone(),
+			prometheus_port: val.prometheus_port,
+		}
+	}
+}
+
+/// Health checks configuration (see [RuntimeConfig] for details)
+#[derive(Clone)]
+pub struct HealthConfig {
+	pub max_header_delay: u32,
+	pub min_dht_peers: usize,
+	pub max_app_lag: u32,
+	pub app_client: bool,
+}
+
+impl From<&RuntimeConfig> for HealthConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		HealthConfig {
+			max_header_delay: val.health_max_header_delay,
+			min_dht_peers: val.health_min_dht_peers,
+			max_app_lag: val.health_max_app_lag,
+			app_client: matches!(Mode::from(val.app_id), Mode::AppClient(_)),
+		}
+	}
+}
+
+/// API authentication configuration (see [RuntimeConfig] for details)
+pub struct AuthConfig {
+	pub keys: Vec<ApiKey>,
+	pub key_rate_limit: Option<u32>,
+	pub ip_rate_limit: Option<u32>,
+}
+
+impl From<&RuntimeConfig> for AuthConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		AuthConfig {
+			keys: val.api_keys.clone(),
+			key_rate_limit: val.api_key_rate_limit,
+			ip_rate_limit: val.api_ip_rate_limit,
+		}
+	}
+}
+
+/// Webhooks configuration (see [RuntimeConfig] for details)
+#[derive(Clone)]
+pub struct WebhooksConfig {
+	pub webhooks: Vec<WebhookConfig>,
+	pub max_retries: u32,
+	pub retry_delay: Duration,
+	pub dead_letter_path: Option<String>,
+}
+
+impl From<&RuntimeConfig> for WebhooksConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		WebhooksConfig {
+			webhooks: val.webhooks.clone(),
+			max_retries: val.webhook_max_retries,
+			retry_delay: Duration::from_secs(val.webhook_retry_delay),
+			dead_letter_path: val.webhook_dead_letter_path.clone(),
+		}
+	}
+}
+
 /// App client configuration (see [RuntimeConfig] for details)
 pub struct AppClientConfig {
 	pub dht_parallelization_limit: usize,
 	pub disable_rpc: bool,
 	pub threshold: usize,
+	pub max_cells_per_rpc: usize,
+	pub archival_mode: bool,
 }
 
 impl From<&RuntimeConfig> for AppClientConfig {
@@ -489,6 +792,8 @@ impl From<&RuntimeConfig> for AppClientConfig {
 			dht_parallelization_limit: val.dht_parallelization_limit,
 			disable_rpc: val.disable_rpc,
 			threshold: val.threshold,
+			max_cells_per_rpc: val.max_cells_per_rpc.unwrap_or(30),
+			archival_mode: val.archival_mode,
 		}
 	}
 }
@@ -497,6 +802,16 @@ impl Default for RuntimeConfig {
 		RuntimeConfig {
 			http_server_host: "127.0.0.1".to_owned(),
 			http_server_port: 7000,
+			health_max_header_delay: 3,
+			health_min_dht_peers: 1,
+			health_max_app_lag: 10,
+			api_keys: vec![],
+			api_key_rate_limit: None,
+			api_ip_rate_limit: None,
+			api_cors_allowed_origins: vec!["*".to_string()],
+			webhook_max_retries: 5,
+			webhook_retry_delay: 1,
+			webhook_dead_letter_path: None,
 			port: 37000,
 			secret_key: None,
 			tcp_port_reuse: false,
@@ -513,10 +828,15 @@ impl Default for RuntimeConfig {
 			full_node_ws: vec!["ws://127.0.0.1:9944".to_owned()],
 			app_id: None,
 			confidence: 92.0,
+			sampling_strategy: SamplingMode::Uniform,
 			avail_path: "avail_path".to_owned(),
+			keystore_path: "keystore".to_owned(),
 			log_level: "INFO".to_owned(),
 			log_format_json: false,
+			metrics_backend: MetricsBackend::Otlp,
 			ot_collector_endpoint: "http://otelcollector.avail.tools:4317".to_string(),
+			ot_tracing: false,
+			prometheus_port: 9520,
 			disable_rpc: false,
 			disable_proof_verification: false,
 			dht_parallelization_limit: 20,
@@ -527,8 +847,12 @@ impl Default for RuntimeConfig {
 			sync_start_block: None,
 			sync_finality_enable: true,
 			max_cells_per_rpc: Some(30),
+			cell_cache_blocks: 16,
 			kad_record_ttl: 24 * 60 * 60,
 			threshold: 5000,
+			proof_verification_queue_size: 1024,
+			proof_verification_batch_size: 4096,
+			archival_mode: false,
 			replication_factor: 20,
 			publication_interval: 12 * 60 * 60,
 			replication_interval: 3 * 60 * 60,
@@ -541,6 +865,11 @@ impl Default for RuntimeConfig {
 			max_kad_record_size: 8192,
 			max_kad_provided_keys: 1024,
 			avail_secret_key: None,
+			avail_key: None,
+			signers: vec![],
+			blob_chunk_size: 512 * 1024,
+			http_server_tls: None,
+			webhooks: vec![],
 			#[cfg(feature = "crawl")]
 			crawl: crate::crawl_client::CrawlConfig::default(),
 		}
@@ -640,14 +969,27 @@ impl RuntimeConfig {
 		Range { start, end }
 	}
 
-	pub fn load_runtime_config(&mut self, opts: &CliOpts) -> Result<()> {
+	/// Loads configuration from the file and applies the command line flags.
+	/// Returns the source of each parameter which is not defaulted.
+	pub fn load_runtime_config(&mut self, opts: &CliOpts) -> Result<ConfigSources> {
+		let mut sources = ConfigSources::new();
 		if let Some(config_path) = &opts.config {
 			fs::metadata(config_path)
 				.map_err(|_| anyhow!("Provided config file doesn't exist."))?;
 			let cfg: RuntimeConfig = confy::load_path(config_path)
 				.context(format!("Failed to load configuration from {}", config_path))?;
 			*self = cfg;
+
+			// Parameters present in the file are sourced from the file, even if equal to the defaults
+			let values: serde_json::Value = confy::load_path(config_path)
+				.context(format!("Failed to load configuration from {}", config_path))?;
+			if let serde_json::Value::Object(values) = values {
+				for name in values.into_iter().map(|(name, _)| name) {
+					sources.insert(name, ConfigSource::ConfigFile);
+				}
+			}
 		}
+		let mut flag = |name: &str| sources.insert(name.to_string(), ConfigSource::Flag);
 
 		// Flags override the config parameters
 		if let Some(network) = &opts.network {
@@ -659,22 +1001,135 @@ impl RuntimeConfig {
 			);
 			self.full_node_ws = vec![network.full_node_ws().to_string()];
 			self.bootstraps = vec![MultiaddrConfig::PeerIdAndMultiaddr(bootstrap)];
+			flag("full_node_ws");
+			flag("bootstraps");
 		}
 
 		if let Some(loglvl) = &opts.verbosity {
 			self.log_level = loglvl.to_string();
+			flag("log_level");
 		}
 
 		if let Some(port) = opts.port {
 			self.port = port;
+			flag("port");
+		}
+
+		if let Some(app_id) = opts.app_id {
+			self.app_id = Some(app_id);
+			flag("app_id");
 		}
 
-		self.app_id = opts.app_id.or(self.app_id);
+		Ok(sources)
+	}
+
+	/// Configuration parameters with their effective values, and the source of each value.
+	/// Parameters without a source are defaulted, and secrets are redacted.
+	pub fn effective_config(
+		&self,
+		sources: &ConfigSources,
+	) -> Result<BTreeMap<String, EffectiveValue>> {
+		let serde_json::Value::Object(values) = serde_json::to_value(self)? else {
+			return Err(anyhow!("Configuration is not serialized as object"));
+		};
+		let effective_config = values
+			.into_iter()
+			.map(|(name, value)| {
+				let source = sources.get(&name).copied().unwrap_or(ConfigSource::Default);
+				(name, EffectiveValue { value, source })
+			})
+			.collect();
+
+		Ok(effective_config)
+	}
+
+	fn has_keystore_keys(&self) -> bool {
+		self.avail_key.is_some()
+			|| self.signers.iter().any(|signer| signer.key.is_some())
+			|| matches!(self.secret_key, Some(SecretKey::Keystore { .. }))
+	}
+
+	/// Loads the Avail account key, unlocking only that key if it is stored in the keystore
+	pub fn load_avail_secret_key(&self) -> Result<Option<AvailSecretKey>> {
+		let Some(name) = &self.avail_key else {
+			return Ok(self.avail_secret_key.clone());
+		};
+		let keystore = Keystore::new(&self.keystore_path);
+		let password = keystore::password_from_env()?;
+		keystore.avail_secret_key(name, &password).map(Some)
+	}
+
+	/// Checks that each signer is configured with exactly one of the `secret_key` or `key`
+	fn check_signers(&self) -> Result<()> {
+		for signer in &self.signers {
+			match (&signer.secret_key, &signer.key) {
+				(None, None) => {
+					return Err(anyhow!(
+						"Signer {} has neither secret_key nor key configured",
+						signer.name
+					))
+				},
+				(Some(_), Some(_)) => {
+					return Err(anyhow!(
+						"Signer {} has both secret_key and key configured",
+						signer.name
+					))
+				},
+				_ => (),
+			}
+		}
+		Ok(())
+	}
+
+	/// Replaces keys referenced by name with the secret keys loaded from the keystore
+	pub fn unlock_keys(&mut self) -> Result<()> {
+		self.check_signers()?;
+		if !self.has_keystore_keys() {
+			return Ok(());
+		}
+
+		let keystore = Keystore::new(&self.keystore_path);
+		let password = keystore::password_from_env()?;
+
+		if let Some(name) = &self.avail_key {
+			self.avail_secret_key = Some(keystore.avail_secret_key(name, &password)?);
+		}
+
+		for signer in self.signers.iter_mut() {
+			if let Some(name) = &signer.key {
+				signer.secret_key = Some(keystore.avail_secret_key(name, &password)?);
+			}
+		}
+
+		if let Some(SecretKey::Keystore { keystore: name }) = &self.secret_key {
+			self.secret_key = Some(keystore.p2p_secret_key(name, &password)?);
+		}
 
 		Ok(())
 	}
 }
 
+/// Source of the effective configuration value
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigSource {
+	/// Value is not configured, default is used
+	Default,
+	/// Value is set in the configuration file
+	ConfigFile,
+	/// Value is overridden with the command line flag
+	Flag,
+}
+
+/// Sources of the configuration parameters, by parameter name
+pub type ConfigSources = BTreeMap<String, ConfigSource>;
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct EffectiveValue {
+	pub value: serde_json::Value,
+	pub source: ConfigSource,
+}
+
 #[derive(Clone)]
 pub struct BlockRange {
 	pub first: u32,
@@ -704,6 +1159,8 @@ pub struct State {
 	pub sync_confidence_achieved: Option<BlockRange>,
 	pub sync_data_verified: Option<BlockRange>,
 	pub finality_synced: bool,
+	pub rpc_connected: bool,
+	pub latest_header_received_at: Option<Instant>,
 }
 
 pub trait OptionBlockRange {
@@ -736,6 +1193,14 @@ impl OptionBlockRange for Option<BlockRange> {
 	}
 }
 
+/// Location of the application transaction, indexed by transaction hash and signer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode)]
+pub struct TransactionLocation {
+	pub app_id: u32,
+	pub block_number: u32,
+	pub index: u32,
+}
+
 #[derive(Debug, Decode, Encode)]
 pub struct FinalitySyncCheckpoint {
 	pub number: u32,
@@ -822,4 +1287,197 @@ pub struct CliOpts {
 	/// Log level
 	#[arg(long)]
 	pub verbosity: Option<LogLevel>,
+	#[command(subcommand)]
+	pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+	/// Export application data from the local database as newline-delimited JSON
+	ExportAppData {
+		/// AppID of the exported data (default: configured app_id)
+		#[arg(long, value_name = "appId")]
+		app_id: Option<u32>,
+		/// First block of the exported range
+		#[arg(long)]
+		from: u32,
+		/// Last block of the exported range
+		#[arg(long)]
+		to: u32,
+		/// Output file (default: standard output)
+		#[arg(short, long, value_name = "FILE")]
+		output: Option<String>,
+	},
+	/// Print effective configuration as JSON, with the source of each value. Secrets are redacted
+	Config,
+	/// Manage keys in the encrypted keystore
+	Keys {
+		#[command(subcommand)]
+		command: KeysCommand,
+	},
+}
+
+#[derive(Subcommand)]
+pub enum KeysCommand {
+	/// Generate a new random key
+	Generate {
+		/// Key name, used to reference the key in the configuration
+		name: String,
+		/// Key kind
+		#[arg(long, value_enum)]
+		kind: KeyKind,
+	},
+	/// Import an existing secret key, read from the prompt or standard input
+	Import {
+		/// Key name, used to reference the key in the configuration
+		name: String,
+		/// Key kind
+		#[arg(long, value_enum)]
+		kind: KeyKind,
+	},
+	/// Print decrypted secret key to standard output
+	Export {
+		/// Key name
+		name: String,
+	},
+	/// List keys in the keystore
+	List,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		AvailSecretKey, BlockVerified, ConfigSource, ConfigSources, DaHeader, RuntimeConfig,
+		Secret, SecretKey, SignerConfig, WebhookConfig,
+	};
+	use avail_subxt::{
+		api::runtime_types::avail_core::{
+			data_lookup::compact::CompactDataLookup,
+			header::extension::{v1::HeaderExtension, HeaderExtension::V1},
+			kate_commitment::v1::KateCommitment,
+		},
+		config::substrate::Digest,
+		utils::H256,
+	};
+	use test_case::test_case;
+	use tokio::sync::broadcast;
+	use tracing::info_span;
+	use tracing_subscriber::{registry::LookupSpan, Registry};
+
+	#[test]
+	fn secrets_are_redacted() {
+		let mut cfg = RuntimeConfig {
+			secret_key: Some(SecretKey::Seed {
+				seed: "identity-seed".to_string().into(),
+			}),
+			avail_secret_key: Some(AvailSecretKey::try_from("//Alice".to_string()).unwrap()),
+			..Default::default()
+		};
+		cfg.webhooks.push(WebhookConfig {
+			url: "http://localhost/webhook".to_string(),
+			topics: vec![],
+			secret: Some(Secret::new("webhook-secret".to_string())),
+		});
+
+		let debug = format!("{cfg:?}");
+		assert!(!debug.contains("identity-seed"));
+		assert!(!debug.contains("webhook-secret"));
+
+		let serialized = serde_json::to_string(&cfg).unwrap();
+		assert!(serialized.contains(r#""secret_key":{"seed":"<redacted>"}"#));
+		assert!(serialized.contains(r#""avail_secret_key":"<redacted>""#));
+		assert!(serialized.contains(r#""secret":"<redacted>""#));
+		assert!(!serialized.contains("webhook-secret"));
+	}
+
+	#[test]
+	fn effective_config_sources() {
+		let cfg = RuntimeConfig {
+			port: 38000,
+			app_id: Some(1),
+			..Default::default()
+		};
+		let sources = ConfigSources::from([
+			("port".to_string(), ConfigSource::Flag),
+			("app_id".to_string(), ConfigSource::Flag),
+			// Value set in the file is not defaulted, even if it is equal to the default
+			("http_server_port".to_string(), ConfigSource::ConfigFile),
+		]);
+
+		let effective_config = cfg.effective_config(&sources).unwrap();
+		let source = |name: &str| effective_config.get(name).unwrap().source;
+		assert_eq!(source("port"), ConfigSource::Flag);
+		assert_eq!(source("app_id"), ConfigSource::Flag);
+		assert_eq!(source("http_server_port"), ConfigSource::ConfigFile);
+		assert_eq!(source("avail_path"), ConfigSource::Default);
+		assert_eq!(effective_config.get("port").unwrap().value, 38000);
+		assert_eq!(
+			effective_config.get("http_server_port").unwrap().value,
+			7000
+		);
+	}
+
+	#[test_case(true, false => Ok(()) ; "Secret key")]
+	#[test_case(false, false => Err("Signer alice has neither secret_key nor key configured".to_string()) ; "No key")]
+	#[test_case(true, true => Err("Signer alice has both secret_key and key configured".to_string()) ; "Both keys")]
+	fn unlock_keys_checks_signers(secret_key: bool, key: bool) -> Result<(), String> {
+		let mut cfg = RuntimeConfig {
+			signers: vec![SignerConfig {
+				name: "alice".to_string(),
+				secret_key: secret_key
+					.then(|| AvailSecretKey::try_from("//Alice".to_string()).unwrap()),
+				key: key.then(|| "alice".to_string()),
+				app_ids: vec![1],
+			}],
+			..Default::default()
+		};
+		cfg.unlock_keys().map_err(|error| error.to_string())
+	}
+
+	fn header() -> DaHeader {
+		DaHeader {
+			parent_hash: H256::zero(),
+			number: 1,
+			state_root: H256::zero(),
+			extrinsics_root: H256::zero(),
+			digest: Digest { logs: vec![] },
+			extension: V1(HeaderExtension {
+				commitment: KateCommitment {
+					rows: 1,
+					cols: 4,
+					data_root: H256::zero(),
+					commitment: vec![0u8; 96],
+				},
+				app_lookup: CompactDataLookup {
+					size: 1,
+					index: vec![],
+				},
+			}),
+		}
+	}
+
+	#[tokio::test]
+	async fn block_verified_carries_block_span_to_consumers() {
+		let subscriber = tracing_subscriber::registry();
+		let _guard = tracing::subscriber::set_default(subscriber);
+
+		let span = info_span!("block", block_number = 1);
+		let block = span
+			.in_scope(|| BlockVerified::try_from((header(), None)))
+			.unwrap();
+		assert_eq!(block.span.id(), span.id());
+
+		let (sender, mut receiver) = broadcast::channel::<BlockVerified>(1);
+		sender.send(block).unwrap();
+		let block = receiver.recv().await.unwrap();
+
+		// Spans created by consumers are children of the block span
+		let child = info_span!(parent: &block.span, "app_reconstruction");
+		let parent = tracing::dispatcher::get_default(|dispatch| {
+			let registry = dispatch.downcast_ref::<Registry>().unwrap();
+			let child = registry.span(&child.id().unwrap()).unwrap();
+			child.parent().map(|parent| parent.id())
+		});
+		assert_eq!(parent, span.id());
+	}
 }
@@ -23,6 +23,8 @@ use kate_recovery::{
 	data::Cell,
 	matrix::{Dimensions, Position},
 };
+use std::time::{SystemTime, UNIX_EPOCH};
+use subxt::utils::{AccountId32, MultiAddress};
 
 pub fn decode_app_data(data: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
 	let extrisic: AppUncheckedExtrinsic =
@@ -34,6 +36,25 @@ pub fn decode_app_data(data: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
 	}
 }
 
+/// Formats address of the transaction signer, account IDs are SS58 encoded
+pub fn signer_address(address: &MultiAddress<AccountId32, u32>) -> String {
+	match address {
+		MultiAddress::Id(account_id) => account_id.to_string(),
+		MultiAddress::Index(index) => index.to_string(),
+		MultiAddress::Raw(bytes) => format!("0x{}", hex::encode(bytes)),
+		MultiAddress::Address32(bytes) => format!("0x{}", hex::encode(bytes)),
+		MultiAddress::Address20(bytes) => format!("0x{}", hex::encode(bytes)),
+	}
+}
+
+/// Returns current UNIX timestamp in seconds
+pub fn unix_timestamp() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_secs())
+		.unwrap_or_default()
+}
+
 /// Calculates confidence from given number of verified cells
 pub fn calculate_confidence(count: u32) -> f64 {
 	100f64 * (1f64 - 1f64 / 2u32.pow(count) as f64)
@@ -101,10 +122,8 @@ pub fn filter_auth_set_changes(header: &DaHeader) -> Vec<Vec<(AuthorityId, u64)>
 	new_auths
 }
 
-// TODO: Remove unused functions if not needed after next iteration
-
-#[allow(dead_code)]
-fn can_reconstruct(dimensions: Dimensions, columns: &[u16], cells: &[Cell]) -> bool {
+/// Checks if given columns can be reconstructed from given cells
+pub(crate) fn can_reconstruct(dimensions: Dimensions, columns: &[u16], cells: &[Cell]) -> bool {
 	columns.iter().all(|&col| {
 		cells
 			.iter()
@@ -114,6 +133,8 @@ fn can_reconstruct(dimensions: Dimensions, columns: &[u16], cells: &[Cell]) -> b
 	})
 }
 
+// TODO: Remove unused functions if not needed after next iteration
+
 #[allow(dead_code)]
 fn diff_positions(positions: &[Position], cells: &[Cell]) -> Vec<Position> {
 	positions